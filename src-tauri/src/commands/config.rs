@@ -1,7 +1,13 @@
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
+use std::path::Path;
 use std::sync::Arc;
-use crate::config::{AppConfig, ConfigManager, GeneralConfig, PreferenceConfig};
+use std::collections::BTreeMap;
+use crate::config::{AppConfig, ConfigManager, GeneralConfig, PreferenceConfig, SitePreferences};
+use crate::models::{ConfigIssue, IssueSeverity};
+use crate::core::history::HistoryManager;
 use crate::core::logging::LogManager;
+use crate::core::{cookies, secrets};
+use crate::commands::system::binary_present;
 use tracing::{debug, error, info, trace};
 
 #[tauri::command]
@@ -16,18 +22,23 @@ pub fn get_app_config(config_manager: State<'_, Arc<ConfigManager>>) -> AppConfi
 pub fn save_general_config(
     config_manager: State<'_, Arc<ConfigManager>>,
     log_manager: State<'_, LogManager>,
+    history: State<'_, HistoryManager>,
     config: GeneralConfig
 ) -> Result<(), String> {
     info!(target: "commands::config", "Saving general configuration");
 
+    let config = config_manager.update_general(config)?;
+
     // 1. Update Log Level immediately
     debug!(target: "commands::config", "Attempting to update log level to: {}", config.log_level);
     if let Err(e) = log_manager.set_level(&config.log_level) {
         error!(target: "commands::config", "Failed to update log level: {}", e);
     }
 
+    crate::core::transport::network::apply_config(&config);
+    history.set_max_entries(config.max_history_entries);
+
     // 2. Save to Disk
-    config_manager.update_general(config);
     match config_manager.save() {
         Ok(_) => {
             debug!(target: "commands::config", "General config saved successfully");
@@ -40,6 +51,102 @@ pub fn save_general_config(
     }
 }
 
+/// Applies a log level immediately and persists it as the startup default.
+#[tauri::command]
+pub fn set_log_level(
+    config_manager: State<'_, Arc<ConfigManager>>,
+    log_manager: State<'_, LogManager>,
+    level: String
+) -> Result<(), String> {
+    let level = level.trim().to_lowercase();
+    if !LogManager::LEVELS.contains(&level.as_str()) {
+        return Err(format!("Invalid log level '{}'. Expected one of: {}", level, LogManager::LEVELS.join(", ")));
+    }
+
+    log_manager.set_level(&level)?;
+
+    let mut general = config_manager.get_config().general.clone();
+    general.log_level = level;
+    config_manager.update_general(general)?;
+    config_manager.save().map_err(|e| {
+        error!(target: "commands::config", "Failed to persist log level: {}", e);
+        e
+    })
+}
+
+/// Runs every setting check against the saved config without changing anything.
+#[tauri::command]
+pub async fn validate_config(config_manager: State<'_, Arc<ConfigManager>>) -> Result<Vec<ConfigIssue>, String> {
+    let config = config_manager.get_config().general.clone();
+    let issues = tauri::async_runtime::spawn_blocking(move || collect_config_issues(&config))
+        .await
+        .map_err(|e| e.to_string())?;
+    info!(target: "commands::config", count = issues.len(), "Config validation finished");
+    Ok(issues)
+}
+
+fn collect_config_issues(config: &GeneralConfig) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    let mut push = |field: &str, severity: IssueSeverity, message: String| {
+        issues.push(ConfigIssue { field: field.to_string(), severity, message });
+    };
+
+    // Anything saving would reject comes from the same checks `validate` runs.
+    let field_errors = config.field_errors();
+    let download_path_ok = !field_errors.iter().any(|(field, _)| *field == "download_path");
+    for (field, message) in field_errors {
+        push(field, IssueSeverity::Error, message);
+    }
+
+    if let Some(path) = config.download_path.as_deref().filter(|p| !p.trim().is_empty() && download_path_ok) {
+        let path = Path::new(path);
+        if !path.exists() {
+            push("download_path", IssueSeverity::Warning, "Folder does not exist yet and will be created".into());
+        } else if let Err(e) = crate::core::paths::check_writable(path) {
+            push("download_path", IssueSeverity::Error, e);
+        }
+    }
+
+    let temp_dir = crate::core::paths::temp_dir();
+    if temp_dir.exists() {
+        if let Err(e) = crate::core::paths::check_writable(&temp_dir) {
+            push("temp_dir", IssueSeverity::Error, e);
+        }
+    }
+
+    if let Some(format) = config.date_folder_format.as_deref().filter(|f| !f.trim().is_empty()) {
+        if crate::commands::downloader::resolve_date_folder(format, chrono::Local::now()).is_none() {
+            push("date_folder_format", IssueSeverity::Warning, format!("'{}' is not a valid date pattern and will be ignored", format));
+        }
+    }
+
+    if let Some(path) = config.cookies_path.as_deref().filter(|p| !p.trim().is_empty()) {
+        match std::fs::read_to_string(path) {
+            Ok(text) => {
+                if let Err(e) = cookies::validate_netscape_cookies(&text) {
+                    push("cookies_path", IssueSeverity::Warning, e);
+                }
+            },
+            Err(e) => push("cookies_path", IssueSeverity::Error, format!("Cookies file cannot be read: {}", e)),
+        }
+    }
+
+    let bin_dir = crate::core::paths::bin_dir();
+    let exe = |name: &str| if cfg!(windows) { format!("{}.exe", name) } else { name.to_string() };
+    if !binary_present(&exe("yt-dlp"), &bin_dir) {
+        push("binaries", IssueSeverity::Error, "yt-dlp is not installed".into());
+    }
+    if !binary_present(&exe("ffmpeg"), &bin_dir) {
+        push("binaries", IssueSeverity::Warning, "FFmpeg is not installed; merging and audio extraction will fail".into());
+    }
+    let wants_aria = config.use_aria2_for_media || config.transport_engine == "aria2";
+    if wants_aria && !binary_present(&exe("aria2c"), &bin_dir) {
+        push("binaries", IssueSeverity::Warning, "aria2 is selected but not installed".into());
+    }
+
+    issues
+}
+
 #[tauri::command]
 pub fn save_preference_config(
     config_manager: State<'_, Arc<ConfigManager>>,
@@ -57,4 +164,115 @@ pub fn save_preference_config(
             Err(e)
         }
     }
-}
\ No newline at end of file
+}
+
+#[tauri::command]
+pub fn get_site_preferences(config_manager: State<'_, Arc<ConfigManager>>) -> BTreeMap<String, SitePreferences> {
+    config_manager.get_config().site_preferences.clone()
+}
+
+/// Replaces all per-site rules. Domains are validated and normalized before saving.
+#[tauri::command]
+pub fn save_site_preferences(
+    config_manager: State<'_, Arc<ConfigManager>>,
+    rules: BTreeMap<String, SitePreferences>
+) -> Result<(), String> {
+    info!(target: "commands::config", count = rules.len(), "Saving per-site preferences");
+    config_manager.update_site_preferences(rules)?;
+    config_manager.save().map_err(|e| {
+        error!(target: "commands::config", "Failed to save per-site preferences: {}", e);
+        e
+    })
+}
+
+/// Opens `config.json` in the OS default editor. Edits made there take effect
+/// after `reload_config`.
+#[tauri::command]
+pub fn open_config_file(config_manager: State<'_, Arc<ConfigManager>>) -> Result<(), String> {
+    let path = config_manager.file_path().to_path_buf();
+    info!(target: "commands::config", "Opening config file {:?}", path);
+    if !path.exists() {
+        config_manager.save()?;
+    }
+
+    #[cfg(target_os = "windows")]
+    let result = std::process::Command::new("explorer").arg(&path).spawn();
+    #[cfg(target_os = "macos")]
+    let result = std::process::Command::new("open").arg("-t").arg(&path).spawn();
+    #[cfg(target_os = "linux")]
+    let result = std::process::Command::new("xdg-open").arg(&path).spawn();
+
+    result.map(|_| ()).map_err(|e| {
+        error!(target: "commands::config", "Failed to open config file: {}", e);
+        format!("Failed to open config file: {}", e)
+    })
+}
+
+/// Applies edits made to `config.json` outside the app and emits `config-reloaded`.
+#[tauri::command]
+pub fn reload_config(
+    app_handle: AppHandle,
+    config_manager: State<'_, Arc<ConfigManager>>,
+    log_manager: State<'_, LogManager>,
+    history: State<'_, HistoryManager>,
+) -> Result<AppConfig, String> {
+    let config = config_manager.reload()?;
+
+    if let Err(e) = log_manager.set_level(&config.general.log_level) {
+        error!(target: "commands::config", "Failed to apply reloaded log level: {}", e);
+    }
+    crate::core::transport::network::apply_config(&config.general);
+    history.set_max_entries(config.general.max_history_entries);
+
+    let _ = app_handle.emit_all("config-reloaded", (*config).clone());
+    Ok((*config).clone())
+}
+
+#[tauri::command]
+pub fn set_github_token(token: Option<String>) -> Result<(), String> {
+    match token.filter(|t| !t.trim().is_empty()) {
+        Some(t) => {
+            info!(target: "commands::config", "Storing GitHub API token");
+            secrets::set_github_token(&t)
+        },
+        None => {
+            info!(target: "commands::config", "Clearing GitHub API token");
+            secrets::clear_github_token()
+        }
+    }
+}
+
+#[tauri::command]
+pub fn has_github_token() -> bool {
+    secrets::get_github_token().is_some()
+}
+
+/// Stores pasted Netscape-format cookies in the managed file and points `cookies_path` at it.
+#[tauri::command]
+pub fn set_cookies_text(
+    config_manager: State<'_, Arc<ConfigManager>>,
+    text: String
+) -> Result<String, String> {
+    info!(target: "commands::config", "Storing pasted cookies");
+    let path = cookies::write_managed_cookies(&text)?;
+    let path_str = path.to_string_lossy().to_string();
+
+    let mut general = config_manager.get_config().general.clone();
+    general.cookies_path = Some(path_str.clone());
+    config_manager.update_general(general)?;
+    config_manager.save()?;
+    Ok(path_str)
+}
+
+/// Removes the managed cookies file and clears any configured cookie source.
+#[tauri::command]
+pub fn clear_cookies(config_manager: State<'_, Arc<ConfigManager>>) -> Result<(), String> {
+    info!(target: "commands::config", "Clearing cookies");
+    cookies::remove_managed_cookies()?;
+
+    let mut general = config_manager.get_config().general.clone();
+    general.cookies_path = None;
+    general.cookies_from_browser = None;
+    config_manager.update_general(general)?;
+    config_manager.save()
+}
@@ -1,6 +1,6 @@
 use tauri::State;
 use crate::core::history::HistoryManager;
-use tracing::{debug, info};
+use tracing::{debug, info, trace};
 
 #[tauri::command]
 pub async fn get_download_history(
@@ -26,3 +26,10 @@ pub async fn clear_download_history(
     info!(target: "commands::history", "Frontend triggered full history clear");
     history.clear().await
 }
+
+/// Previews the key a URL will be deduplicated and recorded under.
+#[tauri::command]
+pub fn normalize_url(url: String) -> String {
+    trace!(target: "commands::history", "Frontend requested URL normalization preview");
+    HistoryManager::normalize_url(&url)
+}
@@ -1,9 +1,12 @@
 use std::process::Command;
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Manager, State};
 use serde::{Serialize, Deserialize};
 use regex::Regex;
 use crate::core::deps::{self, DependencyProvider}; 
-use std::path::PathBuf;
+use crate::core::paths;
+use crate::config::ConfigManager;
+use crate::core::manager::JobManagerHandle;
+use std::path::{Path, PathBuf};
 use tracing::{info, warn, error, debug, trace};
 use tokio::time::{timeout, Duration};
 use once_cell::sync::Lazy;
@@ -15,6 +18,12 @@ use std::sync::Arc;
 // GLOBAL LOCKS to prevent concurrent dependency installs and handle cancellation
 static INSTALL_LOCKS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
 static CANCEL_FLAGS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+// Extractor list keyed on the yt-dlp version that produced it
+type SupportedSitesCache = Option<(String, Arc<Vec<String>>)>;
+static SUPPORTED_SITES_CACHE: Lazy<Mutex<SupportedSitesCache>> = Lazy::new(|| Mutex::new(None));
+// Last capabilities report; rebuilding it spawns several version checks
+static CAPABILITIES_CACHE: Lazy<Mutex<Option<(std::time::Instant, YtDlpCapabilities)>>> = Lazy::new(|| Mutex::new(None));
+const CAPABILITIES_TTL: Duration = Duration::from_secs(60);
 
 #[derive(Serialize, Clone, Debug)]
 pub struct DependencyInfo {
@@ -27,7 +36,7 @@ pub struct DependencyInfo {
     pub is_latest: bool,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct AppDependencies {
     pub yt_dlp: DependencyInfo,
     pub ffmpeg: DependencyInfo,
@@ -197,10 +206,21 @@ pub async fn analyze_js_runtime(_app_handle: &AppHandle, bin_path: &PathBuf) ->
     }
 }
 
+/// True when both ffmpeg and ffprobe resolve, from the managed bin dir or PATH.
+pub async fn is_ffmpeg_available() -> bool {
+    tokio::task::spawn_blocking(|| {
+        let bin_dir = paths::bin_dir();
+        let ff_exe = if cfg!(windows) { "ffmpeg.exe" } else { "ffmpeg" };
+        let fp_exe = if cfg!(windows) { "ffprobe.exe" } else { "ffprobe" };
+        resolve_binary_info(ff_exe, "-version", &bin_dir).available
+            && resolve_binary_info(fp_exe, "-version", &bin_dir).available
+    }).await.unwrap_or(false)
+}
+
 #[tauri::command]
 pub async fn check_local_deps(_app_handle: AppHandle) -> LocalScanResult {
     debug!(target: "commands::system", "Performing fast local dependency scan");
-    let bin_dir = crate::core::deps::get_common_bin_dir();
+    let bin_dir = paths::bin_dir();
     
     if !bin_dir.exists() {
         let _ = std::fs::create_dir_all(&bin_dir);
@@ -238,15 +258,154 @@ pub async fn check_local_deps(_app_handle: AppHandle) -> LocalScanResult {
 #[tauri::command]
 pub async fn check_ytdlp_update(_app_handle: AppHandle) -> Result<bool, String> {
     info!(target: "commands::system", "Checking for yt-dlp updates...");
-    let bin_dir = crate::core::deps::get_common_bin_dir();
+    let bin_dir = paths::bin_dir();
     let provider = deps::YtDlpProvider;
     provider.check_update_available(&bin_dir).await
 }
 
+fn load_supported_sites(bin_dir: &PathBuf) -> Result<Arc<Vec<String>>, String> {
+    let exec_name = if cfg!(windows) { "yt-dlp.exe" } else { "yt-dlp" };
+    let info = resolve_binary_info(exec_name, "--version", bin_dir);
+    let (path, version) = match (info.path, info.version) {
+        (Some(p), Some(v)) => (p, v),
+        _ => return Err("yt-dlp is not installed".into()),
+    };
+
+    if let Some((cached_version, sites)) = SUPPORTED_SITES_CACHE.lock().unwrap().as_ref() {
+        if *cached_version == version {
+            trace!(target: "commands::system", "Serving supported sites from cache (yt-dlp {})", version);
+            return Ok(sites.clone());
+        }
+    }
+
+    debug!(target: "commands::system", "Listing extractors via yt-dlp {}", version);
+    let output = new_silent_command(&path)
+        .arg("--ignore-config")
+        .arg("--list-extractors")
+        .env("PYTHONUTF8", "1")
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        error!(target: "commands::system", "yt-dlp --list-extractors failed: {}", stderr);
+        return Err(format!("Failed to list extractors: {}", stderr));
+    }
+
+    let sites: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(|l| l.to_string())
+        .collect();
+    let sites = Arc::new(sites);
+
+    info!(target: "commands::system", "Cached {} supported extractors for yt-dlp {}", sites.len(), version);
+    *SUPPORTED_SITES_CACHE.lock().unwrap() = Some((version, sites.clone()));
+    Ok(sites)
+}
+
+#[tauri::command]
+pub async fn get_supported_sites(filter: Option<String>) -> Result<Vec<String>, String> {
+    let bin_dir = paths::bin_dir();
+    let sites = tokio::task::spawn_blocking(move || load_supported_sites(&bin_dir))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    let needle = filter.map(|f| f.trim().to_lowercase()).filter(|f| !f.is_empty());
+    Ok(match needle {
+        Some(n) => sites.iter().filter(|s| s.to_lowercase().contains(&n)).cloned().collect(),
+        None => sites.as_ref().clone(),
+    })
+}
+
+/// The tool environment behind downloads, for support requests and extractor debugging.
+#[derive(Serialize, Clone, Debug)]
+pub struct YtDlpCapabilities {
+    pub ytdlp_version: Option<String>,
+    pub ytdlp_path: Option<String>,
+    /// Runtime yt-dlp uses for JS challenges, with its support flags.
+    pub js_runtime: DependencyInfo,
+    pub ffmpeg_available: bool,
+    pub ffprobe_available: bool,
+    pub ffmpeg_version: Option<String>,
+    /// yt-dlp postprocessors the current settings enable for a default download.
+    pub postprocessors: Vec<String>,
+}
+
+/// Postprocessor names as yt-dlp logs them (`[Merger]`, `[EmbedThumbnail]`, ...).
+fn enabled_postprocessors(config: &crate::config::AppConfig, ffmpeg_available: bool) -> Vec<String> {
+    let prefs = &config.preferences;
+    let general = &config.general;
+    let is_audio = prefs.mode == "audio";
+    let mut pps = Vec::new();
+    if ffmpeg_available {
+        pps.push(if is_audio { "ExtractAudio" } else { "Merger" });
+        pps.push("FixupM3u8");
+    }
+    if prefs.music_metadata && is_audio {
+        pps.push("MetadataParser");
+    }
+    if prefs.embed_metadata || (prefs.music_metadata && is_audio) {
+        pps.push("Metadata");
+    }
+    if prefs.embed_thumbnail {
+        pps.push("EmbedThumbnail");
+    }
+    if general.write_cover_art {
+        pps.push("ThumbnailsConvertor");
+    }
+    pps.push("MoveFiles");
+    pps.into_iter().map(String::from).collect()
+}
+
+/// Reports the yt-dlp version, JS runtime support, ffmpeg/ffprobe presence and enabled
+/// postprocessors. Cached for a minute since every field costs a process spawn.
+#[tauri::command]
+pub async fn get_ytdlp_capabilities(app_handle: AppHandle, refresh: Option<bool>) -> Result<YtDlpCapabilities, String> {
+    if !refresh.unwrap_or(false) {
+        if let Some((at, caps)) = CAPABILITIES_CACHE.lock().unwrap().as_ref() {
+            if at.elapsed() < CAPABILITIES_TTL {
+                return Ok(caps.clone());
+            }
+        }
+    }
+
+    debug!(target: "commands::system", "Collecting yt-dlp capabilities");
+    let bin_dir = paths::bin_dir();
+    let blocking_dir = bin_dir.clone();
+    let (yt_dlp, ffmpeg, ffprobe) = tokio::task::spawn_blocking(move || {
+        let exe = |name: &str| if cfg!(windows) { format!("{}.exe", name) } else { name.to_string() };
+        (
+            resolve_binary_info(&exe("yt-dlp"), "--version", &blocking_dir),
+            resolve_binary_info(&exe("ffmpeg"), "-version", &blocking_dir),
+            resolve_binary_info(&exe("ffprobe"), "-version", &blocking_dir),
+        )
+    }).await.map_err(|e| e.to_string())?;
+    let js_runtime = analyze_js_runtime(&app_handle, &bin_dir).await;
+
+    let ffmpeg_version = ffmpeg.version.as_deref().and_then(|v| {
+        Regex::new(r"ffmpeg version ([^\s]+)").unwrap().captures(v).map(|c| c[1].to_string())
+    });
+    let config = app_handle.state::<Arc<ConfigManager>>().get_config();
+    let caps = YtDlpCapabilities {
+        ytdlp_version: yt_dlp.version,
+        ytdlp_path: yt_dlp.path,
+        js_runtime,
+        ffmpeg_available: ffmpeg.available,
+        ffprobe_available: ffprobe.available,
+        ffmpeg_version,
+        postprocessors: enabled_postprocessors(&config, ffmpeg.available && ffprobe.available),
+    };
+
+    *CAPABILITIES_CACHE.lock().unwrap() = Some((std::time::Instant::now(), caps.clone()));
+    Ok(caps)
+}
+
 #[tauri::command]
 pub async fn check_dependencies(app_handle: AppHandle) -> AppDependencies {
     debug!(target: "commands::system", "Initiating comprehensive dependency check");
-    let bin_dir = crate::core::deps::get_common_bin_dir();
+    let bin_dir = paths::bin_dir();
 
     let (yt_res, ff_res, aria_res, js_res) = tokio::join!(
         async {
@@ -327,6 +486,7 @@ pub async fn install_dependency(app_handle: AppHandle, name: String) -> Result<(
             let _ = app_handle.emit_all("install-progress", deps::InstallProgressPayload {
                 name: String::new(),
                 percentage: 0,
+                phase: deps::InstallPhase::Failed,
                 status: String::new(),
             });
         } else {
@@ -334,11 +494,63 @@ pub async fn install_dependency(app_handle: AppHandle, name: String) -> Result<(
         }
     } else {
         info!(target: "commands::system", "Installation of {} succeeded", name);
+        *CAPABILITIES_CACHE.lock().unwrap() = None;
     }
     
     result
 }
 
+/// Installs `name` unless its binaries are already present. If another caller is
+/// already installing it, waits for that install instead of failing.
+pub async fn ensure_dependency(app_handle: AppHandle, name: &str) -> Result<(), String> {
+    let provider = deps::get_provider(name).ok_or("Unknown dependency")?;
+    let bin_dir = paths::bin_dir();
+    let is_present = || provider.get_binaries().iter().all(|b| bin_dir.join(b).exists());
+
+    loop {
+        if is_present() {
+            return Ok(());
+        }
+        if !INSTALL_LOCKS.lock().unwrap().contains(name) {
+            break;
+        }
+        trace!(target: "commands::system", "Waiting for in-flight install of {}", name);
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+
+    info!(target: "commands::system", "Provisioning missing dependency on demand: {}", name);
+    match install_dependency(app_handle, name.to_string()).await {
+        Err(_) if is_present() => Ok(()),
+        res => res,
+    }
+}
+
+#[tauri::command]
+pub async fn uninstall_dependency(
+    app_handle: AppHandle,
+    manager: State<'_, JobManagerHandle>,
+    name: String
+) -> Result<AppDependencies, String> {
+    info!(target: "commands::system", "Dependency removal requested: {}", name);
+
+    if INSTALL_LOCKS.lock().unwrap().contains(&name) {
+        return Err(format!("{} is currently being installed", name));
+    }
+
+    let active = manager.get_active_count().await;
+    if active > 0 {
+        warn!(target: "commands::system", "Refusing to remove {} while {} download(s) are active", name, active);
+        return Err(format!("Cannot remove {} while {} download(s) are active", name, active));
+    }
+
+    deps::uninstall_dep(&name)?;
+    info!(target: "commands::system", "Removed dependency {}", name);
+
+    let deps = check_dependencies(app_handle.clone()).await;
+    let _ = app_handle.emit_all("dependencies-changed", &deps);
+    Ok(deps)
+}
+
 #[tauri::command]
 pub async fn cancel_dependency_install(name: String) -> Result<(), String> {
     if let Some(flag) = CANCEL_FLAGS.lock().unwrap().get(&name) {
@@ -348,9 +560,65 @@ pub async fn cancel_dependency_install(name: String) -> Result<(), String> {
     Ok(())
 }
 
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct YtDlpUpdatePayload {
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+}
+
+const AUTO_UPDATE_CHECK_TIMEOUT_SECS: u64 = 15;
+
+/// Checks GitHub for a newer yt-dlp and installs it, announcing each step to the UI via
+/// `update-available`, `update-started` and `update-complete`.
+async fn auto_update_yt_dlp(app_handle: AppHandle) {
+    let bin_dir = paths::bin_dir();
+    let provider = deps::YtDlpProvider;
+    let local_path = bin_dir.join(provider.get_binaries()[0]);
+    if !local_path.exists() {
+        trace!(target: "commands::system", "yt-dlp not installed, skipping auto-update");
+        return;
+    }
+
+    let needs_update = match timeout(Duration::from_secs(AUTO_UPDATE_CHECK_TIMEOUT_SECS), provider.check_update_available(&bin_dir)).await {
+        Ok(Ok(needs)) => needs,
+        Ok(Err(e)) => {
+            warn!(target: "commands::system", "yt-dlp auto-update check failed: {}", e);
+            return;
+        },
+        Err(_) => {
+            warn!(target: "commands::system", "yt-dlp auto-update check timed out");
+            return;
+        }
+    };
+    if !needs_update {
+        return;
+    }
+
+    let old_version = deps::get_local_version(&local_path, "--version").map(|v| v.trim().to_string());
+    let _ = app_handle.emit_all("update-available", YtDlpUpdatePayload { old_version: old_version.clone(), new_version: None });
+    let _ = app_handle.emit_all("update-started", YtDlpUpdatePayload { old_version: old_version.clone(), new_version: None });
+    info!(target: "commands::system", old_version = ?old_version, "Auto-updating yt-dlp");
+
+    if let Err(e) = install_dependency(app_handle.clone(), provider.get_name()).await {
+        warn!(target: "commands::system", "yt-dlp auto-update failed: {}", e);
+        return;
+    }
+
+    let new_version = deps::get_local_version(&local_path, "--version").map(|v| v.trim().to_string());
+    info!(target: "commands::system", old_version = ?old_version, new_version = ?new_version, "yt-dlp auto-update complete");
+    let _ = app_handle.emit_all("update-complete", YtDlpUpdatePayload { old_version, new_version });
+}
+
 #[tauri::command]
 pub async fn sync_dependencies(app_handle: AppHandle) -> Result<AppDependencies, String> {
     trace!(target: "commands::system", "Frontend requested dependency sync");
+    let config = app_handle.state::<Arc<ConfigManager>>().get_config();
+    if config.general.offline_mode {
+        debug!(target: "commands::system", "Offline mode enabled, skipping yt-dlp auto-update");
+    } else if config.general.check_for_updates {
+        tokio::spawn(auto_update_yt_dlp(app_handle.clone()));
+    }
     Ok(check_dependencies(app_handle).await)
 }
 
@@ -364,22 +632,96 @@ pub fn open_external_link(app_handle: AppHandle, url: String) -> Result<(), Stri
         })
 }
 
+#[derive(Serialize, Clone, Debug)]
+pub struct DependencyReadiness {
+    pub ready: bool,
+    pub missing: Vec<String>,
+}
+
+/// yt-dlp and FFmpeg are the only hard requirements for a download to succeed.
+fn core_readiness(deps: &AppDependencies) -> DependencyReadiness {
+    let missing: Vec<String> = [&deps.yt_dlp, &deps.ffmpeg]
+        .iter()
+        .filter(|d| !d.available)
+        .map(|d| d.name.clone())
+        .collect();
+    DependencyReadiness { ready: missing.is_empty(), missing }
+}
+
+const READY_CHECK_DEFAULT_TIMEOUT_SECS: u64 = 30;
+const READY_CHECK_MAX_TIMEOUT_SECS: u64 = 300;
+const READY_CHECK_POLL_INTERVAL_MS: u64 = 500;
+
+/// Whether `exec_name` exists in `bin_dir` or on PATH. Only stats files, so it is
+/// cheap enough to poll while an install is still running.
+pub(crate) fn binary_present(exec_name: &str, bin_dir: &Path) -> bool {
+    bin_dir.join(exec_name).exists()
+        || std::env::var_os("PATH")
+            .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(exec_name).is_file()))
+            .unwrap_or(false)
+}
+
+#[tauri::command]
+pub async fn ready_check(app_handle: AppHandle, timeout_secs: Option<u64>) -> DependencyReadiness {
+    let limit = Duration::from_secs(timeout_secs.unwrap_or(READY_CHECK_DEFAULT_TIMEOUT_SECS).min(READY_CHECK_MAX_TIMEOUT_SECS));
+    let started = tokio::time::Instant::now();
+
+    loop {
+        let present = tokio::task::spawn_blocking(|| {
+            let bin_dir = paths::bin_dir();
+            let exe = |name: &str| if cfg!(windows) { format!("{}.exe", name) } else { name.to_string() };
+            ["yt-dlp", "ffmpeg", "ffprobe"].iter().all(|name| binary_present(&exe(name), &bin_dir))
+        }).await.unwrap_or(false);
+        if present || started.elapsed() >= limit {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(READY_CHECK_POLL_INTERVAL_MS)).await;
+    }
+
+    // The full check runs each binary once, so a present-but-broken install still reports missing.
+    let readiness = core_readiness(&check_dependencies(app_handle).await);
+    if !readiness.ready {
+        warn!(target: "commands::system", missing = ?readiness.missing, "Dependencies not ready after {:?}", limit);
+    }
+    readiness
+}
+
 #[tauri::command]
-pub fn close_splash(app_handle: AppHandle) {
+pub async fn close_splash(app_handle: AppHandle, force: Option<bool>, timeout_secs: Option<u64>) -> DependencyReadiness {
+    // Lazy provisioning installs on first download, so missing binaries are expected here.
+    let lazy = app_handle.state::<Arc<ConfigManager>>().get_config().general.is_lazy_provisioning();
+    let force = force.unwrap_or(false) || lazy;
+
+    let readiness = if force {
+        core_readiness(&check_dependencies(app_handle.clone()).await)
+    } else {
+        ready_check(app_handle.clone(), timeout_secs).await
+    };
+
+    if !readiness.ready && !force {
+        // Leave the splash up so the user can retry or explicitly override.
+        return readiness;
+    }
+
     info!(target: "commands::system", "Closing splash screen and focusing main window");
     if let Some(splash) = app_handle.get_window("splashscreen") {
         let _ = splash.close();
     }
     if let Some(main) = app_handle.get_window("main") {
+        if !readiness.ready && !lazy {
+            warn!(target: "commands::system", missing = ?readiness.missing, "Splash closed by user override with missing dependencies");
+            let _ = main.emit("dependencies-not-ready", readiness.clone());
+        }
         let _ = main.show();
         let _ = main.set_focus();
     }
+    readiness
 }
 
 #[tauri::command]
 pub async fn get_latest_app_version() -> Result<String, String> {
     debug!(target: "commands::system", "Fetching latest app version tag from GitHub");
-    match timeout(Duration::from_secs(45), deps::get_latest_github_tag("zqily/multiyt-dlp")).await {
+    match timeout(Duration::from_secs(45), deps::get_latest_github_tag(APP_REPO)).await {
         Ok(res) => res,
         Err(_) => {
             warn!(target: "commands::system", "App version check timed out");
@@ -388,6 +730,36 @@ pub async fn get_latest_app_version() -> Result<String, String> {
     }
 }
 
+const APP_REPO: &str = "zqily/multiyt-dlp";
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AppUpdateInfo {
+    pub current: String,
+    pub latest: String,
+    pub update_available: bool,
+    pub release_url: String,
+}
+
+/// Compares the latest release tag with the running build. Tags that don't contain
+/// an `x.y.z` version never report an update.
+#[tauri::command]
+pub async fn check_app_update() -> Result<AppUpdateInfo, String> {
+    let current = env!("CARGO_PKG_VERSION").to_string();
+    let tag = get_latest_app_version().await?;
+    let latest = tag.trim().trim_start_matches(['v', 'V']).to_string();
+
+    let parseable = Regex::new(r"\d+\.\d+\.\d+").unwrap().is_match(&latest);
+    let update_available = parseable && !deps::compare_semver(&current, &latest);
+    info!(target: "commands::system", current = %current, latest = %latest, update_available, "App update check complete");
+
+    Ok(AppUpdateInfo {
+        release_url: format!("https://github.com/{}/releases/tag/{}", APP_REPO, tag.trim()),
+        current,
+        latest,
+        update_available,
+    })
+}
+
 #[tauri::command]
 pub fn request_attention(app_handle: AppHandle) {
     trace!(target: "commands::system", "Requesting OS user attention (Flash taskbar)");
@@ -429,11 +801,34 @@ pub fn show_in_folder(path: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Effective locations of the app's files, for support and "where is my data" questions.
+#[derive(Serialize, Clone, Debug)]
+pub struct AppPaths {
+    pub config_dir: String,
+    pub log_dir: String,
+    pub history_file: String,
+    pub temp_dir: String,
+    pub bin_dir: String,
+    pub persistence_file: String,
+}
+
+#[tauri::command]
+pub fn get_app_paths() -> AppPaths {
+    let display = |p: PathBuf| p.to_string_lossy().to_string();
+    AppPaths {
+        config_dir: display(paths::app_data_root()),
+        log_dir: display(paths::log_dir()),
+        history_file: display(paths::history_file()),
+        temp_dir: display(paths::temp_dir()),
+        bin_dir: display(paths::bin_dir()),
+        persistence_file: display(paths::persistence_file()),
+    }
+}
+
 #[tauri::command]
 pub fn open_log_folder() -> Result<(), String> {
     info!(target: "commands::system", "Opening log folder");
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    let log_dir = home.join(".multiyt-dlp").join("logs");
+    let log_dir = paths::log_dir();
 
     if !log_dir.exists() {
         std::fs::create_dir_all(&log_dir).map_err(|e| {
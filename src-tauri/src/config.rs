@@ -1,10 +1,14 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 use arc_swap::ArcSwap;
 use tracing::{debug, error, info, trace, warn};
+use crate::models::{DownloadFormatPreset, StartDownloadOptions};
+use crate::core::history::HistoryManager;
+use crate::core::paths;
 
 // --- Configuration Structs ---
 
@@ -67,7 +71,226 @@ pub struct GeneralConfig {
     pub cookies_from_browser: Option<String>,
     pub aria2_prompt_dismissed: bool,
     pub use_concurrent_fragments: bool,
+    /// yt-dlp `-N`. Clamped to 1-16 and scaled down when many downloads run at once.
     pub concurrent_fragments: u32,
+    /// Hand non-fragmented media downloads to aria2c via yt-dlp's external downloader
+    pub use_aria2_for_media: bool,
+    /// Explicit proxy for yt-dlp, aria2c and the app's own HTTP traffic. When unset, system
+    /// proxy env vars apply; an empty string forces a direct connection. A malformed value
+    /// is rejected on save and fails downloads.
+    pub proxy_url: Option<String>,
+    /// Force direct connections, ignoring both `proxy_url` and the environment.
+    pub no_proxy: bool,
+    /// INSECURE: disables TLS certificate verification for yt-dlp and the app's own
+    /// downloads. Only for TLS-intercepting proxies or hosts with broken certificates.
+    pub no_check_certificate: bool,
+    /// Date subfolder under the download path: "year", "year_month", "date" or a strftime pattern.
+    pub date_folder_format: Option<String>,
+    /// Passed to yt-dlp as `--max-filesize` (e.g. "2G"); larger items are skipped.
+    pub max_filesize: Option<String>,
+    /// Duration bounds in seconds; items outside them are skipped by yt-dlp.
+    pub min_duration: Option<u32>,
+    pub max_duration: Option<u32>,
+    /// Skip every update-check network call and rely on local binaries only.
+    pub offline_mode: bool,
+    /// "eager" installs missing binaries from the splash screen; "lazy" defers
+    /// that to the first download that needs them.
+    pub dependency_provisioning: String,
+    /// Engine for dependency downloads: "auto" (aria2 when installed, native fallback),
+    /// "aria2" (no native fallback once aria2 is installed) or "native" (never aria2).
+    pub transport_engine: String,
+    /// Case-insensitive substrings that mark a download error as permanent, dropping
+    /// the job from persistence instead of offering a retry. Terms joined with `&&`
+    /// must all appear.
+    pub fatal_error_patterns: Vec<String>,
+    /// Write `<playlist title>.m3u8` next to a playlist's files once all of its jobs finish.
+    pub write_playlist_m3u: bool,
+    /// Save the thumbnail of a playlist's first finished item as `cover.jpg`, for media
+    /// servers that read folder images. Only applies when the filename template puts the
+    /// playlist in its own folder.
+    pub write_cover_art: bool,
+    /// Replace a `cover.jpg` that is already in the folder.
+    pub overwrite_cover_art: bool,
+    /// Default yt-dlp `-S` sort order, e.g. "res:1080,fps". Refines the preset rather than replacing it.
+    pub format_sort: Option<String>,
+    /// Command run after each completed download, with `{path}` and `{url}` substituted
+    /// into its arguments. This runs an arbitrary program, so it is off (`None`) by default.
+    pub post_download_hook: Option<String>,
+    /// When set, only URLs on these domains (or their subdomains) may be downloaded.
+    /// An empty list is saved as `None`.
+    pub allowed_domains: Option<Vec<String>>,
+    /// Cap on `downloads.txt` lines; the oldest are dropped once it's exceeded. `0` means unlimited.
+    pub max_history_entries: usize,
+    /// Passed as `--age-limit`; items rated above it are skipped. Only effective when the
+    /// site reports an age rating, so unrated content still goes through.
+    pub age_limit: Option<u32>,
+    /// Weight of the newest sample in the per-job speed average, from 0.01 (very smooth)
+    /// to 1.0 (raw, unsmoothed readings).
+    pub speed_smoothing: f64,
+    /// Cancel everything still queued or running as soon as one job fails.
+    pub stop_on_first_error: bool,
+    /// "launch" archives the log on every start; "daily" keeps appending to the same
+    /// log until the date changes.
+    pub log_rotation: String,
+    /// strftime pattern for archived log file names, e.g. "app-%Y-%m-%d.log".
+    pub log_archive_pattern: String,
+    /// Number of archived logs to keep. `0` keeps all of them.
+    pub log_archive_keep: usize,
+    /// Bandwidth cap in bytes per second, e.g. "2M" or "500K", for all downloads together.
+    /// Each yt-dlp process gets `--limit-rate` of an equal share per concurrent slot; a
+    /// job's own limit can lower its share but not exceed it. The app's own dependency
+    /// downloads share the full cap. Empty or `None` means unlimited.
+    pub rate_limit: Option<String>,
+    /// Free space (MB) to leave on the download and temp volumes. Below it no new
+    /// downloads start and running ones are suspended. `0` disables the guard.
+    pub min_free_space_mb: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportEngineChoice {
+    Auto,
+    Aria2,
+    Native,
+}
+
+pub const TRANSPORT_ENGINES: [&str; 3] = ["auto", "aria2", "native"];
+
+pub const LOG_ROTATIONS: [&str; 2] = ["launch", "daily"];
+
+pub const DEPENDENCY_PROVISIONING_MODES: [&str; 2] = ["eager", "lazy"];
+
+/// Highest age rating sites report (some use 21 rather than 18).
+pub const MAX_AGE_LIMIT: u32 = 21;
+
+pub const DEFAULT_FATAL_ERROR_PATTERNS: [&str; 4] = [
+    "video unavailable",
+    "this video has been removed",
+    "fragment && not received",
+    "http error 404",
+];
+
+impl GeneralConfig {
+    pub fn is_lazy_provisioning(&self) -> bool {
+        self.dependency_provisioning.eq_ignore_ascii_case("lazy")
+    }
+
+    pub fn is_daily_log_rotation(&self) -> bool {
+        self.log_rotation.eq_ignore_ascii_case("daily")
+    }
+
+    pub fn transport_engine(&self) -> TransportEngineChoice {
+        match self.transport_engine.to_ascii_lowercase().as_str() {
+            "aria2" => TransportEngineChoice::Aria2,
+            "native" => TransportEngineChoice::Native,
+            _ => TransportEngineChoice::Auto,
+        }
+    }
+
+    /// Checks `url` against `allowed_domains`. Hosts are compared after the same
+    /// normalization the history uses, so `www.` and `m.` variants match.
+    pub fn is_url_allowed(&self, url: &str) -> bool {
+        let Some(allowed) = &self.allowed_domains else { return true };
+        let Some(host) = url::Url::parse(url).ok().and_then(|u| u.host_str().map(|h| h.to_string())) else { return false };
+        let host = HistoryManager::normalize_host(&host);
+        allowed.iter().any(|domain| {
+            let domain = HistoryManager::normalize_host(domain);
+            host == domain || host.ends_with(&format!(".{}", domain))
+        })
+    }
+
+    /// Normalizes the settings and rejects values the app can't use. Shared by saving
+    /// from the UI, reloading `config.json` and startup.
+    pub fn validate(&mut self) -> Result<(), String> {
+        self.rate_limit = self.rate_limit.as_deref().map(str::trim).filter(|l| !l.is_empty()).map(str::to_string);
+        self.max_filesize = self.max_filesize.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string);
+        self.format_sort = self.format_sort.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string);
+        self.proxy_url = self.proxy_url.as_deref().map(|p| p.trim().to_string());
+        self.transport_engine = self.transport_engine.trim().to_lowercase();
+        self.log_rotation = self.log_rotation.trim().to_lowercase();
+        self.dependency_provisioning = self.dependency_provisioning.trim().to_lowercase();
+        self.log_archive_pattern = self.log_archive_pattern.trim().to_string();
+        if let Some(domains) = self.allowed_domains.as_mut() {
+            domains.retain(|d| !d.trim().is_empty());
+            for d in domains.iter_mut() {
+                *d = d.trim().to_lowercase();
+            }
+        }
+        // A cleared list means "no restriction", not an allowlist that blocks everything.
+        if self.allowed_domains.as_ref().is_some_and(|d| d.is_empty()) {
+            self.allowed_domains = None;
+        }
+
+        if let Some((_, e)) = self.field_errors().into_iter().next() {
+            return Err(e);
+        }
+
+        // Store the canonical spelling; both already passed `field_errors`.
+        self.rate_limit = self.rate_limit.as_deref().map(crate::core::process::validate_rate_limit).transpose()?;
+        self.format_sort = self.format_sort.as_deref().map(crate::core::process::validate_format_sort).transpose()?;
+        Ok(())
+    }
+
+    /// Every setting `validate` would reject, as `(field, message)`, without changing
+    /// anything. `validate` fails on the first; the settings diagnostics list them all.
+    pub fn field_errors(&self) -> Vec<(&'static str, String)> {
+        fn set(value: &Option<String>) -> Option<&str> {
+            value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+        }
+        let mut errors = Vec::new();
+        let mut check = |field: &'static str, result: Result<(), String>| {
+            if let Err(e) = result {
+                errors.push((field, e));
+            }
+        };
+
+        if let Some(path) = set(&self.download_path) {
+            check("download_path", validate_download_path(Path::new(path)));
+        }
+        if let Some(limit) = set(&self.rate_limit) {
+            check("rate_limit", crate::core::process::validate_rate_limit(limit).map(drop));
+        }
+        if let Some(size) = set(&self.max_filesize).filter(|s| !crate::core::process::is_valid_filesize(s)) {
+            check("max_filesize", Err(format!("Invalid max file size '{}'. Use a number with an optional K, M, G or T suffix.", size)));
+        }
+        if let Some(sort) = set(&self.format_sort) {
+            check("format_sort", crate::core::process::validate_format_sort(sort).map(drop));
+        }
+        if let Some(hook) = set(&self.post_download_hook) {
+            check("post_download_hook", crate::core::hooks::validate_hook(hook));
+        }
+        if let Some(proxy) = set(&self.proxy_url) {
+            check("proxy_url", crate::core::transport::network::validate_proxy_url(proxy).map(drop));
+        }
+        if self.age_limit.is_some_and(|limit| limit > MAX_AGE_LIMIT) {
+            check("age_limit", Err(format!("Age limit must be between 0 and {}", MAX_AGE_LIMIT)));
+        }
+        let engine = self.transport_engine.trim().to_lowercase();
+        if !TRANSPORT_ENGINES.contains(&engine.as_str()) {
+            check("transport_engine", Err(format!("Unknown transport engine '{}'", engine)));
+        }
+        let rotation = self.log_rotation.trim().to_lowercase();
+        if !LOG_ROTATIONS.contains(&rotation.as_str()) {
+            check("log_rotation", Err(format!("Unknown log rotation '{}'", rotation)));
+        }
+        let provisioning = self.dependency_provisioning.trim().to_lowercase();
+        if !DEPENDENCY_PROVISIONING_MODES.contains(&provisioning.as_str()) {
+            check("dependency_provisioning", Err(format!("Unknown dependency provisioning mode '{}'", provisioning)));
+        }
+        check("log_archive_pattern", crate::core::logging::validate_archive_pattern(self.log_archive_pattern.trim()));
+        // 0 means "no bound", the same as the filter built at spawn time.
+        if let (Some(min), Some(max)) = (self.min_duration.filter(|&m| m > 0), self.max_duration.filter(|&m| m > 0)) {
+            if min > max {
+                check("min_duration", Err(format!("Minimum duration ({}s) is longer than the maximum duration ({}s)", min, max)));
+            }
+        }
+        if !(0.01..=1.0).contains(&self.speed_smoothing) {
+            check("speed_smoothing", Err("Speed smoothing must be between 0.01 and 1".into()));
+        }
+        if let Some(bad) = self.allowed_domains.iter().flatten().find(|d| !d.trim().is_empty() && !is_valid_domain(d.trim())) {
+            check("allowed_domains", Err(format!("'{}' is not a valid domain", bad)));
+        }
+        errors
+    }
 }
 
 impl Default for GeneralConfig {
@@ -85,6 +308,33 @@ impl Default for GeneralConfig {
             aria2_prompt_dismissed: false,
             use_concurrent_fragments: false,
             concurrent_fragments: 4,
+            use_aria2_for_media: false,
+            proxy_url: None,
+            no_proxy: false,
+            no_check_certificate: false,
+            date_folder_format: None,
+            max_filesize: None,
+            min_duration: None,
+            max_duration: None,
+            offline_mode: false,
+            dependency_provisioning: "eager".to_string(),
+            transport_engine: "auto".to_string(),
+            fatal_error_patterns: DEFAULT_FATAL_ERROR_PATTERNS.iter().map(|p| p.to_string()).collect(),
+            write_playlist_m3u: false,
+            write_cover_art: false,
+            overwrite_cover_art: false,
+            format_sort: None,
+            post_download_hook: None,
+            allowed_domains: None,
+            max_history_entries: 100_000,
+            age_limit: None,
+            speed_smoothing: 0.3,
+            stop_on_first_error: false,
+            log_rotation: "launch".to_string(),
+            log_archive_pattern: crate::core::logging::DEFAULT_LOG_ARCHIVE_PATTERN.to_string(),
+            log_archive_keep: 10,
+            rate_limit: None,
+            min_free_space_mb: 500,
         }
     }
 }
@@ -101,6 +351,7 @@ pub struct PreferenceConfig {
     pub embed_thumbnail: bool,
     pub live_from_start: bool,
     pub enable_playlist_selection: bool,
+    pub music_metadata: bool,
 }
 
 impl Default for PreferenceConfig {
@@ -115,7 +366,66 @@ impl Default for PreferenceConfig {
             embed_thumbnail: false,
             live_from_start: false,
             enable_playlist_selection: true,
+            music_metadata: false,
+        }
+    }
+}
+
+impl PreferenceConfig {
+    /// Drops preset strings the backend no longer recognises, so a stale config
+    /// can't feed an unknown preset into `start_download`.
+    pub fn sanitize(&mut self) {
+        let defaults = Self::default();
+        if DownloadFormatPreset::try_from(self.format_preset.as_str()).is_err() {
+            warn!(target: "config", "Unknown format_preset '{}', resetting", self.format_preset);
+            self.format_preset = defaults.format_preset;
+        }
+        if !DownloadFormatPreset::try_from(self.video_preset.as_str()).is_ok_and(|p| !p.is_audio()) {
+            self.video_preset = defaults.video_preset;
         }
+        if !DownloadFormatPreset::try_from(self.audio_preset.as_str()).is_ok_and(|p| p.is_audio()) {
+            self.audio_preset = defaults.audio_preset;
+        }
+    }
+
+    /// Records `preset` as the active choice and as the remembered preset for its mode.
+    /// Returns whether anything changed.
+    pub fn remember_preset(&mut self, preset: &DownloadFormatPreset) -> bool {
+        let value = preset.to_preset_string().to_string();
+        let (mode, slot) = if preset.is_audio() {
+            ("audio", &mut self.audio_preset)
+        } else {
+            ("video", &mut self.video_preset)
+        };
+        let changed = self.format_preset != value || *slot != value || self.mode != mode;
+        *slot = value.clone();
+        self.format_preset = value;
+        self.mode = mode.to_string();
+        changed
+    }
+}
+
+/// Per-site overrides of `PreferenceConfig`; fields left unset keep the global value.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SitePreferences {
+    pub format_preset: Option<String>,
+    pub video_resolution: Option<String>,
+    pub embed_metadata: Option<bool>,
+    pub embed_thumbnail: Option<bool>,
+    pub live_from_start: Option<bool>,
+    pub music_metadata: Option<bool>,
+}
+
+impl SitePreferences {
+    /// Overrides the options the caller sent with the ones this rule sets.
+    pub fn apply_to(&self, options: &mut StartDownloadOptions) {
+        if let Some(preset) = &self.format_preset { options.format_preset = preset.clone(); }
+        if let Some(resolution) = &self.video_resolution { options.video_resolution = resolution.clone(); }
+        if let Some(v) = self.embed_metadata { options.embed_metadata = v; }
+        if let Some(v) = self.embed_thumbnail { options.embed_thumbnail = v; }
+        if let Some(v) = self.live_from_start { options.live_from_start = Some(v); }
+        if let Some(v) = self.music_metadata { options.music_metadata = Some(v); }
     }
 }
 
@@ -125,6 +435,8 @@ pub struct AppConfig {
     pub general: GeneralConfig,
     pub preferences: PreferenceConfig,
     pub window: WindowConfig,
+    /// Keyed by domain, normalized the way the history normalizes hosts.
+    pub site_preferences: BTreeMap<String, SitePreferences>,
 }
 
 impl Default for AppConfig {
@@ -133,10 +445,104 @@ impl Default for AppConfig {
             general: GeneralConfig::default(),
             preferences: PreferenceConfig::default(),
             window: WindowConfig::default(),
+            site_preferences: BTreeMap::new(),
         }
     }
 }
 
+impl AppConfig {
+    /// The rule for `url`'s host, or for its closest parent domain (a `youtube.com` rule
+    /// also covers `music.youtube.com`).
+    pub fn site_preferences_for(&self, url: &str) -> Option<&SitePreferences> {
+        if self.site_preferences.is_empty() { return None; }
+        let host = url::Url::parse(url).ok()?.host_str().map(HistoryManager::normalize_host)?;
+        let mut candidate = host.as_str();
+        loop {
+            if let Some(rule) = self.site_preferences.get(candidate) {
+                return Some(rule);
+            }
+            candidate = candidate.split_once('.')?.1;
+        }
+    }
+
+    /// [`GeneralConfig::validate`] plus the per-site rules.
+    pub fn validate(&mut self) -> Result<(), String> {
+        self.general.validate()?;
+        self.site_preferences = normalize_site_preferences(std::mem::take(&mut self.site_preferences))?;
+        Ok(())
+    }
+}
+
+/// Checks each rule's domain and preset and keys it by the normalized host, so
+/// lookups match `www.` and `m.` variants.
+fn normalize_site_preferences(rules: BTreeMap<String, SitePreferences>) -> Result<BTreeMap<String, SitePreferences>, String> {
+    let mut normalized = BTreeMap::new();
+    for (domain, rule) in rules {
+        if !is_valid_domain(&domain) {
+            return Err(format!("'{}' is not a valid domain", domain));
+        }
+        if let Some(preset) = &rule.format_preset {
+            DownloadFormatPreset::try_from(preset.as_str())?;
+        }
+        normalized.insert(HistoryManager::normalize_host(&domain), rule);
+    }
+    Ok(normalized)
+}
+
+/// Accepts bare hostnames such as `youtube.com` or `media.example.org`.
+pub fn is_valid_domain(domain: &str) -> bool {
+    let domain = domain.trim().trim_end_matches('.');
+    !domain.is_empty() && domain.len() <= 253 && domain.split('.').all(|label| {
+        !label.is_empty() && label.len() <= 63
+            && !label.starts_with('-') && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
+
+/// Resolves symlinks and `..` even when the tail of `path` doesn't exist yet, by
+/// canonicalizing the deepest existing ancestor and re-appending the rest. The tail
+/// has no symlinks to follow, so its `.` and `..` are resolved lexically.
+fn canonicalize_lenient(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    while !existing.exists() {
+        match (existing.components().next_back(), existing.parent()) {
+            (Some(last), Some(parent)) => {
+                rest.push(last);
+                existing = parent;
+            },
+            _ => break,
+        }
+    }
+    let mut resolved = fs::canonicalize(existing).unwrap_or_else(|_| existing.to_path_buf());
+    for part in rest.into_iter().rev() {
+        match part {
+            Component::ParentDir => { resolved.pop(); },
+            Component::CurDir => {},
+            other => resolved.push(other),
+        }
+    }
+    resolved
+}
+
+/// Downloads must never land in the app's own directory: temp cleanup deletes
+/// everything under `temp_downloads`, and the config dir holds app state.
+pub fn validate_download_path(path: &Path) -> Result<(), String> {
+    let app_dir = canonicalize_lenient(&paths::app_data_root());
+    let temp_dir = canonicalize_lenient(&paths::temp_dir());
+    let target = canonicalize_lenient(path);
+
+    if target.starts_with(&temp_dir) {
+        warn!(target: "config", "Rejected download path inside the temp directory: {:?}", target);
+        return Err("Download folder cannot be inside the temporary downloads folder; its contents are deleted automatically.".into());
+    }
+    if target.starts_with(&app_dir) {
+        warn!(target: "config", "Rejected download path inside the app directory: {:?}", target);
+        return Err(format!("Download folder cannot be inside the app's data folder ({}).", app_dir.display()));
+    }
+    Ok(())
+}
+
 // --- Manager (Lock‑Free via arc_swap) ---
 
 pub struct ConfigManager {
@@ -147,9 +553,8 @@ pub struct ConfigManager {
 impl ConfigManager {
     pub fn new() -> Self {
         info!(target: "config", "Initializing ConfigManager");
-        let home = dirs::home_dir().expect("Could not find home directory");
-        let config_dir = home.join(".multiyt-dlp");
-        let file_path = config_dir.join("config.json");
+        let config_dir = paths::app_data_root();
+        let file_path = paths::config_file();
 
         if !config_dir.exists() {
             trace!(target: "config", "Creating config directory at {:?}", config_dir);
@@ -167,7 +572,12 @@ impl ConfigManager {
                 AppConfig::default()
             });
 
+        if let Err(e) = config.validate() {
+            // Nowhere to report it yet; validate_config surfaces it once the UI is up.
+            error!(target: "config", "Loaded config has invalid settings: {}", e);
+        }
         config.window.sanitize();
+        config.preferences.sanitize();
 
         let manager = Self {
             config: ArcSwap::from_pointee(config),
@@ -276,17 +686,45 @@ impl ConfigManager {
         Ok(())
     }
 
+    /// Re-reads the file from disk and replaces the in-memory config. A file that
+    /// doesn't parse or fails validation leaves the current config in place and returns
+    /// the reason; neither the backup nor defaults are substituted for the user's edit.
+    pub fn reload(&self) -> Result<Arc<AppConfig>, String> {
+        info!(target: "config", "Reloading configuration from disk");
+        let kept = |e: String| {
+            error!(target: "config", "Reload failed; keeping the current in-memory config: {}", e);
+            format!("{}: {}. The current settings were kept.", self.file_path.display(), e)
+        };
+        let content = fs::read_to_string(&self.file_path).map_err(|e| kept(e.to_string()))?;
+        let mut config: AppConfig = serde_json::from_str(&content).map_err(|e| kept(e.to_string()))?;
+        config.validate().map_err(kept)?;
+
+        config.window.sanitize();
+        config.preferences.sanitize();
+
+        let config = Arc::new(config);
+        self.config.store(config.clone());
+        Ok(config)
+    }
+
+    pub fn file_path(&self) -> &Path {
+        &self.file_path
+    }
+
     /// Returns an `Arc<AppConfig>` – cheap, lock‑free, and wait‑free.
     pub fn get_config(&self) -> Arc<AppConfig> {
         self.config.load_full()
     }
 
-    pub fn update_general(&self, general: GeneralConfig) {
+    /// Stores `general` after [`GeneralConfig::validate`] and returns the normalized copy.
+    pub fn update_general(&self, mut general: GeneralConfig) -> Result<GeneralConfig, String> {
+        general.validate()?;
         debug!(target: "config", "Updating General Configuration");
         let current = self.config.load_full();
         let mut new_cfg = (*current).clone();
-        new_cfg.general = general;
+        new_cfg.general = general.clone();
         self.config.store(Arc::new(new_cfg));
+        Ok(general)
     }
 
     pub fn update_preferences(&self, prefs: PreferenceConfig) {
@@ -297,6 +735,17 @@ impl ConfigManager {
         self.config.store(Arc::new(new_cfg));
     }
 
+    /// Replaces the per-site rules after checking each domain and preset.
+    pub fn update_site_preferences(&self, rules: BTreeMap<String, SitePreferences>) -> Result<(), String> {
+        let normalized = normalize_site_preferences(rules)?;
+        debug!(target: "config", count = normalized.len(), "Updating per-site preferences");
+        let current = self.config.load_full();
+        let mut new_cfg = (*current).clone();
+        new_cfg.site_preferences = normalized;
+        self.config.store(Arc::new(new_cfg));
+        Ok(())
+    }
+
     pub fn update_window(&self, mut window: WindowConfig) {
         trace!(target: "config", "Updating Window Configuration");
         window.sanitize(); 
@@ -305,4 +754,102 @@ impl ConfigManager {
         new_cfg.window = window;
         self.config.store(Arc::new(new_cfg));
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonicalize_lenient_resolves_parent_dirs_in_missing_tail() {
+        let root = tempfile::tempdir().unwrap();
+        let base = fs::canonicalize(root.path()).unwrap();
+
+        let path = base.join("missing").join("..").join("child").join(".").join("file");
+        assert_eq!(canonicalize_lenient(&path), base.join("child").join("file"));
+
+        let path = base.join("missing").join("deeper").join("..").join("..");
+        assert_eq!(canonicalize_lenient(&path), base);
+    }
+
+    #[test]
+    fn remembered_presets_go_to_their_mode_slot() {
+        let mut prefs = PreferenceConfig::default();
+
+        assert!(prefs.remember_preset(&DownloadFormatPreset::AudioFlac));
+        assert_eq!((prefs.mode.as_str(), prefs.audio_preset.as_str(), prefs.video_preset.as_str()), ("audio", "audio_flac", "best"));
+
+        assert!(prefs.remember_preset(&DownloadFormatPreset::BestMkv));
+        assert_eq!((prefs.mode.as_str(), prefs.audio_preset.as_str(), prefs.video_preset.as_str()), ("video", "audio_flac", "best_mkv"));
+        assert_eq!(prefs.format_preset, "best_mkv");
+
+        assert!(!prefs.remember_preset(&DownloadFormatPreset::BestMkv));
+    }
+
+    #[test]
+    fn sanitize_resets_presets_stored_in_the_wrong_slot() {
+        let mut prefs = PreferenceConfig {
+            format_preset: "best_avi".into(),
+            video_preset: "audio_mp3".into(),
+            audio_preset: "best_mp4".into(),
+            ..Default::default()
+        };
+        prefs.sanitize();
+        let defaults = PreferenceConfig::default();
+        assert_eq!(prefs.format_preset, defaults.format_preset);
+        assert_eq!(prefs.video_preset, defaults.video_preset);
+        assert_eq!(prefs.audio_preset, defaults.audio_preset);
+    }
+
+    #[test]
+    fn validate_fails_on_the_first_field_error() {
+        let mut general = GeneralConfig {
+            transport_engine: " Native ".into(),
+            rate_limit: Some(" 2m ".into()),
+            ..Default::default()
+        };
+        assert!(general.field_errors().is_empty());
+        general.validate().unwrap();
+        assert_eq!(general.transport_engine, "native");
+
+        general.age_limit = Some(MAX_AGE_LIMIT + 1);
+        general.log_rotation = "weekly".into();
+        let fields: Vec<&str> = general.field_errors().into_iter().map(|(field, _)| field).collect();
+        assert_eq!(fields, ["age_limit", "log_rotation"]);
+        assert_eq!(general.clone().validate().unwrap_err(), general.field_errors()[0].1);
+
+        let mut general = GeneralConfig { min_duration: Some(600), max_duration: Some(60), ..Default::default() };
+        assert!(general.validate().unwrap_err().contains("Minimum duration"));
+        general.max_duration = Some(0);
+        general.validate().unwrap();
+
+        general.dependency_provisioning = " LAZY ".into();
+        general.validate().unwrap();
+        assert!(general.is_lazy_provisioning());
+        general.dependency_provisioning = "later".into();
+        assert!(general.validate().unwrap_err().contains("provisioning"));
+    }
+
+    #[test]
+    fn site_rules_override_only_what_they_set() {
+        let mut options: StartDownloadOptions = serde_json::from_value(serde_json::json!({
+            "formatPreset": "best", "videoResolution": "1080p", "embedMetadata": true,
+            "embedThumbnail": false, "filenameTemplate": "%(title)s.%(ext)s", "liveFromStart": false,
+        })).unwrap();
+        let rule = SitePreferences { format_preset: Some("audio_mp3".into()), live_from_start: Some(true), ..Default::default() };
+        rule.apply_to(&mut options);
+        assert_eq!(options.format_preset, "audio_mp3");
+        assert_eq!(options.video_resolution, "1080p");
+        assert!(options.embed_metadata);
+        assert_eq!(options.live_from_start, Some(true));
+        assert_eq!(options.music_metadata, None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn validate_download_path_rejects_temp_dir_behind_missing_parent() {
+        let temp_dir = paths::temp_dir();
+        let sneaky = Path::new("/nonexistent-multiyt-dlp/..").join(temp_dir.strip_prefix("/").unwrap());
+        assert!(validate_download_path(&sneaky).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use std::fs;
+use std::path::PathBuf;
+use tracing::{debug, trace};
+
+const NETSCAPE_HEADERS: [&str; 2] = ["# Netscape HTTP Cookie File", "# HTTP Cookie File"];
+
+/// Checks the header and that every data line has the seven tab-separated fields
+/// (domain, subdomains flag, path, secure flag, expiry, name, value).
+pub fn validate_netscape_cookies(text: &str) -> Result<(), String> {
+    let mut lines = text.lines().map(str::trim_end).filter(|l| !l.trim().is_empty());
+
+    let header = lines.next().ok_or("Cookie text is empty")?;
+    if !NETSCAPE_HEADERS.iter().any(|h| header.trim().starts_with(h)) {
+        return Err("Missing '# Netscape HTTP Cookie File' header".into());
+    }
+
+    let mut cookie_count = 0;
+    for (i, line) in lines.enumerate() {
+        // "#HttpOnly_" prefixes a real cookie; any other '#' line is a comment.
+        if line.starts_with('#') && !line.starts_with("#HttpOnly_") {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 7 {
+            return Err(format!("Line {} has {} fields, expected 7 tab-separated fields", i + 2, fields.len()));
+        }
+        if !matches!(fields[1], "TRUE" | "FALSE") || !matches!(fields[3], "TRUE" | "FALSE") {
+            return Err(format!("Line {} has invalid TRUE/FALSE flags", i + 2));
+        }
+        if fields[4].parse::<i64>().is_err() {
+            return Err(format!("Line {} has a non-numeric expiry", i + 2));
+        }
+        cookie_count += 1;
+    }
+
+    if cookie_count == 0 {
+        return Err("No cookies found after the header".into());
+    }
+    trace!(target: "core::cookies", "Validated {} cookies", cookie_count);
+    Ok(())
+}
+
+/// Validates and writes `text` to the managed cookie file, returning its path.
+pub fn write_managed_cookies(text: &str) -> Result<PathBuf, String> {
+    validate_netscape_cookies(text)?;
+    let path = crate::core::paths::cookies_file();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let mut content = text.replace("\r\n", "\n");
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    fs::write(&path, content).map_err(|e| format!("Failed to write cookies file: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o600));
+    }
+
+    debug!(target: "core::cookies", "Wrote managed cookies file to {:?}", path);
+    Ok(path)
+}
+
+pub fn remove_managed_cookies() -> Result<(), String> {
+    let path = crate::core::paths::cookies_file();
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| format!("Failed to remove cookies file: {}", e))?;
+        debug!(target: "core::cookies", "Removed managed cookies file");
+    }
+    Ok(())
+}
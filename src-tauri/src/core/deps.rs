@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::fs::{self, File};
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -7,7 +8,8 @@ use tauri::{AppHandle, Manager};
 use serde::{Serialize, Deserialize};
 use std::process::Command;
 use async_trait::async_trait;
-use crate::core::transport::download_file_robust;
+use crate::core::transport::{download_file_robust, network};
+use crate::core::paths;
 use regex::Regex;
 use tokio::time::{timeout, Duration, sleep};
 use tracing::{debug, error, info, trace, warn};
@@ -53,16 +55,33 @@ const DENO_SIZE: u64 = 116_000_000;
 const BUN_SIZE: u64 = 97_700_000;
 const ARIA2_SIZE: u64 = 5_380_000;
 
+/// Stage of a dependency install, so the UI doesn't have to guess from `status` text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallPhase {
+    Downloading,
+    Extracting,
+    Verifying,
+    Done,
+    Failed,
+}
+
 #[derive(Clone, Serialize)]
 pub struct InstallProgressPayload {
     pub name: String,
     pub percentage: u64,
+    pub phase: InstallPhase,
+    /// Human-readable detail for the current phase.
     pub status: String,
 }
 
-pub fn get_common_bin_dir() -> PathBuf {
-    let base = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
-    base.join("Syntax Free").join("Common").join("bin")
+pub(crate) fn emit_install_progress(app_handle: &AppHandle, name: &str, percentage: u64, phase: InstallPhase, status: impl Into<String>) {
+    let _ = app_handle.emit_all("install-progress", InstallProgressPayload {
+        name: name.to_string(),
+        percentage,
+        phase,
+        status: status.into(),
+    });
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -75,7 +94,7 @@ pub struct SfsAppEntry {
 pub fn register_sfs_app() {
     std::thread::spawn(|| {
         trace!(target: "core::deps", "Registering SFS Application Presence");
-        let common_dir = match get_common_bin_dir().parent() {
+        let common_dir = match paths::bin_dir().parent() {
             Some(p) => p.to_path_buf(),
             Option::None => return,
         };
@@ -133,7 +152,7 @@ pub fn is_any_sfs_app_running() -> bool {
 
     trace!(target: "core::deps", "Checking for other running SFS applications (Windows)");
 
-    let common_dir = match get_common_bin_dir().parent() {
+    let common_dir = match paths::bin_dir().parent() {
         Some(p) => p.to_path_buf(),
         Option::None => return false,
     };
@@ -239,16 +258,24 @@ pub trait DependencyProvider: Send + Sync {
 }
 
 pub async fn get_latest_github_tag(repo: &str) -> Result<String, String> {
+    if network::is_offline() {
+        debug!(target: "core::deps", "Offline mode enabled, not querying GitHub for {}", repo);
+        return Err("Offline mode is enabled".into());
+    }
     debug!(target: "core::deps", "Fetching latest GitHub release tag for repo: {}", repo);
-    let client = reqwest::Client::builder()
+    let builder = reqwest::Client::builder()
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .connect_timeout(Duration::from_secs(10))
+        .connect_timeout(Duration::from_secs(10));
+    let client = network::apply_network_options(builder, &network::current())?
         .build()
         .map_err(|e| {
             error!(target: "core::deps", "Failed to build HTTP client: {}", e);
             e.to_string()
         })?;
 
+    // Authenticated API calls get 5000 req/h instead of the anonymous 60.
+    let mut token = crate::core::secrets::get_github_token();
+
     let url = format!("https://github.com/{}/releases/latest", repo);
     let mut last_error = String::new();
     let max_retries = 3;
@@ -279,31 +306,42 @@ pub async fn get_latest_github_tag(repo: &str) -> Result<String, String> {
         }
 
         let api_url = format!("https://api.github.com/repos/{}/releases/latest", repo);
-        match timeout(Duration::from_secs(10), client.get(&api_url)
-            .header(reqwest::header::ACCEPT, "application/vnd.github.v3+json")
-            .send()).await 
-        {
-            Ok(Ok(resp)) => {
-                if resp.status().is_success() {
-                    if let Ok(json) = resp.json::<serde_json::Value>().await {
-                        if let Some(tag) = json.get("tag_name").and_then(|v| v.as_str()) {
-                            debug!(target: "core::deps", "Successfully resolved tag {} via JSON API", tag);
-                            return Ok(tag.to_string());
+        loop {
+            let mut request = client.get(&api_url)
+                .header(reqwest::header::ACCEPT, "application/vnd.github.v3+json");
+            if let Some(ref t) = token {
+                request = request.bearer_auth(t);
+            }
+
+            match timeout(Duration::from_secs(10), request.send()).await {
+                Ok(Ok(resp)) => {
+                    if resp.status() == reqwest::StatusCode::UNAUTHORIZED && token.is_some() {
+                        warn!(target: "core::deps", "GitHub token was rejected (401). Falling back to unauthenticated API access.");
+                        token = None;
+                        continue;
+                    }
+                    if resp.status().is_success() {
+                        if let Ok(json) = resp.json::<serde_json::Value>().await {
+                            if let Some(tag) = json.get("tag_name").and_then(|v| v.as_str()) {
+                                debug!(target: "core::deps", "Successfully resolved tag {} via JSON API", tag);
+                                return Ok(tag.to_string());
+                            }
                         }
+                    } else {
+                        last_error = format!("API HTTP Status {}", resp.status());
+                        warn!(target: "core::deps", "API tag fetch failed: {}", last_error);
                     }
-                } else {
-                    last_error = format!("API HTTP Status {}", resp.status());
-                    warn!(target: "core::deps", "API tag fetch failed: {}", last_error);
-                }
-            },
-            Ok(Err(e)) => {
-                last_error = format!("API Network Error: {}", e);
-                warn!(target: "core::deps", "{}", last_error);
-            },
-            Err(_) => {
-                last_error = "API Connection Timeout".to_string();
-                warn!(target: "core::deps", "{}", last_error);
-            },
+                },
+                Ok(Err(e)) => {
+                    last_error = format!("API Network Error: {}", e);
+                    warn!(target: "core::deps", "{}", last_error);
+                },
+                Err(_) => {
+                    last_error = "API Connection Timeout".to_string();
+                    warn!(target: "core::deps", "{}", last_error);
+                },
+            }
+            break;
         }
 
         if attempt < max_retries - 1 {
@@ -372,67 +410,108 @@ pub fn get_local_version(path: &PathBuf, arg: &str) -> Option<String> {
     Some(ver)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    Zip,
+    TarXz,
+    TarBz2,
+}
+
+impl ArchiveKind {
+    fn from_path(path: &Path) -> Option<Self> {
+        let name = path.to_string_lossy().to_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.xz") {
+            Some(Self::TarXz)
+        } else if name.ends_with(".tar.bz2") {
+            Some(Self::TarBz2)
+        } else {
+            None
+        }
+    }
+}
+
+/// Writes one archive member to `target_dir/<file_name>`, flattening whatever
+/// directory layout the archive used.
+fn write_extracted_binary<R: Read>(reader: &mut R, target_dir: &Path, file_name: &std::ffi::OsStr) -> Result<PathBuf, String> {
+    let final_target = target_dir.join(file_name);
+    let tmp_target = final_target.with_extension("tmp_extract");
+
+    let mut out_file = File::create(&tmp_target).map_err(|e| e.to_string())?;
+    std::io::copy(reader, &mut out_file).map_err(|e| e.to_string())?;
+    drop(out_file);
+
+    replace_dependency_robust_sync(&tmp_target, &final_target).map_err(|e| e.to_string())?;
+    Ok(final_target)
+}
+
+/// Copies every member of a zip whose file name is in `binary_names` into `target_dir`.
+/// Pure I/O: no `AppHandle`, so it can run against in-memory fixtures.
+fn extract_zip_finding_binary<R: Read + std::io::Seek>(reader: R, target_dir: &Path, binary_names: &[&str]) -> Result<Vec<PathBuf>, String> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(|e| e.to_string())?;
+    let mut extracted = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(|e| e.to_string())?;
+        if file.is_dir() {
+            continue;
+        }
+        let outpath = match file.enclosed_name() {
+            Some(path) => path.to_owned(),
+            Option::None => continue,
+        };
+        if let Some(file_name) = outpath.file_name() {
+            let file_name_str = file_name.to_string_lossy();
+            if binary_names.contains(&file_name_str.as_ref()) {
+                debug!(target: "core::deps", "Found match '{}' inside Zip archive", file_name_str);
+                extracted.push(write_extracted_binary(&mut file, target_dir, file_name)?);
+            }
+        }
+    }
+    Ok(extracted)
+}
+
+/// Tar counterpart of [`extract_zip_finding_binary`]; `reader` is the already-decompressed stream.
+fn extract_tar_finding_binary<R: Read>(reader: R, target_dir: &Path, binary_names: &[&str]) -> Result<Vec<PathBuf>, String> {
+    let mut archive = tar::Archive::new(reader);
+    let mut extracted = Vec::new();
+    for entry in archive.entries().map_err(|e| e.to_string())? {
+        let mut file = entry.map_err(|e| e.to_string())?;
+        if !file.header().entry_type().is_file() {
+            continue;
+        }
+        let path = file.path().map_err(|e| e.to_string())?.into_owned();
+        if let Some(file_name) = path.file_name() {
+            let file_name_str = file_name.to_string_lossy();
+            if binary_names.contains(&file_name_str.as_ref()) {
+                debug!(target: "core::deps", "Found match '{}' inside Tar archive", file_name_str);
+                extracted.push(write_extracted_binary(&mut file, target_dir, file_name)?);
+            }
+        }
+    }
+    Ok(extracted)
+}
+
 fn extract_archive_finding_binary(archive_path: &PathBuf, target_dir: &PathBuf, binary_names: &[&str]) -> Result<(), String> {
     info!(target: "core::deps", "Extracting binary components from archive {:?}", archive_path);
+    let kind = ArchiveKind::from_path(archive_path)
+        .ok_or_else(|| format!("Unknown archive format: {:?}", archive_path))?;
     let file = File::open(archive_path).map_err(|e| {
         error!(target: "core::deps", "Failed to open archive: {}", e);
         e.to_string()
     })?;
-    let path_str = archive_path.to_string_lossy().to_lowercase();
-    
-    if path_str.ends_with(".zip") {
-        let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i).map_err(|e| e.to_string())?;
-            let outpath = match file.enclosed_name() {
-                Some(path) => path.to_owned(),
-                Option::None => continue,
-            };
-            if let Some(file_name) = outpath.file_name() {
-                let file_name_str = file_name.to_string_lossy();
-                if binary_names.contains(&file_name_str.as_ref()) {
-                    debug!(target: "core::deps", "Found match '{}' inside Zip archive", file_name_str);
-                    let final_target = target_dir.join(file_name);
-                    let tmp_target = final_target.with_extension("tmp_extract");
-                    
-                    let mut out_file = File::create(&tmp_target).map_err(|e| e.to_string())?;
-                    std::io::copy(&mut file, &mut out_file).map_err(|e| e.to_string())?;
-                    
-                    replace_dependency_robust_sync(&tmp_target, &final_target).map_err(|e| e.to_string())?;
-                }
-            }
-        }
-    } else if path_str.ends_with(".tar.xz") || path_str.ends_with(".tar.bz2") {
-        let is_xz = path_str.ends_with(".tar.xz");
-        
-        let mut archive = if is_xz {
-            let decompressed = xz2::read::XzDecoder::new(file);
-            tar::Archive::new(decompressed)
-        } else {
-            return Err("Unsupported archive format for decompression logic".into());
-        };
 
-        for entry in archive.entries().map_err(|e| e.to_string())? {
-            let mut file = entry.map_err(|e| e.to_string())?;
-            let path = file.path().map_err(|e| e.to_string())?.into_owned();
-            if let Some(file_name) = path.file_name() {
-                let file_name_str = file_name.to_string_lossy();
-                if binary_names.contains(&file_name_str.as_ref()) {
-                    debug!(target: "core::deps", "Found match '{}' inside Tar archive", file_name_str);
-                    let final_target = target_dir.join(file_name);
-                    let tmp_target = final_target.with_extension("tmp_extract");
-                    
-                    let mut out_file = File::create(&tmp_target).map_err(|e| e.to_string())?;
-                    std::io::copy(&mut file, &mut out_file).map_err(|e| e.to_string())?;
-                    
-                    replace_dependency_robust_sync(&tmp_target, &final_target).map_err(|e| e.to_string())?;
-                }
-            }
-        }
-    } else {
-        warn!(target: "core::deps", "Unknown archive format: {:?}", archive_path);
+    let extracted = match kind {
+        ArchiveKind::Zip => extract_zip_finding_binary(file, target_dir, binary_names)?,
+        ArchiveKind::TarXz => extract_tar_finding_binary(xz2::read::XzDecoder::new(file), target_dir, binary_names)?,
+        ArchiveKind::TarBz2 => extract_tar_finding_binary(bzip2::read::MultiBzDecoder::new(file), target_dir, binary_names)?,
+    };
+
+    if extracted.is_empty() {
+        error!(target: "core::deps", "None of {:?} found in {:?}", binary_names, archive_path);
+        return Err(format!("Archive did not contain any of: {}", binary_names.join(", ")));
     }
-    
+    debug!(target: "core::deps", "Extracted {:?}", extracted);
     Ok(())
 }
 
@@ -450,6 +529,7 @@ impl DependencyProvider for YtDlpProvider {
         debug!(target: "core::deps::ytdlp", "Checking for updates");
         let local_path = bin_dir.join(self.get_binaries()[0]);
         if !local_path.exists() { return Ok(true); }
+        if network::is_offline() { return Ok(false); }
         let remote_tag = get_latest_github_tag("yt-dlp/yt-dlp").await?;
         let res = get_local_version(&local_path, "--version").map_or(true, |v| v.trim() != remote_tag.trim());
         info!(target: "core::deps::ytdlp", "Update available: {}", res);
@@ -470,11 +550,7 @@ impl DependencyProvider for FfmpegProvider {
         download_file_robust(FFMPEG_URL, archive_path.clone(), &self.get_name(), &app_handle, Some(FFMPEG_SIZE), cancel_flag.clone()).await.map_err(|e| e.to_string())?;
         if cancel_flag.load(Ordering::Relaxed) { return Err("Cancelled".to_string()); }
 
-        let _ = app_handle.emit_all("install-progress", InstallProgressPayload {
-            name: self.get_name(),
-            percentage: 100,
-            status: "Extracting FFmpeg...".to_string()
-        });
+        emit_install_progress(&app_handle, &self.get_name(), 100, InstallPhase::Extracting, "Extracting FFmpeg...");
         
         extract_archive_finding_binary(&archive_path, &target_dir, &self.get_binaries())?;
         let _ = fs::remove_file(&archive_path);
@@ -484,13 +560,10 @@ impl DependencyProvider for FfmpegProvider {
             if cancel_flag.load(Ordering::Relaxed) { return Err("Cancelled".to_string()); }
             let ffprobe_archive = std::env::temp_dir().join("ffprobe_tmp.zip");
             let ffprobe_url = "https://evermeet.cx/ffmpeg/getrelease/ffprobe/zip";
-            let _ = app_handle.emit_all("install-progress", InstallProgressPayload {
-                name: "FFprobe".to_string(),
-                percentage: 50,
-                status: "Downloading FFprobe...".to_string()
-            });
+            emit_install_progress(&app_handle, "FFprobe", 50, InstallPhase::Downloading, "Downloading FFprobe...");
             if download_file_robust(ffprobe_url, ffprobe_archive.clone(), "FFprobe", &app_handle, Option::None, cancel_flag.clone()).await.is_ok() {
                 if !cancel_flag.load(Ordering::Relaxed) {
+                    emit_install_progress(&app_handle, "FFprobe", 100, InstallPhase::Extracting, "Extracting FFprobe...");
                     let _ = extract_archive_finding_binary(&ffprobe_archive, &target_dir, &self.get_binaries());
                 }
                 let _ = fs::remove_file(&ffprobe_archive);
@@ -512,6 +585,7 @@ impl DependencyProvider for DenoProvider {
         let archive_path = std::env::temp_dir().join("deno.zip");
         download_file_robust(DENO_URL, archive_path.clone(), &self.get_name(), &app_handle, Some(DENO_SIZE), cancel_flag.clone()).await.map_err(|e| e.to_string())?;
         if cancel_flag.load(Ordering::Relaxed) { return Err("Cancelled".to_string()); }
+        emit_install_progress(&app_handle, &self.get_name(), 100, InstallPhase::Extracting, "Extracting Deno...");
         extract_archive_finding_binary(&archive_path, &target_dir, &self.get_binaries())?;
         let _ = fs::remove_file(archive_path);
         Ok(())
@@ -520,6 +594,7 @@ impl DependencyProvider for DenoProvider {
         debug!(target: "core::deps::deno", "Checking for updates");
         let local_path = bin_dir.join(self.get_binaries()[0]);
         if !local_path.exists() { return Ok(true); }
+        if network::is_offline() { return Ok(false); }
         let remote_tag = get_latest_github_tag("denoland/deno").await?;
         let clean_remote = remote_tag.replace('v', "");
         Ok(get_local_version(&local_path, "--version").map_or(true, |v| !v.contains(&clean_remote)))
@@ -536,6 +611,7 @@ impl DependencyProvider for BunProvider {
         let archive_path = std::env::temp_dir().join("bun.zip");
         download_file_robust(BUN_URL, archive_path.clone(), &self.get_name(), &app_handle, Some(BUN_SIZE), cancel_flag.clone()).await.map_err(|e| e.to_string())?;
         if cancel_flag.load(Ordering::Relaxed) { return Err("Cancelled".to_string()); }
+        emit_install_progress(&app_handle, &self.get_name(), 100, InstallPhase::Extracting, "Extracting Bun...");
         extract_archive_finding_binary(&archive_path, &target_dir, &self.get_binaries())?;
         let _ = fs::remove_file(archive_path);
         Ok(())
@@ -544,6 +620,7 @@ impl DependencyProvider for BunProvider {
         debug!(target: "core::deps::bun", "Checking for updates");
         let local_path = bin_dir.join(self.get_binaries()[0]);
         if !local_path.exists() { return Ok(true); }
+        if network::is_offline() { return Ok(false); }
         let remote_tag = get_latest_github_tag("oven-sh/bun").await?;
         let clean_remote = remote_tag.replace('v', "");
         Ok(get_local_version(&local_path, "--version").map_or(true, |v| !v.contains(&clean_remote)))
@@ -561,7 +638,8 @@ impl DependencyProvider for Aria2Provider {
         let archive_path = std::env::temp_dir().join(format!("aria2_tmp.{}", ext));
         download_file_robust(ARIA2_URL, archive_path.clone(), &self.get_name(), &app_handle, Some(ARIA2_SIZE), cancel_flag.clone()).await.map_err(|e| e.to_string())?;
         if cancel_flag.load(Ordering::Relaxed) { return Err("Cancelled".to_string()); }
-        
+        emit_install_progress(&app_handle, &self.get_name(), 100, InstallPhase::Extracting, "Extracting Aria2...");
+
         match extract_archive_finding_binary(&archive_path, &target_dir, &self.get_binaries()) {
             Ok(_) => {
                 let _ = fs::remove_file(archive_path);
@@ -591,14 +669,128 @@ pub fn get_provider(name: &str) -> Option<Box<dyn DependencyProvider>> {
     }
 }
 
+/// Deletes every binary the provider manages from the shared bin dir.
+pub fn uninstall_dep(name: &str) -> Result<(), String> {
+    let provider = get_provider(name).ok_or("Unknown dependency")?;
+    let bin_dir = paths::bin_dir();
+    for binary in provider.get_binaries() {
+        let path = bin_dir.join(binary);
+        if !path.exists() {
+            trace!(target: "core::deps", "Nothing to remove at {:?}", path);
+            continue;
+        }
+        std::fs::remove_file(&path).map_err(|e| {
+            error!(target: "core::deps", "Failed to remove {:?}: {}", path, e);
+            if e.kind() == std::io::ErrorKind::PermissionDenied {
+                format!("{} is locked by another process and could not be removed", binary)
+            } else {
+                format!("Failed to remove {}: {}", binary, e)
+            }
+        })?;
+        debug!(target: "core::deps", "Removed {:?}", path);
+    }
+    Ok(())
+}
+
 pub async fn install_dep(name: String, app_handle: AppHandle, cancel_flag: Arc<AtomicBool>) -> Result<(), String> {
     let provider = get_provider(&name).ok_or("Unknown dependency")?;
-    let bin_dir = get_common_bin_dir();
+    let bin_dir = paths::bin_dir();
     if !bin_dir.exists() { 
         fs::create_dir_all(&bin_dir).map_err(|e| {
             error!(target: "core::deps", "Failed to create binary directory: {}", e);
             e.to_string()
         })?; 
     }
-    provider.install(app_handle, bin_dir, cancel_flag).await
+    let display_name = provider.get_name();
+    let result = provider.install(app_handle.clone(), bin_dir.clone(), cancel_flag.clone()).await.and_then(|_| {
+        emit_install_progress(&app_handle, &display_name, 100, InstallPhase::Verifying, "Verifying...");
+        // Only the primary binary is mandatory; secondary ones (e.g. ffprobe on macOS) are best-effort.
+        let primary = bin_dir.join(provider.get_binaries()[0]);
+        if primary.exists() {
+            Ok(())
+        } else {
+            error!(target: "core::deps", "{} missing after install: {:?}", display_name, primary);
+            Err(format!("{} was not found after installation", display_name))
+        }
+    });
+
+    match &result {
+        Ok(_) => emit_install_progress(&app_handle, &display_name, 100, InstallPhase::Done, "Installed"),
+        // Cancellation is reported by the caller as a reset event.
+        Err(_) if cancel_flag.load(Ordering::Relaxed) => {},
+        Err(e) => emit_install_progress(&app_handle, &display_name, 0, InstallPhase::Failed, e.clone()),
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+
+    const PAYLOAD: &[u8] = b"#!/bin/sh\necho ffmpeg\n";
+
+    fn zip_fixture(entries: &[(&str, &[u8])]) -> Cursor<Vec<u8>> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in entries {
+            writer.start_file(*name, zip::write::FileOptions::default()).unwrap();
+            writer.write_all(data).unwrap();
+        }
+        Cursor::new(writer.finish().unwrap().into_inner())
+    }
+
+    fn tar_fixture(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *data).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn zip_binary_in_nested_dir_is_flattened_into_target() {
+        let target = tempfile::tempdir().unwrap();
+        let archive = zip_fixture(&[
+            ("ffmpeg-7.0-essentials/README.txt", b"readme"),
+            ("ffmpeg-7.0-essentials/bin/ffmpeg", PAYLOAD),
+        ]);
+
+        let extracted = extract_zip_finding_binary(archive, target.path(), &["ffmpeg"]).unwrap();
+
+        assert_eq!(extracted, vec![target.path().join("ffmpeg")]);
+        assert_eq!(fs::read(target.path().join("ffmpeg")).unwrap(), PAYLOAD);
+        assert!(!target.path().join("ffmpeg-7.0-essentials").exists());
+        assert!(!target.path().join("README.txt").exists());
+    }
+
+    #[test]
+    fn tar_binary_in_nested_dir_is_flattened_into_target() {
+        let target = tempfile::tempdir().unwrap();
+        let archive = tar_fixture(&[
+            ("aria2-1.37.0/doc/README", b"readme"),
+            ("aria2-1.37.0/bin/aria2c", PAYLOAD),
+        ]);
+
+        let extracted = extract_tar_finding_binary(archive.as_slice(), target.path(), &["aria2c"]).unwrap();
+
+        assert_eq!(extracted, vec![target.path().join("aria2c")]);
+        assert_eq!(fs::read(target.path().join("aria2c")).unwrap(), PAYLOAD);
+        assert!(!target.path().join("aria2-1.37.0").exists());
+    }
+
+    #[test]
+    fn archive_without_the_binary_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("bin");
+        fs::create_dir(&target).unwrap();
+        let archive_path = dir.path().join("ffmpeg.zip");
+        fs::write(&archive_path, zip_fixture(&[("ffmpeg-7.0/bin/ffplay", PAYLOAD)]).into_inner()).unwrap();
+
+        assert!(extract_archive_finding_binary(&archive_path, &target, &["ffmpeg"]).is_err());
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 0);
+    }
 }
@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use tokio::sync::{mpsc, oneshot};
 use tokio::fs::{OpenOptions, File};
@@ -6,6 +7,10 @@ use tokio::io::{AsyncWriteExt, BufWriter};
 use url::Url;
 use tracing::{debug, error, info, trace, warn};
 
+/// Once past the cap, the file may grow by this fraction of it before the next trim,
+/// so a full history isn't rewritten on every add.
+const TRIM_SLACK_DIVISOR: usize = 10;
+
 #[derive(Debug)]
 enum HistoryMessage {
     Add(String),
@@ -18,13 +23,30 @@ enum HistoryMessage {
 pub struct HistoryManager {
     cache: Arc<RwLock<HashSet<String>>>,
     sender: mpsc::Sender<HistoryMessage>,
+    max_entries: Arc<AtomicUsize>,
+}
+
+fn non_empty_lines(content: &str) -> Vec<&str> {
+    content.lines().filter(|l| !l.trim().is_empty()).collect()
+}
+
+/// The newest `max_entries` lines, or `None` when nothing needs dropping. `0` means unlimited.
+fn trimmed_tail<'a>(lines: &[&'a str], max_entries: usize) -> Option<Vec<&'a str>> {
+    if max_entries == 0 || lines.len() <= max_entries {
+        return None;
+    }
+    Some(lines[lines.len() - max_entries..].to_vec())
+}
+
+fn build_cache(lines: &[&str]) -> HashSet<String> {
+    lines.iter().map(|l| HistoryManager::normalize_url(l)).collect()
 }
 
 impl HistoryManager {
-    pub fn new() -> Self {
+    /// `max_entries` caps `downloads.txt`; the oldest lines are dropped first. `0` disables the cap.
+    pub fn new(max_entries: usize) -> Self {
         info!(target: "core::history", "Initializing HistoryManager");
-        let home = dirs::home_dir().expect("Could not find home directory");
-        let file_path = home.join(".multiyt-dlp").join("downloads.txt");
+        let file_path = crate::core::paths::history_file();
 
         if let Some(parent) = file_path.parent() {
             if !parent.exists() {
@@ -34,36 +56,44 @@ impl HistoryManager {
         }
 
         let cache = Arc::new(RwLock::new(HashSet::new()));
+        let mut entry_count = 0;
         
         if file_path.exists() {
              debug!(target: "core::history", "Loading existing history from {:?}", file_path);
-             if let Ok(file) = std::fs::File::open(&file_path) {
-                let reader = std::io::BufReader::new(file);
-                let mut c = cache.write().unwrap();
-                use std::io::BufRead;
-                let mut count = 0;
-                for line in reader.lines() {
-                    if let Ok(l) = line {
-                        if !l.trim().is_empty() {
-                            c.insert(Self::normalize_url(&l));
-                            count += 1;
+             match std::fs::read_to_string(&file_path) {
+                Ok(content) => {
+                    let mut lines = non_empty_lines(&content);
+                    if let Some(kept) = trimmed_tail(&lines, max_entries) {
+                        info!(target: "core::history", "History has {} entries, trimming to the newest {}", lines.len(), max_entries);
+                        let mut trimmed = kept.join("\n");
+                        trimmed.push('\n');
+                        if let Err(e) = std::fs::write(&file_path, trimmed) {
+                            warn!(target: "core::history", "Failed to write trimmed history: {}", e);
                         }
+                        lines = kept;
                     }
+                    entry_count = lines.len();
+                    *cache.write().unwrap() = build_cache(&lines);
+                    debug!(target: "core::history", "Loaded {} URLs into history cache", entry_count);
+                },
+                Err(e) => {
+                    warn!(target: "core::history", "History file exists but could not be opened for read: {}", e);
                 }
-                debug!(target: "core::history", "Loaded {} URLs into history cache", count);
-             } else {
-                 warn!(target: "core::history", "History file exists but could not be opened for read");
              }
         } else {
              trace!(target: "core::history", "No existing history file found");
         }
 
+        let max_entries = Arc::new(AtomicUsize::new(max_entries));
+        let actor_max_entries = max_entries.clone();
+
         let (tx, mut rx) = mpsc::channel(100);
         let actor_path = file_path.clone();
         let actor_cache = cache.clone();
         
         tauri::async_runtime::spawn(async move {
             debug!(target: "core::history", "History background actor started");
+            let mut entry_count = entry_count;
             let mut writer: Option<BufWriter<File>> = match OpenOptions::new()
                 .create(true)
                 .append(true)
@@ -91,6 +121,18 @@ impl HistoryManager {
                                 if let Ok(mut c) = actor_cache.write() {
                                     c.insert(normalized);
                                 }
+                                entry_count += 1;
+
+                                let cap = actor_max_entries.load(Ordering::Relaxed);
+                                if cap > 0 && entry_count > cap + cap / TRIM_SLACK_DIVISOR {
+                                    drop(writer.take());
+                                    if let Some(count) = Self::trim_file(&actor_path, &actor_cache, cap).await {
+                                        entry_count = count;
+                                    }
+                                    if let Ok(f) = OpenOptions::new().create(true).append(true).open(&actor_path).await {
+                                        writer = Some(BufWriter::with_capacity(8192, f));
+                                    }
+                                }
                             }
                         } else {
                             warn!(target: "core::history", "Writer not available, attempting to reopen file");
@@ -109,14 +151,10 @@ impl HistoryManager {
                                      error!(target: "core::history", "Failed to overwrite history file: {}", e);
                                      let _ = resp.send(Err(e.to_string()));
                                  } else {
-                                     let mut new_set = HashSet::new();
-                                     for line in content.lines() {
-                                         if !line.trim().is_empty() {
-                                             new_set.insert(Self::normalize_url(line));
-                                         }
-                                     }
+                                     let lines = non_empty_lines(&content);
+                                     entry_count = lines.len();
                                      if let Ok(mut c) = actor_cache.write() {
-                                         *c = new_set;
+                                         *c = build_cache(&lines);
                                      }
                                      let _ = resp.send(Ok(()));
                                  }
@@ -139,6 +177,7 @@ impl HistoryManager {
                                 if let Ok(mut c) = actor_cache.write() {
                                     c.clear();
                                 }
+                                entry_count = 0;
                                 let _ = resp.send(Ok(()));
                             },
                             Err(e) => {
@@ -172,7 +211,55 @@ impl HistoryManager {
 
         Self {
             cache,
-            sender: tx
+            sender: tx,
+            max_entries,
+        }
+    }
+
+    /// Applies a new cap; the file is trimmed on the next add that pushes it over.
+    pub fn set_max_entries(&self, max_entries: usize) {
+        self.max_entries.store(max_entries, Ordering::Relaxed);
+    }
+
+    /// Rewrites `path` with only its newest `max_entries` lines and rebuilds `cache` to
+    /// match. Returns the remaining entry count, or `None` if the file couldn't be rewritten.
+    async fn trim_file(path: &std::path::Path, cache: &RwLock<HashSet<String>>, max_entries: usize) -> Option<usize> {
+        let content = match tokio::fs::read_to_string(path).await {
+            Ok(s) => s,
+            Err(e) => {
+                warn!(target: "core::history", "Failed to read history for trimming: {}", e);
+                return None;
+            }
+        };
+        let lines = non_empty_lines(&content);
+        let Some(kept) = trimmed_tail(&lines, max_entries) else { return Some(lines.len()) };
+
+        let mut trimmed = kept.join("\n");
+        trimmed.push('\n');
+        let tmp = path.with_extension("txt.tmp");
+        if let Err(e) = tokio::fs::write(&tmp, trimmed).await {
+            warn!(target: "core::history", "Failed to write trimmed history: {}", e);
+            return None;
+        }
+        if let Err(e) = tokio::fs::rename(&tmp, path).await {
+            warn!(target: "core::history", "Failed to replace history with trimmed copy: {}", e);
+            return None;
+        }
+
+        if let Ok(mut c) = cache.write() {
+            *c = build_cache(&kept);
+        }
+        info!(target: "core::history", "Trimmed history from {} to {} entries", lines.len(), kept.len());
+        Some(kept.len())
+    }
+
+    /// Canonical form of a host for comparisons: lowercase, no `www.`, and the
+    /// mobile/short YouTube hosts folded into `youtube.com`.
+    pub fn normalize_host(host: &str) -> String {
+        let host = host.trim().trim_end_matches('.').to_lowercase();
+        match host.as_str() {
+            "youtu.be" | "m.youtube.com" => "youtube.com".to_string(),
+            h => h.strip_prefix("www.").unwrap_or(h).to_string(),
         }
     }
 
@@ -192,11 +279,11 @@ impl HistoryManager {
                         url = u;
                     }
                 }
-            } else if host == "m.youtube.com" {
-                let _ = url.set_host(Some("youtube.com"));
-            } else if host.starts_with("www.") {
-                let new_host = &host[4..];
-                let _ = url.set_host(Some(new_host));
+            } else {
+                let new_host = Self::normalize_host(&host);
+                if new_host != host {
+                    let _ = url.set_host(Some(&new_host));
+                }
             }
         }
 
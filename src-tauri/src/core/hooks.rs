@@ -0,0 +1,117 @@
+use std::process::Stdio;
+use tokio::time::{timeout, Duration};
+use tracing::{debug, info, warn};
+
+/// A hook that hasn't exited by then is killed so it can't pile up behind the queue.
+pub const HOOK_TIMEOUT_SECS: u64 = 300;
+/// Combined stdout/stderr kept on the job record.
+const MAX_HOOK_OUTPUT_LEN: usize = 4096;
+
+pub struct HookOutcome {
+    pub exit_code: Option<i32>,
+    pub output: String,
+}
+
+/// Splits a command template into argv the way a POSIX shell would for plain words,
+/// 'single quotes' and "double quotes" (with `\"` and `\\` escapes). No shell is
+/// involved, so substituted values can never be interpreted as syntax.
+fn split_template(template: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(ch) => current.push(ch),
+                        None => return Err("Unterminated single quote in hook".into()),
+                    }
+                }
+            },
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.peek(), Some('"') | Some('\\')) => current.push(chars.next().unwrap_or_default()),
+                        Some(ch) => current.push(ch),
+                        None => return Err("Unterminated double quote in hook".into()),
+                    }
+                }
+            },
+            c if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            },
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+/// Validates a hook template without running it.
+pub fn validate_hook(template: &str) -> Result<(), String> {
+    match split_template(template)?.first() {
+        Some(_) => Ok(()),
+        None => Err("Hook command is empty".into()),
+    }
+}
+
+/// Runs the user's post-download hook with `{path}` and `{url}` substituted into
+/// each argument. This executes an arbitrary program chosen by the user.
+pub async fn run_post_download_hook(template: &str, path: &str, url: &str) -> Result<HookOutcome, String> {
+    let args: Vec<String> = split_template(template)?
+        .into_iter()
+        .map(|a| a.replace("{path}", path).replace("{url}", url))
+        .collect();
+    let (program, rest) = args.split_first().ok_or("Hook command is empty")?;
+
+    let mut cmd = tokio::process::Command::new(program);
+    cmd.args(rest)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    #[cfg(target_os = "windows")]
+    {
+        cmd.creation_flags(0x08000000);
+    }
+
+    info!(target: "core::hooks", program = %program, "Running post-download hook");
+    let output = match timeout(Duration::from_secs(HOOK_TIMEOUT_SECS), cmd.output()).await {
+        Ok(Ok(out)) => out,
+        Ok(Err(e)) => return Err(format!("Failed to start hook: {}", e)),
+        Err(_) => {
+            warn!(target: "core::hooks", "Post-download hook timed out after {}s", HOOK_TIMEOUT_SECS);
+            return Err(format!("Hook timed out after {} seconds", HOOK_TIMEOUT_SECS));
+        }
+    };
+
+    let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    let text = text.trim();
+    let mut cut = text.len().min(MAX_HOOK_OUTPUT_LEN);
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+
+    debug!(target: "core::hooks", exit_code = ?output.status.code(), "Post-download hook finished");
+    Ok(HookOutcome {
+        exit_code: output.status.code(),
+        output: text[..cut].to_string(),
+    })
+}
@@ -1,5 +1,5 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{info, error};
 use tracing_subscriber::{
     fmt, 
@@ -9,7 +9,9 @@ use tracing_subscriber::{
     EnvFilter
 };
 use tracing_appender::non_blocking::WorkerGuard;
-use chrono::Local;
+use chrono::{DateTime, Local};
+use chrono::format::{Item, StrftimeItems};
+use crate::config::GeneralConfig;
 
 // --- Structs ---
 
@@ -30,8 +32,7 @@ pub struct LogPaths {
 
 impl LogPaths {
     pub fn new() -> Option<Self> {
-        let home = dirs::home_dir()?;
-        let log_dir = home.join(".multiyt-dlp").join("logs");
+        let log_dir = crate::core::paths::log_dir();
         let latest_log = log_dir.join("latest.log");
         let archive_dir = log_dir.join("archive");
         
@@ -66,9 +67,65 @@ pub fn register_panic_hook() {
 
 // --- Rotation Logic ---
 
+pub const DEFAULT_LOG_ARCHIVE_PATTERN: &str = "app-%Y-%m-%d_%H-%M-%S.log";
+
+/// Checks a `log_archive_pattern`: a strftime pattern for a plain file name.
+pub fn validate_archive_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.trim().is_empty() {
+        return Err("Archive name pattern is empty".to_string());
+    }
+    if pattern.contains(['/', '\\']) {
+        return Err("Archive name pattern must not contain path separators".to_string());
+    }
+    // chrono panics when displaying an invalid specifier, so validate up front.
+    if StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error)) {
+        return Err(format!("'{}' is not a valid date pattern", pattern));
+    }
+    Ok(())
+}
+
+/// Archive file name for a log last written at `time`. Falls back to the default
+/// pattern when the configured one is invalid.
+fn archive_name(pattern: &str, time: DateTime<Local>) -> String {
+    let pattern = match validate_archive_pattern(pattern) {
+        Ok(()) => pattern.trim(),
+        Err(e) => {
+            eprintln!("Ignoring log_archive_pattern: {}", e);
+            DEFAULT_LOG_ARCHIVE_PATTERN
+        }
+    };
+    time.format(pattern).to_string()
+}
+
+/// Adds a `-N` counter before the extension so a coarse pattern (e.g. per day)
+/// never overwrites an earlier archive.
+fn unique_archive_path(archive_dir: &Path, name: &str) -> PathBuf {
+    let candidate = archive_dir.join(name);
+    if !candidate.exists() {
+        return candidate;
+    }
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, format!(".{}", ext)),
+        _ => (name, String::new()),
+    };
+    (1..)
+        .map(|n| archive_dir.join(format!("{}-{}{}", stem, n, ext)))
+        .find(|p| !p.exists())
+        .unwrap_or(candidate)
+}
+
+/// True when `latest.log` was last written today, so daily rotation keeps appending to it.
+fn is_from_today(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(|t| DateTime::<Local>::from(t).date_naive() == Local::now().date_naive())
+        .unwrap_or(false)
+}
+
 /// Rotates 'latest.log' to 'archive/...' and cleans up old files.
+/// With daily rotation, a log from today is left in place for `LogManager::init` to append to.
 /// This must be called BEFORE LogManager::init to ensure the file isn't locked.
-pub fn rotate_logs() -> Result<(), String> {
+pub fn rotate_logs(config: &GeneralConfig) -> Result<(), String> {
     let paths = LogPaths::new().ok_or("Could not determine home directory")?;
 
     // 1. Ensure directories exist
@@ -79,11 +136,16 @@ pub fn rotate_logs() -> Result<(), String> {
         fs::create_dir_all(&paths.archive_dir).map_err(|e| e.to_string())?;
     }
 
-    // 2. Rotate latest.log if it exists
-    if paths.latest_log.exists() {
-        let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
-        let archive_name = format!("app-{}.log", timestamp);
-        let archive_path = paths.archive_dir.join(archive_name);
+    // 2. Rotate latest.log if it exists (and, for daily rotation, isn't from today)
+    let keep_today = config.is_daily_log_rotation() && is_from_today(&paths.latest_log);
+    if paths.latest_log.exists() && !keep_today {
+        // Daily archives are named after the day they cover, not the day they were rotated.
+        let stamp = if config.is_daily_log_rotation() {
+            fs::metadata(&paths.latest_log).and_then(|m| m.modified()).map(DateTime::<Local>::from).unwrap_or_else(|_| Local::now())
+        } else {
+            Local::now()
+        };
+        let archive_path = unique_archive_path(&paths.archive_dir, &archive_name(&config.log_archive_pattern, stamp));
 
         // Attempt rename
         if let Err(e) = fs::rename(&paths.latest_log, &archive_path) {
@@ -97,13 +159,18 @@ pub fn rotate_logs() -> Result<(), String> {
         }
     }
 
-    // 3. Cleanup old archives (Keep last 10)
-    cleanup_archives(&paths.archive_dir).map_err(|e| format!("Cleanup failed: {}", e))?;
+    // 3. Cleanup old archives
+    cleanup_archives(&paths.archive_dir, config.log_archive_keep).map_err(|e| format!("Cleanup failed: {}", e))?;
 
     Ok(())
 }
 
-fn cleanup_archives(archive_dir: &PathBuf) -> std::io::Result<()> {
+/// Keeps the newest `keep` archives; `0` keeps everything.
+fn cleanup_archives(archive_dir: &Path, keep: usize) -> std::io::Result<()> {
+    if keep == 0 {
+        return Ok(());
+    }
+
     let mut entries: Vec<PathBuf> = fs::read_dir(archive_dir)?
         .filter_map(|res| res.ok())
         .map(|e| e.path())
@@ -123,9 +190,9 @@ fn cleanup_archives(archive_dir: &PathBuf) -> std::io::Result<()> {
         }
     });
 
-    // Keep top 10, delete the rest
-    if entries.len() > 10 {
-        for path in entries.iter().skip(10) {
+    // Keep the newest `keep`, delete the rest
+    if entries.len() > keep {
+        for path in entries.iter().skip(keep) {
             if let Err(e) = fs::remove_file(path) {
                 eprintln!("Failed to delete old log {:?}: {}", path, e);
             }
@@ -138,12 +205,19 @@ fn cleanup_archives(archive_dir: &PathBuf) -> std::io::Result<()> {
 // --- Manager Implementation ---
 
 impl LogManager {
-    pub fn init(log_level: &str) -> Self {
+    /// `append` continues an existing "latest.log" (daily rotation) instead of truncating it.
+    pub fn init(log_level: &str, append: bool) -> Self {
         // 1. Get Paths
         let paths = LogPaths::new().expect("Could not determine log paths during init");
         
-        // 2. Create/Truncate "latest.log"
-        let file = std::fs::File::create(&paths.latest_log).expect("Failed to create latest.log");
+        // 2. Create/Truncate (or append to) "latest.log"
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(&paths.latest_log)
+            .expect("Failed to create latest.log");
 
         // 3. Non-blocking Writer
         let (non_blocking, guard) = tracing_appender::non_blocking(file);
@@ -187,6 +261,9 @@ impl LogManager {
         }
     }
 
+    /// Levels accepted by `set_level` and stored in `GeneralConfig::log_level`.
+    pub const LEVELS: [&'static str; 5] = ["error", "warn", "info", "debug", "trace"];
+
     pub fn set_level(&self, level: &str) -> Result<(), String> {
         let filter_str = Self::get_filter_string(level);
         let new_filter = EnvFilter::try_new(&filter_str)
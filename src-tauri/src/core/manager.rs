@@ -1,4 +1,4 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::{mpsc, oneshot};
@@ -10,15 +10,55 @@ use std::path::{Path, PathBuf};
 use tracing::{info, warn, error, debug, trace};
 
 use crate::models::{
-    Job, JobStatus, QueuedJob, JobMessage, 
+    Job, JobPhase, JobStatus, QueuedJob, JobMessage, 
     DownloadProgressPayload, BatchProgressPayload, 
     DownloadCompletePayload,
-    DownloadCancelledPayload,
-    Download
+    DownloadCancelledPayload, BatchHaltedPayload, LowDiskSpacePayload,
+    Download, QueueSummary, GroupProgress, RetryEligibility, GroupFailure, ResumeResult, PersistedQueue,
+    UsageCounters, UsageStats
 };
 use crate::config::ConfigManager;
-use crate::core::process::run_download_process;
-use crate::core::native;
+use crate::core::process::{parse_speed, run_download_process, set_process_suspended};
+use crate::core::sink::{ProgressSink, TauriSink};
+use crate::core::playlist::{write_cover_art, write_m3u8, PlaylistItem};
+use crate::core::hooks::run_post_download_hook;
+use crate::core::history::HistoryManager;
+use crate::core::paths;
+
+/// Errors that won't go away on retry; such jobs are dropped from persistence.
+/// `patterns` comes from `GeneralConfig::fatal_error_patterns`.
+pub fn is_fatal_error(err_msg: &str, patterns: &[String]) -> bool {
+    let msg = err_msg.to_lowercase();
+    patterns.iter().any(|pattern| {
+        let mut terms = pattern.split("&&").map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty()).peekable();
+        terms.peek().is_some() && terms.all(|t| msg.contains(&t))
+    })
+}
+
+/// `add_job` error for a URL that already has a pending or running job.
+pub const DUPLICATE_JOB_ERROR: &str = "URL is already in queue";
+
+/// Starts the work for one queued job. It reports back through `tx_actor` and must
+/// finish with `WorkerFinished`. The GUI uses [`YtDlpRunner`]; a test harness can
+/// supply a runner that never spawns a process.
+pub trait JobRunner: Send + Sync {
+    fn spawn(&self, job: QueuedJob, tx_actor: mpsc::Sender<JobMessage>, cancel_flag: Arc<AtomicBool>);
+}
+
+/// Runs yt-dlp through `run_download_process`, which needs the app to provision
+/// missing dependencies.
+pub struct YtDlpRunner {
+    app_handle: AppHandle,
+}
+
+impl JobRunner for YtDlpRunner {
+    fn spawn(&self, job: QueuedJob, tx_actor: mpsc::Sender<JobMessage>, cancel_flag: Arc<AtomicBool>) {
+        let app = self.app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            run_download_process(job, app, tx_actor, cancel_flag).await;
+        });
+    }
+}
 
 #[derive(Clone)]
 pub struct JobManagerHandle {
@@ -27,8 +67,17 @@ pub struct JobManagerHandle {
 
 impl JobManagerHandle {
     pub fn new(app_handle: AppHandle) -> Self {
+        let config_manager = app_handle.state::<Arc<ConfigManager>>().inner().clone();
+        let app_version = app_handle.package_info().version.to_string();
+        let sink = Arc::new(TauriSink::new(app_handle.clone()));
+        Self::with_sink(config_manager, app_version, sink, Arc::new(YtDlpRunner { app_handle }))
+    }
+
+    /// Builds a manager that needs no Tauri app: it reports through `sink` and
+    /// starts jobs through `runner`.
+    pub fn with_sink(config_manager: Arc<ConfigManager>, app_version: String, sink: Arc<dyn ProgressSink>, runner: Arc<dyn JobRunner>) -> Self {
         let (sender, receiver) = mpsc::channel(1000);
-        let actor = JobManagerActor::new(app_handle, receiver, sender.clone());
+        let actor = JobManagerActor::new(config_manager, app_version, sink, runner, receiver, sender.clone());
         tauri::async_runtime::spawn(actor.run());
         
         Self { sender }
@@ -36,7 +85,7 @@ impl JobManagerHandle {
 
     pub async fn add_job(&self, job: QueuedJob) -> Result<(), String> {
         let (tx, rx) = oneshot::channel();
-        let _ = self.sender.send(JobMessage::AddJob { job, resp: tx }).await;
+        let _ = self.sender.send(JobMessage::AddJob { job: Box::new(job), resp: tx }).await;
         rx.await.map_err(|_| "Actor closed".to_string())?
     }
 
@@ -56,7 +105,68 @@ impl JobManagerHandle {
         rx.await.unwrap_or(0)
     }
 
-    pub async fn resume_pending(&self) -> Vec<QueuedJob> {
+    /// Jobs that are queued or running and may still spawn a managed binary.
+    pub async fn get_active_count(&self) -> u32 {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::GetActiveCount(tx)).await;
+        rx.await.unwrap_or(0)
+    }
+
+    pub async fn get_job(&self, id: Uuid) -> Option<Job> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::GetJob { id, resp: tx }).await;
+        rx.await.ok().flatten()
+    }
+
+    /// Applies from the job's next start; yt-dlp can't change its rate mid-download.
+    /// Capped at the job's share of the global limit, see `process::effective_rate_limit`.
+    pub async fn set_rate_limit(&self, id: Uuid, limit: Option<String>) -> Result<(), String> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::SetRateLimit { id, limit, resp: tx }).await;
+        rx.await.map_err(|_| "Actor closed".to_string())?
+    }
+
+    /// Records that a finished job's file was moved and re-announces it to the UI.
+    pub async fn set_output_path(&self, id: Uuid, output_path: String) -> Result<(), String> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::SetOutputPath { id, output_path, resp: tx }).await;
+        rx.await.map_err(|_| "Actor closed".to_string())?
+    }
+
+    pub async fn get_group_failures(&self, group_id: Uuid) -> Vec<GroupFailure> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::GetGroupFailures { group_id, resp: tx }).await;
+        rx.await.unwrap_or_default()
+    }
+
+    /// Puts a failed job back in the queue under the same id, from its persisted request.
+    pub async fn requeue_job(&self, id: Uuid) -> Result<(), String> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::RequeueJob { id, immediate: false, split_streams: false, resp: tx }).await;
+        rx.await.map_err(|_| "Actor closed".to_string())?
+    }
+
+    /// Like `requeue_job`, but runs next and doesn't count against the retry budget.
+    pub async fn force_retry_now(&self, id: Uuid) -> Result<(), String> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::RequeueJob { id, immediate: true, split_streams: false, resp: tx }).await;
+        rx.await.map_err(|_| "Actor closed".to_string())?
+    }
+
+    /// Like `force_retry_now`, but downloads video and audio as separate files.
+    pub async fn retry_without_merging(&self, id: Uuid) -> Result<(), String> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::RequeueJob { id, immediate: true, split_streams: true, resp: tx }).await;
+        rx.await.map_err(|_| "Actor closed".to_string())?
+    }
+
+    pub async fn set_max_retries(&self, id: Uuid, max_retries: u32) -> Result<(), String> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::SetMaxRetries { id, max_retries, resp: tx }).await;
+        rx.await.map_err(|_| "Actor closed".to_string())?
+    }
+
+    pub async fn resume_pending(&self) -> ResumeResult {
         let (tx, rx) = oneshot::channel();
         let _ = self.sender.send(JobMessage::ResumePending(tx)).await;
         rx.await.unwrap_or_default()
@@ -66,12 +176,57 @@ impl JobManagerHandle {
         let _ = self.sender.send(JobMessage::ClearPending).await;
     }
 
+    /// Drops finished/errored entries from `jobs.json`, keeping genuinely pending work.
+    /// Returns how many entries were removed.
+    pub async fn clear_finished_persistence(&self) -> Result<u32, String> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::ClearFinishedPersistence(tx)).await;
+        rx.await.map_err(|_| "Actor closed".to_string())
+    }
+
     pub async fn sync_state(&self) -> Vec<Download> {
         let (tx, rx) = oneshot::channel();
         let _ = self.sender.send(JobMessage::SyncState(tx)).await;
         rx.await.unwrap_or_default()
     }
     
+    /// Stops new jobs from starting; with `suspend_running`, also freezes the ones in flight.
+    pub async fn pause_queue(&self, suspend_running: bool) -> Result<QueueSummary, String> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::PauseQueue { suspend_running, resp: tx }).await;
+        rx.await.map_err(|_| "Actor closed".to_string())
+    }
+
+    pub async fn resume_queue(&self) -> Result<QueueSummary, String> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::ResumeQueue(tx)).await;
+        rx.await.map_err(|_| "Actor closed".to_string())
+    }
+
+    pub async fn get_queue_summary(&self) -> Result<QueueSummary, String> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::GetQueueSummary(tx)).await;
+        rx.await.map_err(|_| "Actor closed".to_string())
+    }
+
+    pub async fn get_stats(&self) -> Result<UsageStats, String> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::GetStats(tx)).await;
+        rx.await.map_err(|_| "Actor closed".to_string())
+    }
+
+    pub async fn get_group_progress(&self) -> Vec<GroupProgress> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::GetGroupProgress(tx)).await;
+        rx.await.unwrap_or_default()
+    }
+
+    pub async fn can_retry(&self, id: Uuid) -> Result<RetryEligibility, String> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.sender.send(JobMessage::CanRetry { id, resp: tx }).await;
+        rx.await.map_err(|_| "Actor closed".to_string())
+    }
+
     pub async fn shutdown(&self) {
         let (tx, rx) = oneshot::channel();
         let _ = self.sender.send(JobMessage::Shutdown(tx)).await;
@@ -79,13 +234,22 @@ impl JobManagerHandle {
     }
 }
 
+/// Version string of the managed yt-dlp binary, if it is installed.
+fn current_ytdlp_version() -> Option<String> {
+    let exe = paths::bin_dir().join(if cfg!(windows) { "yt-dlp.exe" } else { "yt-dlp" });
+    crate::core::deps::get_local_version(&exe, "--version")
+}
+
 enum PersistenceMsg {
     Save(Vec<QueuedJob>),
     Clear
 }
 
 struct JobManagerActor {
-    app_handle: AppHandle,
+    config_manager: Arc<ConfigManager>,
+    app_version: String,
+    sink: Arc<dyn ProgressSink>,
+    runner: Arc<dyn JobRunner>,
     receiver: mpsc::Receiver<JobMessage>,
     self_sender: mpsc::Sender<JobMessage>,
 
@@ -97,24 +261,59 @@ struct JobManagerActor {
     
     dirty_persistence: bool,
 
+    queue_paused: bool,
+    suspended_jobs: HashSet<Uuid>,
+    finished_groups: HashSet<Uuid>,
+    /// Groups whose cover art has already been attempted this session.
+    covered_groups: HashSet<Uuid>,
+    /// Set once the persisted queue has been restored; later ResumePending calls are no-ops.
+    pending_resumed: bool,
+    /// Free space on a download or temp volume is below `min_free_space_mb`.
+    low_disk_space: bool,
+    /// Jobs suspended by the free-space guard rather than by the user; resumed when space returns.
+    disk_suspended_jobs: HashSet<Uuid>,
+
     active_network_jobs: u32,
     active_process_instances: u32,
     completed_session_count: u32,
 
+    /// Counters for this session; added to `lifetime_base` (loaded from disk) when read or saved.
+    session_stats: UsageCounters,
+    lifetime_base: UsageCounters,
+    dirty_stats: bool,
+
     pending_updates: HashMap<Uuid, DownloadProgressPayload>,
 }
 
 impl JobManagerActor {
-    fn new(app_handle: AppHandle, receiver: mpsc::Receiver<JobMessage>, self_sender: mpsc::Sender<JobMessage>) -> Self {
+    fn new(
+        config_manager: Arc<ConfigManager>,
+        app_version: String,
+        sink: Arc<dyn ProgressSink>,
+        runner: Arc<dyn JobRunner>,
+        receiver: mpsc::Receiver<JobMessage>,
+        self_sender: mpsc::Sender<JobMessage>,
+    ) -> Self {
         
         let (ptx, mut prx) = mpsc::channel(100);
+        let saved_version = app_version.clone();
         tauri::async_runtime::spawn(async move {
-            let path = Self::get_persistence_path();
+            let path = paths::persistence_file();
+            // Looked up on the first save only; spawning yt-dlp every flush would be wasteful.
+            let mut ytdlp_version: Option<Option<String>> = None;
             while let Some(msg) = prx.recv().await {
                 match msg {
                     PersistenceMsg::Save(jobs) => {
                         trace!(target: "core::persistence", "Flushing {} jobs to persistence", jobs.len());
-                        if let Ok(json) = serde_json::to_string_pretty(&jobs) {
+                        if ytdlp_version.is_none() {
+                            ytdlp_version = Some(tokio::task::spawn_blocking(current_ytdlp_version).await.ok().flatten());
+                        }
+                        let file = PersistedQueue {
+                            app_version: Some(saved_version.clone()),
+                            ytdlp_version: ytdlp_version.clone().flatten(),
+                            jobs,
+                        };
+                        if let Ok(json) = serde_json::to_string_pretty(&file) {
                             let tmp_path = path.with_extension("tmp");
                             if tokio::fs::write(&tmp_path, json).await.is_ok() {
                                 let _ = tokio::fs::rename(tmp_path, &path).await;
@@ -134,7 +333,10 @@ impl JobManagerActor {
         });
 
         Self {
-            app_handle,
+            config_manager,
+            app_version,
+            sink,
+            runner,
             receiver,
             self_sender,
             jobs: HashMap::new(),
@@ -143,16 +345,378 @@ impl JobManagerActor {
             persistence_registry: HashMap::new(),
             persistence_tx: ptx,
             dirty_persistence: false,
+            queue_paused: paths::queue_paused_marker().exists(),
+            suspended_jobs: HashSet::new(),
+            finished_groups: HashSet::new(),
+            covered_groups: HashSet::new(),
+            low_disk_space: false,
+            disk_suspended_jobs: HashSet::new(),
+            pending_resumed: false,
             active_network_jobs: 0,
             active_process_instances: 0,
             completed_session_count: 0,
+            session_stats: UsageCounters::default(),
+            lifetime_base: Self::load_lifetime_stats(),
+            dirty_stats: false,
             pending_updates: HashMap::new(),
         }
     }
 
-    fn get_persistence_path() -> PathBuf {
-        let home = dirs::home_dir().expect("Could not find home directory");
-        home.join(".multiyt-dlp").join("jobs.json")
+    /// `stop_on_first_error`: cancels every other queued or running job after `failed_id` failed.
+    fn halt_batch(&mut self, failed_id: Uuid) {
+        let to_cancel: Vec<Uuid> = self.jobs.values()
+            .filter(|j| j.id != failed_id && matches!(j.status, JobStatus::Pending | JobStatus::Downloading))
+            .map(|j| j.id)
+            .collect();
+        if to_cancel.is_empty() {
+            return;
+        }
+
+        warn!(target: "core::manager", job_id = ?failed_id, count = to_cancel.len(), "Stopping batch after failure");
+        for id in &to_cancel {
+            self.cancel_job(*id);
+        }
+        self.queue.clear();
+        self.sink.batch_halted(BatchHaltedPayload {
+            failed_job_id: failed_id,
+            cancelled_job_ids: to_cancel,
+        });
+    }
+
+    fn cancel_job(&mut self, id: Uuid) {
+        self.pending_updates.remove(&id);
+
+        if let Some(flag) = self.cancel_flags.get(&id) {
+            flag.store(true, Ordering::Relaxed);
+        }
+        self.cancel_flags.remove(&id);
+
+        if let Some(job) = self.jobs.get_mut(&id) {
+            if let Some(pid) = job.pid {
+                debug!(target: "core::manager", job_id = ?id, "Killing underlying process PID: {}", pid);
+                kill_process(pid);
+                // A stopped process won't act on SIGTERM until it is continued.
+                if self.suspended_jobs.remove(&id) {
+                    let _ = set_process_suspended(pid, false);
+                }
+            }
+            if let Some(temp) = job.temp_path.clone() {
+                tauri::async_runtime::spawn(async move {
+                    let path = PathBuf::from(temp);
+                    if path.exists() { 
+                        let _ = tokio::fs::remove_file(&path).await; 
+                    }
+                    if let Some(parent) = path.parent() {
+                        let _ = tokio::fs::remove_dir(parent).await;
+                    }
+                });
+            }
+            job.status = JobStatus::Cancelled;
+            job.sequence_id += 1;
+        } else {
+            warn!(target: "core::manager", "Job {} not found during cancellation", id);
+        }
+        
+        self.persistence_registry.remove(&id);
+        self.mark_dirty();
+
+        self.sink.cancelled(DownloadCancelledPayload {
+            job_id: id
+        });
+        self.maybe_finish_group(id);
+    }
+
+    /// Whether a pending or running job already targets `url`, compared in normalized form.
+    fn has_active_url(&self, url: &str) -> bool {
+        let target = HistoryManager::normalize_url(url);
+        self.jobs.values().any(|j| {
+            (j.status == JobStatus::Pending || j.status == JobStatus::Downloading)
+                && HistoryManager::normalize_url(&j.url) == target
+        })
+    }
+
+    fn read_persisted_queue() -> Option<PersistedQueue> {
+        let content = fs::read_to_string(paths::persistence_file()).ok()?;
+        PersistedQueue::parse(&content)
+    }
+
+    /// Logs when the persisted queue was written by a different app or yt-dlp version,
+    /// and names the resumed jobs whose options depend on yt-dlp behaviour.
+    async fn warn_on_version_drift(&self, file: &PersistedQueue) {
+        if let Some(saved) = file.app_version.as_deref().filter(|v| *v != self.app_version) {
+            warn!(target: "core::persistence", saved = %saved, current = %self.app_version, "Resuming jobs queued under a different app version");
+        }
+
+        let Some(saved) = file.ytdlp_version.as_deref() else { return };
+        let current = tokio::task::spawn_blocking(current_ytdlp_version).await.ok().flatten();
+        let Some(current) = current.filter(|v| v != saved) else { return };
+        warn!(target: "core::persistence", saved = %saved, current = %current, "Resuming jobs queued under a different yt-dlp version");
+        for job in &file.jobs {
+            let mut sensitive = Vec::new();
+            if job.format_sort.is_some() { sensitive.push("format_sort"); }
+            if job.download_sections.is_some() { sensitive.push("download_sections"); }
+            if job.live_from_start { sensitive.push("live_from_start"); }
+            if job.write_live_chat || job.write_comments { sensitive.push("sidecars"); }
+            if !sensitive.is_empty() {
+                warn!(target: "core::persistence", job_id = ?job.id, options = ?sensitive, "Resumed job uses options that may behave differently after the yt-dlp update");
+            }
+        }
+    }
+
+    fn persist_queue_paused(&self) {
+        let path = paths::queue_paused_marker();
+        let result = if self.queue_paused {
+            fs::write(&path, b"")
+        } else if path.exists() {
+            fs::remove_file(&path)
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
+            warn!(target: "core::persistence", "Failed to persist queue pause state: {}", e);
+        }
+    }
+
+    fn load_lifetime_stats() -> UsageCounters {
+        fs::read_to_string(paths::stats_file()).ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .unwrap_or_default()
+    }
+
+    fn usage_stats(&self) -> UsageStats {
+        let mut lifetime = self.lifetime_base;
+        lifetime.add(&self.session_stats);
+        UsageStats { session: self.session_stats, lifetime }
+    }
+
+    /// Counts a finished download, sized from the file now at `output_path`.
+    fn record_completion(&mut self, output_path: &str) {
+        self.session_stats.completed += 1;
+        self.session_stats.bytes_downloaded += fs::metadata(output_path).map(|m| m.len()).unwrap_or(0);
+        self.dirty_stats = true;
+    }
+
+    fn save_stats(&mut self) {
+        if !self.dirty_stats { return; }
+        let lifetime = self.usage_stats().lifetime;
+        match serde_json::to_string_pretty(&lifetime) {
+            Ok(json) => {
+                if let Err(e) = fs::write(paths::stats_file(), json) {
+                    warn!(target: "core::persistence", "Failed to save usage stats: {}", e);
+                    return;
+                }
+                self.dirty_stats = false;
+            }
+            Err(e) => warn!(target: "core::persistence", "Failed to serialize usage stats: {}", e),
+        }
+    }
+
+    fn queue_summary(&self) -> QueueSummary {
+        let active_count = self.jobs.values()
+            .filter(|j| j.status == JobStatus::Downloading && j.pid.is_some())
+            .count();
+        let suspended_count = self.suspended_jobs.iter()
+            .filter(|id| self.jobs.get(id).is_some_and(|j| j.status == JobStatus::Downloading))
+            .count();
+        QueueSummary {
+            paused: self.queue_paused,
+            suspended_count: suspended_count as u32,
+            queued_count: self.queue.len() as u32,
+            active_count: active_count as u32,
+            low_disk_space: self.low_disk_space,
+        }
+    }
+
+    /// The first download or temp volume with less free space than the configured margin,
+    /// with its free bytes. Paths that don't exist yet are measured at their nearest
+    /// existing ancestor.
+    fn find_low_disk_volume(&self, min_free_bytes: u64) -> Option<(PathBuf, u64)> {
+        let config = self.config_manager.get_config();
+        let mut dirs: HashSet<PathBuf> = HashSet::from([paths::temp_dir()]);
+        dirs.extend(config.general.download_path.as_deref().filter(|p| !p.trim().is_empty()).map(PathBuf::from)
+            .or_else(tauri::api::path::download_dir));
+        dirs.extend(self.jobs.values()
+            .filter(|j| matches!(j.status, JobStatus::Pending | JobStatus::Downloading))
+            .filter_map(|j| j.download_path.as_deref().map(PathBuf::from)));
+
+        dirs.into_iter().find_map(|dir| {
+            let existing = dir.ancestors().find(|p| p.exists())?.to_path_buf();
+            let free = fs2::available_space(&existing).ok()?;
+            (free < min_free_bytes).then_some((existing, free))
+        })
+    }
+
+    /// Re-evaluates the free-space guard. Entering low space suspends running downloads
+    /// and notifies once; recovering resumes what the guard suspended and restarts the queue.
+    fn check_disk_space(&mut self) {
+        let min_free_mb = self.config_manager.get_config().general.min_free_space_mb;
+        let low = if min_free_mb == 0 { None } else { self.find_low_disk_volume(min_free_mb * 1024 * 1024) };
+
+        match low {
+            Some((path, free_bytes)) if !self.low_disk_space => {
+                warn!(target: "core::manager", path = ?path, free_mb = free_bytes / (1024 * 1024), min_free_mb = min_free_mb, "Low disk space, holding the queue");
+                self.low_disk_space = true;
+                for job in self.jobs.values() {
+                    if job.status != JobStatus::Downloading || self.suspended_jobs.contains(&job.id) {
+                        continue;
+                    }
+                    let Some(pid) = job.pid else { continue };
+                    match set_process_suspended(pid, true) {
+                        Ok(_) => {
+                            self.suspended_jobs.insert(job.id);
+                            self.disk_suspended_jobs.insert(job.id);
+                        },
+                        Err(e) => warn!(target: "core::manager", job_id = ?job.id, "Failed to suspend process {}: {}", pid, e),
+                    }
+                }
+                self.sink.low_disk_space(LowDiskSpacePayload {
+                    path: path.to_string_lossy().to_string(),
+                    free_bytes,
+                    min_free_bytes: min_free_mb * 1024 * 1024,
+                    suspended_count: self.disk_suspended_jobs.len() as u32,
+                });
+            },
+            None if self.low_disk_space => {
+                info!(target: "core::manager", resuming = self.disk_suspended_jobs.len(), "Disk space recovered, releasing the queue");
+                self.low_disk_space = false;
+                for id in std::mem::take(&mut self.disk_suspended_jobs) {
+                    // A user pause keeps its suspensions.
+                    if self.queue_paused || !self.suspended_jobs.remove(&id) {
+                        continue;
+                    }
+                    if let Some(pid) = self.jobs.get(&id).and_then(|j| j.pid) {
+                        if let Err(e) = set_process_suspended(pid, false) {
+                            warn!(target: "core::manager", job_id = ?id, "Failed to resume process {}: {}", pid, e);
+                        }
+                    }
+                }
+                self.process_queue();
+            },
+            _ => {}
+        }
+    }
+
+    fn group_progress(&self) -> Vec<GroupProgress> {
+        // Per group: the aggregate, the members still in `jobs`, and their summed progress.
+        let mut groups: HashMap<Uuid, (GroupProgress, u32, f32)> = HashMap::new();
+        for job in self.jobs.values() {
+            let Some(group_id) = job.group_id else { continue };
+            let (entry, members, sum) = groups.entry(group_id).or_insert_with(|| (GroupProgress {
+                group_id,
+                title: job.group_title.clone(),
+                total: job.group_total.unwrap_or(0),
+                completed: 0,
+                failed: 0,
+                cancelled: 0,
+                percentage: 0.0,
+            }, 0, 0.0));
+            *members += 1;
+            *sum += match job.status {
+                JobStatus::Completed | JobStatus::Modified | JobStatus::Skipped => {
+                    entry.completed += 1;
+                    100.0
+                },
+                JobStatus::Error => { entry.failed += 1; job.progress },
+                JobStatus::Cancelled => { entry.cancelled += 1; 100.0 },
+                _ => job.progress,
+            };
+        }
+
+        let mut result: Vec<GroupProgress> = groups.into_values()
+            .map(|(mut g, members, sum)| {
+                g.total = g.total.max(members);
+                g.percentage = (sum / g.total as f32).min(100.0);
+                g
+            })
+            .collect();
+        result.sort_by_key(|g| g.group_id);
+        result
+    }
+
+    /// Starts the configured post-download hook for a finished file. Runs off the
+    /// actor so a slow hook never holds up the queue; the result comes back as `HookFinished`.
+    fn spawn_post_download_hook(&self, id: Uuid, output_path: &str) {
+        let config = self.config_manager.get_config().general.clone();
+        let Some(template) = config.post_download_hook.filter(|h| !h.trim().is_empty()) else { return };
+        let Some(url) = self.jobs.get(&id).map(|j| j.url.clone()) else { return };
+
+        let path = output_path.to_string();
+        let tx = self.self_sender.clone();
+        tauri::async_runtime::spawn(async move {
+            let (exit_code, output) = match run_post_download_hook(&template, &path, &url).await {
+                Ok(outcome) => (outcome.exit_code, outcome.output),
+                Err(e) => (None, e),
+            };
+            let _ = tx.send(JobMessage::HookFinished { id, exit_code, output }).await;
+        });
+    }
+
+    /// Saves `cover.jpg` for a playlist group when its first item finishes. Skipped when
+    /// the file landed directly in the download folder, where the cover would be shared
+    /// with unrelated downloads.
+    fn maybe_write_cover_art(&mut self, id: Uuid, output_path: &str) {
+        let config = self.config_manager.get_config().general.clone();
+        if !config.write_cover_art { return; }
+        let Some(job) = self.jobs.get(&id) else { return };
+        let Some(group_id) = job.group_id else { return };
+        let Some(dir) = Path::new(output_path).parent().map(Path::to_path_buf) else { return };
+        if job.download_path.as_deref().is_none_or(|base| Path::new(base) == dir) {
+            trace!(target: "core::manager", group_id = ?group_id, "Skipping cover art: playlist has no folder of its own");
+            return;
+        }
+        if !self.covered_groups.insert(group_id) { return; }
+
+        let url = job.url.clone();
+        tauri::async_runtime::spawn(async move {
+            let overwrite = config.overwrite_cover_art;
+            match write_cover_art(&config, &url, &dir, overwrite).await {
+                Ok(Some(path)) => info!(target: "core::manager", group_id = ?group_id, "Saved playlist cover art to {:?}", path),
+                Ok(None) => {}
+                Err(e) => warn!(target: "core::manager", group_id = ?group_id, "Failed to save playlist cover art: {}", e),
+            }
+        });
+    }
+
+    /// Writes the group's .m3u8 once its last member reaches a terminal state.
+    fn maybe_finish_group(&mut self, id: Uuid) {
+        let Some(group_id) = self.jobs.get(&id).and_then(|j| j.group_id) else { return };
+        if self.finished_groups.contains(&group_id) { return; }
+
+        let mut members: Vec<&Job> = self.jobs.values().filter(|j| j.group_id == Some(group_id)).collect();
+        let total = members.first().and_then(|j| j.group_total).unwrap_or(0) as usize;
+        let all_done = members.iter().all(|j| matches!(j.status,
+            JobStatus::Completed | JobStatus::Modified | JobStatus::Cancelled | JobStatus::Error | JobStatus::Skipped));
+        if members.len() < total || !all_done { return; }
+        self.finished_groups.insert(group_id);
+
+        let config = self.config_manager.get_config().general.clone();
+        if !config.write_playlist_m3u { return; }
+
+        members.sort_by_key(|j| j.group_index);
+        let items: Vec<PlaylistItem> = members.iter()
+            .filter(|j| matches!(j.status, JobStatus::Completed | JobStatus::Modified))
+            .filter_map(|j| j.output_path.as_ref())
+            .map(|p| {
+                let path = PathBuf::from(p);
+                let title = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+                PlaylistItem { title, path }
+            })
+            .collect();
+
+        if items.is_empty() {
+            info!(target: "core::manager", group_id = ?group_id, "No completed files in playlist group, skipping m3u8");
+            return;
+        }
+        if items.len() < members.len() {
+            info!(target: "core::manager", group_id = ?group_id, written = items.len(), total = members.len(), "Playlist group finished with failures; m3u8 lists only the completed files");
+        }
+
+        let first = members[0];
+        let Some(dir) = first.download_path.clone().map(PathBuf::from) else { return };
+        let title = first.group_title.clone().unwrap_or_else(|| "playlist".to_string());
+        if let Err(e) = write_m3u8(&dir, &title, &items) {
+            warn!(target: "core::manager", group_id = ?group_id, "Failed to write playlist m3u8: {}", e);
+        }
     }
 
     fn mark_dirty(&mut self) {
@@ -178,6 +742,7 @@ impl JobManagerActor {
                             let jobs: Vec<QueuedJob> = self.persistence_registry.values().cloned().collect();
                             let _ = self.persistence_tx.send(PersistenceMsg::Save(jobs)).await;
                         }
+                        self.save_stats();
                         
                         let _ = tx.send(());
                         break;
@@ -199,6 +764,8 @@ impl JobManagerActor {
                             warn!(target: "core::manager", "Failed to send persistence save request (channel full)");
                         }
                     }
+                    self.save_stats();
+                    self.check_disk_space();
                 }
             }
         }
@@ -215,6 +782,11 @@ impl JobManagerActor {
         for pid in pids {
             kill_process(pid);
         }
+        for id in std::mem::take(&mut self.suspended_jobs) {
+            if let Some(pid) = self.jobs.get(&id).and_then(|j| j.pid) {
+                let _ = set_process_suspended(pid, false);
+            }
+        }
 
         let deadline = time::Instant::now() + Duration::from_secs(3);
         while self.active_process_instances > 0 {
@@ -234,29 +806,18 @@ impl JobManagerActor {
         debug!(target: "core::manager", "Shutdown sequence complete");
     }
 
-    fn is_fatal_error(err_msg: &str) -> bool {
-        let msg = err_msg.to_lowercase();
-        msg.contains("video unavailable") || 
-        msg.contains("this video has been removed") ||
-        (msg.contains("fragment") && msg.contains("not received")) ||
-        msg.contains("http error 404")
-    }
-
     async fn handle_message(&mut self, msg: JobMessage) {
         match msg {
             JobMessage::AddJob { job, resp } => {
+                let job = *job;
                 trace!(target: "core::manager", "Received AddJob for ID: {}", job.id);
                 if self.jobs.contains_key(&job.id) {
                     warn!(target: "core::manager", "Rejected job {}: Job already exists", job.id);
                     let _ = resp.send(Err("Job already exists".into()));
                 } else {
-                    let is_duplicate_active = self.jobs.values().any(|j| {
-                        j.url == job.url && (j.status == JobStatus::Pending || j.status == JobStatus::Downloading)
-                    });
-
-                    if is_duplicate_active {
+                    if self.has_active_url(&job.url) {
                         warn!(target: "core::manager", "Rejected job {}: URL already active in queue", job.url);
-                        let _ = resp.send(Err("URL is already in queue".into()));
+                        let _ = resp.send(Err(DUPLICATE_JOB_ERROR.into()));
                     } else {
                         debug!(target: "core::manager", job_id = ?job.id, url = %job.url, "Job successfully admitted to queue");
                         
@@ -270,6 +831,12 @@ impl JobManagerActor {
                         j.restrict_filenames = Some(job.restrict_filenames);
                         j.live_from_start = Some(job.live_from_start);
                         j.download_sections = job.download_sections.clone();
+                        j.music_metadata = Some(job.music_metadata);
+                        j.group_id = job.group_id;
+                        j.group_total = job.group_total;
+                        j.group_index = job.group_index;
+                        j.group_title = job.group_title.clone();
+                        j.rate_limit = job.rate_limit.clone();
 
                         self.cancel_flags.insert(job.id, Arc::new(AtomicBool::new(false)));
                         self.jobs.insert(job.id, j);
@@ -283,42 +850,7 @@ impl JobManagerActor {
             },
             JobMessage::CancelJob { id } => {
                 info!(target: "core::manager", job_id = ?id, "Processing job cancellation request");
-                
-                self.pending_updates.remove(&id);
-
-                if let Some(flag) = self.cancel_flags.get(&id) {
-                    flag.store(true, Ordering::Relaxed);
-                }
-                self.cancel_flags.remove(&id);
-
-                if let Some(job) = self.jobs.get_mut(&id) {
-                    if let Some(pid) = job.pid {
-                        debug!(target: "core::manager", job_id = ?id, "Killing underlying process PID: {}", pid);
-                        kill_process(pid);
-                    }
-                    if let Some(temp) = job.temp_path.clone() {
-                        tauri::async_runtime::spawn(async move {
-                            let path = PathBuf::from(temp);
-                            if path.exists() { 
-                                let _ = tokio::fs::remove_file(&path).await; 
-                            }
-                            if let Some(parent) = path.parent() {
-                                let _ = tokio::fs::remove_dir(parent).await;
-                            }
-                        });
-                    }
-                    job.status = JobStatus::Cancelled;
-                    job.sequence_id += 1;
-                } else {
-                    warn!(target: "core::manager", "Job {} not found during cancellation", id);
-                }
-                
-                self.persistence_registry.remove(&id);
-                self.mark_dirty();
-
-                let _ = self.app_handle.emit_all("download-cancelled", DownloadCancelledPayload {
-                    job_id: id
-                });
+                self.cancel_job(id);
             },
             JobMessage::ResolveConflict { id, resolution, resp } => {
                 trace!(target: "core::manager", job_id = ?id, "Processing conflict resolution: {}", resolution);
@@ -353,7 +885,8 @@ impl JobManagerActor {
                                     debug!(target: "core::manager", job_id = ?id, "Overwrite successful");
                                     job.status = if job.is_modified { JobStatus::Modified } else { JobStatus::Completed };
                                     job.progress = 100.0;
-                                    job.phase = Some("Done".to_string());
+                                    job.phase = Some(JobPhase::Done);
+                                    job.phase_detail = None;
                                     job.sequence_id += 1;
                                     
                                     if let Some(parent) = t_path.parent() {
@@ -380,10 +913,11 @@ impl JobManagerActor {
                             }
                             
                             job.status = JobStatus::Cancelled;
-                            job.phase = Some("Discarded".to_string());
+                            job.phase = Some(JobPhase::Discarded);
+                            job.phase_detail = None;
                             job.sequence_id += 1;
                             
-                            let _ = self.app_handle.emit_all("download-cancelled", DownloadCancelledPayload {
+                            self.sink.cancelled(DownloadCancelledPayload {
                                 job_id: id
                             });
                             let _ = resp.send(Ok(()));
@@ -403,15 +937,19 @@ impl JobManagerActor {
                 self.mark_dirty();
 
                 if let (Some(st), Some(p)) = (status_to_emit, path_to_emit) {
-                    let _ = self.app_handle.emit_all("download-complete", DownloadCompletePayload {
+                    self.spawn_post_download_hook(id, &p);
+                    self.maybe_write_cover_art(id, &p);
+                    self.record_completion(&p);
+                    self.sink.complete(DownloadCompletePayload {
                         job_id: id,
                         output_path: p,
                         status: st,
                         used_command: cmd_to_emit,
                     });
                 }
+                self.maybe_finish_group(id);
             },
-            JobMessage::ProcessStarted { id, pid } => {
+            JobMessage::ProcessStarted { id, pid, used_command } => {
                 debug!(target: "core::manager", job_id = ?id, pid = pid, "Subprocess successfully spawned");
                 let mut started = false;
                 if let Some(job) = self.jobs.get_mut(&id) {
@@ -420,6 +958,7 @@ impl JobManagerActor {
                         kill_process(pid);
                     } else {
                         job.pid = Some(pid);
+                        job.used_command = Some(used_command);
                         job.status = JobStatus::Downloading;
                         job.sequence_id += 1;
                         started = true;
@@ -429,17 +968,17 @@ impl JobManagerActor {
                     self.mark_dirty();
                 }
             },
-            JobMessage::UpdateProgress { id, percentage, speed, eta, filename, phase } => {
+            JobMessage::UpdateProgress { id, percentage, speed, eta, filename, phase, detail } => {
                 if let Some(job) = self.jobs.get_mut(&id) {
                     if job.status == JobStatus::Cancelled { return; }
                     
-                    if job.status == JobStatus::FileConflict || job.status == JobStatus::Completed || job.status == JobStatus::Modified || job.status == JobStatus::Error {
+                    if job.status == JobStatus::FileConflict || job.status == JobStatus::Completed || job.status == JobStatus::Modified || job.status == JobStatus::Error || job.status == JobStatus::Skipped {
                         return;
                     }
 
-                    if let Some(ref current_phase) = job.phase {
-                        if current_phase != &phase {
-                            debug!(target: "core::manager", job_id = ?id, "Phase transitioned: '{}' -> '{}'", current_phase, phase);
+                    if let Some(current_phase) = job.phase {
+                        if current_phase != phase {
+                            debug!(target: "core::manager", job_id = ?id, "Phase transitioned: {:?} -> {:?}", current_phase, phase);
                         }
                     }
 
@@ -447,7 +986,8 @@ impl JobManagerActor {
                     job.speed = Some(speed.clone());
                     job.eta = Some(eta.clone());
                     if filename.is_some() { job.filename = filename.clone(); }
-                    job.phase = Some(phase.clone());
+                    job.phase = Some(phase);
+                    job.phase_detail = detail.clone();
                     job.sequence_id += 1;
 
                     self.pending_updates.insert(id, DownloadProgressPayload {
@@ -458,6 +998,7 @@ impl JobManagerActor {
                         eta,
                         filename,
                         phase: Some(phase),
+                        phase_detail: detail,
                         status: Some(job.status.clone())
                     });
                 }
@@ -473,7 +1014,8 @@ impl JobManagerActor {
                     job.status = JobStatus::FileConflict;
                     job.temp_path = Some(temp_path.clone());
                     job.output_path = Some(output_path.clone());
-                    job.phase = Some("File Exists - Action Required".to_string());
+                    job.phase = Some(JobPhase::FileConflict);
+                    job.phase_detail = None;
                     job.is_modified = is_modified;
                     job.used_command = Some(used_command.clone());
                     job.sequence_id += 1;
@@ -485,14 +1027,15 @@ impl JobManagerActor {
                         speed: job.speed.clone().unwrap_or_default(),
                         eta: job.eta.clone().unwrap_or_default(),
                         filename: job.filename.clone(),
-                        phase: job.phase.clone(),
+                        phase: job.phase,
+                        phase_detail: job.phase_detail.clone(),
                         status: Some(JobStatus::FileConflict),
                     });
                 }
 
                 if let Some(p) = payload {
                     self.mark_dirty();
-                    let _ = self.app_handle.emit_all("download-progress-batch", BatchProgressPayload { 
+                    self.sink.progress_batch(BatchProgressPayload { 
                         updates: vec![p]
                     });
                 }
@@ -510,7 +1053,8 @@ impl JobManagerActor {
                     job.status = status.clone();
                     job.progress = 100.0;
                     job.output_path = Some(output_path.clone());
-                    job.phase = Some("Done".to_string());
+                    job.phase = Some(JobPhase::Done);
+                    job.phase_detail = None;
                     job.is_modified = is_modified;
                     job.used_command = Some(used_command.clone());
                     job.sequence_id += 1;
@@ -519,12 +1063,16 @@ impl JobManagerActor {
                 self.persistence_registry.remove(&id);
                 self.mark_dirty();
 
-                let _ = self.app_handle.emit_all("download-complete", DownloadCompletePayload {
+                self.spawn_post_download_hook(id, &output_path);
+                self.maybe_write_cover_art(id, &output_path);
+                self.record_completion(&output_path);
+                self.sink.complete(DownloadCompletePayload {
                     job_id: id,
                     output_path,
                     status,
                     used_command: Some(used_command),
                 });
+                self.maybe_finish_group(id);
             },
             JobMessage::JobError { id, payload } => {
                 error!(target: "core::manager", job_id = ?id, exit_code = ?payload.exit_code, "Job failed: {}", payload.error);
@@ -542,7 +1090,8 @@ impl JobManagerActor {
                     job.sequence_id += 1;
                 }
                 
-                if Self::is_fatal_error(&payload.error) || Self::is_fatal_error(&payload.stderr) {
+                let patterns = self.config_manager.get_config().general.fatal_error_patterns.clone();
+                if is_fatal_error(&payload.error, &patterns) || is_fatal_error(&payload.stderr, &patterns) {
                     debug!(target: "core::manager", job_id = ?id, "Error deemed fatal, removing from persistence registry");
                     self.persistence_registry.remove(&id);
                 } else {
@@ -554,8 +1103,54 @@ impl JobManagerActor {
                     }
                 }
                 self.mark_dirty();
+                self.session_stats.failed += 1;
+                self.dirty_stats = true;
+
+                self.sink.error(payload);
+                self.maybe_finish_group(id);
+
+                if self.config_manager.get_config().general.stop_on_first_error {
+                    self.halt_batch(id);
+                }
+            },
+            JobMessage::JobSkipped { id, reason } => {
+                info!(target: "core::manager", job_id = ?id, "Job skipped: {}", reason);
+
+                self.pending_updates.remove(&id);
+                self.cancel_flags.remove(&id);
+
+                let mut payload = None;
+                if let Some(job) = self.jobs.get_mut(&id) {
+                    if job.status == JobStatus::Cancelled { return; }
+                    job.status = JobStatus::Skipped;
+                    job.phase = Some(JobPhase::Skipped);
+                    job.phase_detail = Some(reason);
+                    job.speed = Some("--".to_string());
+                    job.eta = Some("--".to_string());
+                    job.sequence_id += 1;
+
+                    payload = Some(DownloadProgressPayload {
+                        job_id: id,
+                        percentage: job.progress,
+                        sequence_id: job.sequence_id,
+                        speed: "--".to_string(),
+                        eta: "--".to_string(),
+                        filename: job.filename.clone(),
+                        phase: job.phase,
+                        phase_detail: job.phase_detail.clone(),
+                        status: Some(JobStatus::Skipped),
+                    });
+                }
+
+                self.persistence_registry.remove(&id);
+                self.mark_dirty();
 
-                let _ = self.app_handle.emit_all("download-error", payload);
+                if let Some(p) = payload {
+                    self.sink.progress_batch(BatchProgressPayload {
+                        updates: vec![p]
+                    });
+                }
+                self.maybe_finish_group(id);
             },
             JobMessage::WorkerFinished => {
                 trace!(target: "core::manager", "WorkerFinished signal received");
@@ -575,74 +1170,177 @@ impl JobManagerActor {
                 }
                 self.process_queue();
             },
+            JobMessage::SetRateLimit { id, limit, resp } => {
+                let Some(job) = self.jobs.get_mut(&id) else {
+                    let _ = resp.send(Err("Job not found".into()));
+                    return;
+                };
+                debug!(target: "core::manager", job_id = ?id, limit = ?limit, "Updating per-job rate limit");
+                job.rate_limit = limit.clone();
+                job.sequence_id += 1;
+
+                if let Some(queued) = self.queue.iter_mut().find(|q| q.id == id) {
+                    queued.rate_limit = limit.clone();
+                }
+                if let Some(reg_entry) = self.persistence_registry.get_mut(&id) {
+                    reg_entry.rate_limit = limit;
+                    self.mark_dirty();
+                }
+                let _ = resp.send(Ok(()));
+            },
+            JobMessage::HookFinished { id, exit_code, output } => {
+                match exit_code {
+                    Some(0) => debug!(target: "core::manager", job_id = ?id, "Post-download hook succeeded"),
+                    _ => warn!(target: "core::manager", job_id = ?id, exit_code = ?exit_code, "Post-download hook failed: {}", output),
+                }
+                if let Some(job) = self.jobs.get_mut(&id) {
+                    job.hook_exit_code = exit_code;
+                    job.hook_output = Some(output);
+                    job.sequence_id += 1;
+                }
+            },
+            JobMessage::GetJob { id, resp } => {
+                let _ = resp.send(self.jobs.get(&id).cloned());
+            },
+            JobMessage::GetActiveCount(tx) => {
+                let count = self.jobs.values()
+                    .filter(|j| matches!(j.status, JobStatus::Pending | JobStatus::Downloading))
+                    .count();
+                let _ = tx.send(count as u32);
+            },
             JobMessage::GetPendingCount(tx) => {
                 trace!(target: "core::manager", "Reading persistence file for GetPendingCount");
-                let path = Self::get_persistence_path();
-                if path.exists() {
-                     if let Ok(content) = fs::read_to_string(path) {
-                         if let Ok(jobs) = serde_json::from_str::<Vec<QueuedJob>>(&content) {
-                             let _ = tx.send(jobs.len() as u32);
-                             return;
-                         }
-                     }
-                }
-                let _ = tx.send(0);
+                let count = Self::read_persisted_queue().map_or(0, |file| file.jobs.len());
+                let _ = tx.send(count as u32);
             },
             JobMessage::ResumePending(tx) => {
+                let mut result = ResumeResult::default();
+                if self.pending_resumed {
+                    // A retried call from the UI; everything on disk is already in memory.
+                    debug!(target: "core::manager", "Pending jobs already resumed, ignoring repeat request");
+                    result.already_active = self.persistence_registry.keys().copied().collect();
+                    let _ = tx.send(result);
+                    return;
+                }
+                self.pending_resumed = true;
+
                 info!(target: "core::manager", "Resuming pending jobs from disk");
-                let path = Self::get_persistence_path();
-                let mut resumed = Vec::new();
-                if path.exists() {
-                    if let Ok(content) = fs::read_to_string(path) {
-                        if let Ok(jobs) = serde_json::from_str::<Vec<QueuedJob>>(&content) {
-                            debug!(target: "core::manager", "Found {} jobs on disk to resume", jobs.len());
-                            for job in jobs {
-                                if !self.jobs.contains_key(&job.id) {
-                                    let mut j = Job::new(job.id, job.url.clone());
-                                    j.preset = Some(job.format_preset.clone());
-                                    j.video_resolution = Some(job.video_resolution.clone());
-                                    j.download_path = job.download_path.clone();
-                                    j.filename_template = Some(job.filename_template.clone());
-                                    j.embed_metadata = Some(job.embed_metadata);
-                                    j.embed_thumbnail = Some(job.embed_thumbnail);
-                                    j.restrict_filenames = Some(job.restrict_filenames);
-                                    j.live_from_start = Some(job.live_from_start);
-                                    j.download_sections = job.download_sections.clone();
-                                    
-                                    if let Some(st) = &job.status {
-                                        if st == "error" {
-                                            j.status = JobStatus::Error;
-                                            j.error = job.error.clone();
-                                            j.stderr = job.stderr.clone();
-                                        }
-                                    }
+                let Some(file) = Self::read_persisted_queue() else {
+                    trace!(target: "core::manager", "No persistence file found during ResumePending");
+                    let _ = tx.send(result);
+                    return;
+                };
+                self.warn_on_version_drift(&file).await;
+                debug!(target: "core::manager", "Found {} jobs on disk to resume", file.jobs.len());
+
+                // Stage everything first and commit it in one pass below, so no job can
+                // start (and finish) while other entries from the file are still unregistered.
+                let mut staged: Vec<(Job, QueuedJob)> = Vec::new();
+                let mut dropped = false;
+                for mut job in file.jobs {
+                    if matches!(job.status.as_deref(), Some("completed" | "cancelled")) {
+                        debug!(target: "core::manager", job_id = ?job.id, "Dropping finished job from persistence instead of resuming it");
+                        dropped = true;
+                        continue;
+                    }
+                    let staged_dup = staged.iter().any(|(j, q)| {
+                        j.id == job.id || (q.status.is_none() && job.status.is_none()
+                            && HistoryManager::normalize_url(&q.url) == HistoryManager::normalize_url(&job.url))
+                    });
+                    if staged_dup || self.jobs.contains_key(&job.id) || (job.status.is_none() && self.has_active_url(&job.url)) {
+                        debug!(target: "core::manager", job_id = ?job.id, url = %job.url, "Skipping resume: job already active");
+                        result.already_active.push(job.id);
+                        dropped = true;
+                        continue;
+                    }
 
-                                    self.cancel_flags.insert(job.id, Arc::new(AtomicBool::new(false)));
-                                    self.jobs.insert(job.id, j.clone());
-                                    self.persistence_registry.insert(job.id, job.clone());
-                                    
-                                    if j.status != JobStatus::Error {
-                                        self.queue.push_back(job.clone());
-                                    }
-                                    
-                                    resumed.push(job);
-                                }
-                            }
-                        }
+                    if job.live_from_start && job.status.is_none() {
+                        debug!(target: "core::manager", job_id = ?job.id, "Restoring interrupted live recording");
+                        job.resume_live = true;
                     }
-                } else {
-                    trace!(target: "core::manager", "No persistence file found during ResumePending");
+                    let mut j = Job::new(job.id, job.url.clone());
+                    j.preset = Some(job.format_preset.clone());
+                    j.video_resolution = Some(job.video_resolution.clone());
+                    j.download_path = job.download_path.clone();
+                    j.filename_template = Some(job.filename_template.clone());
+                    j.embed_metadata = Some(job.embed_metadata);
+                    j.embed_thumbnail = Some(job.embed_thumbnail);
+                    j.restrict_filenames = Some(job.restrict_filenames);
+                    j.live_from_start = Some(job.live_from_start);
+                    j.download_sections = job.download_sections.clone();
+                    j.music_metadata = Some(job.music_metadata);
+                    j.group_id = job.group_id;
+                    j.group_total = job.group_total;
+                    j.group_index = job.group_index;
+                    j.group_title = job.group_title.clone();
+                    j.rate_limit = job.rate_limit.clone();
+
+                    if job.status.as_deref() == Some("error") {
+                        j.status = JobStatus::Error;
+                        j.error = job.error.clone();
+                        j.stderr = job.stderr.clone();
+                    }
+                    staged.push((j, job));
                 }
-                self.process_queue(); 
-                let _ = tx.send(resumed);
+
+                for (j, job) in staged {
+                    self.cancel_flags.insert(job.id, Arc::new(AtomicBool::new(false)));
+                    self.persistence_registry.insert(job.id, job.clone());
+                    if j.status != JobStatus::Error {
+                        self.queue.push_back(job.clone());
+                    }
+                    self.jobs.insert(job.id, j);
+                    result.resumed.push(job);
+                }
+                if dropped {
+                    // The file still lists the entries that were not restored.
+                    self.mark_dirty();
+                }
+
+                self.process_queue();
+                let _ = tx.send(result);
             },
             JobMessage::ClearPending => {
                 info!(target: "core::manager", "Clearing pending jobs queue and disk persistence");
                 let _ = self.persistence_tx.try_send(PersistenceMsg::Clear);
                 self.clean_temp_directory().await;
             },
+            JobMessage::ClearFinishedPersistence(tx) => {
+                let is_finished = |status: Option<&str>| matches!(status, Some("error" | "cancelled" | "completed"));
+                let removed = if self.pending_resumed {
+                    let before = self.persistence_registry.len();
+                    let jobs = &self.jobs;
+                    self.persistence_registry.retain(|id, entry| {
+                        let terminal = jobs.get(id).is_some_and(|j| matches!(j.status, JobStatus::Completed | JobStatus::Error | JobStatus::Cancelled));
+                        !terminal && !is_finished(entry.status.as_deref())
+                    });
+                    let removed = before - self.persistence_registry.len();
+                    if removed > 0 {
+                        self.mark_dirty();
+                    }
+                    removed
+                } else {
+                    // Nothing restored yet, so the file on disk is the only copy.
+                    let jobs = Self::read_persisted_queue().map(|file| file.jobs).unwrap_or_default();
+                    let before = jobs.len();
+                    let kept: Vec<QueuedJob> = jobs.into_iter().filter(|j| !is_finished(j.status.as_deref())).collect();
+                    let removed = before - kept.len();
+                    if kept.is_empty() {
+                        let _ = self.persistence_tx.try_send(PersistenceMsg::Clear);
+                    } else if removed > 0 {
+                        let _ = self.persistence_tx.try_send(PersistenceMsg::Save(kept));
+                    }
+                    removed
+                };
+                info!(target: "core::manager", removed, "Cleared finished entries from persistence");
+                let _ = tx.send(removed as u32);
+            },
             JobMessage::SyncState(tx) => {
                 trace!(target: "core::manager", "Aggregating full state for SyncState");
+                let positions: HashMap<Uuid, u32> = self.queue.iter()
+                    .enumerate()
+                    .map(|(i, q)| (q.id, i as u32))
+                    .collect();
                 let mut downloads: Vec<Download> = Vec::new();
                 for job in self.jobs.values() {
                     downloads.push(Download {
@@ -657,7 +1355,8 @@ impl JobManagerActor {
                         temp_path: job.temp_path.clone(),
                         error: job.error.clone(),
                         filename: job.filename.clone(),
-                        phase: job.phase.clone(),
+                        phase: job.phase,
+                        phase_detail: job.phase_detail.clone(),
                         exit_code: job.exit_code,
                         stderr: job.stderr.clone(),
                         logs: job.logs.clone(),
@@ -670,27 +1369,232 @@ impl JobManagerActor {
                         restrict_filenames: job.restrict_filenames,
                         live_from_start: job.live_from_start,
                         download_sections: job.download_sections.clone(),
+                        music_metadata: job.music_metadata,
                         used_command: job.used_command.clone(),
+                        queue_position: positions.get(&job.id).copied(),
+                        group_id: job.group_id,
+                        group_total: job.group_total,
+                        rate_limit: job.rate_limit.clone(),
+                        hook_exit_code: job.hook_exit_code,
+                        hook_output: job.hook_output.clone(),
+                        retry_count: job.retry_count,
+                        max_retries: job.max_retries,
                     });
                 }
+                // Running and finished jobs first, then the queue in execution order.
+                downloads.sort_by_key(|d| (d.queue_position.is_some(), d.queue_position));
                 let _ = tx.send(downloads);
             },
+            JobMessage::PauseQueue { suspend_running, resp } => {
+                info!(target: "core::manager", suspend_running = suspend_running, "Pausing queue");
+                self.queue_paused = true;
+                self.persist_queue_paused();
+
+                if suspend_running {
+                    for job in self.jobs.values() {
+                        if job.status != JobStatus::Downloading || self.suspended_jobs.contains(&job.id) {
+                            continue;
+                        }
+                        if let Some(pid) = job.pid {
+                            match set_process_suspended(pid, true) {
+                                Ok(_) => { self.suspended_jobs.insert(job.id); },
+                                Err(e) => warn!(target: "core::manager", job_id = ?job.id, "Failed to suspend process {}: {}", pid, e),
+                            }
+                        }
+                    }
+                }
+                let _ = resp.send(self.queue_summary());
+            },
+            JobMessage::ResumeQueue(tx) => {
+                info!(target: "core::manager", suspended = self.suspended_jobs.len(), "Resuming queue");
+                self.queue_paused = false;
+                self.persist_queue_paused();
+
+                for id in std::mem::take(&mut self.suspended_jobs) {
+                    // The disk guard only suspends on entering low space, so it takes over
+                    // these holds and releases them itself once space recovers.
+                    if self.low_disk_space {
+                        self.suspended_jobs.insert(id);
+                        self.disk_suspended_jobs.insert(id);
+                        continue;
+                    }
+                    if let Some(pid) = self.jobs.get(&id).and_then(|j| j.pid) {
+                        if let Err(e) = set_process_suspended(pid, false) {
+                            warn!(target: "core::manager", job_id = ?id, "Failed to resume process {}: {}", pid, e);
+                        }
+                    }
+                }
+                self.process_queue();
+                let _ = tx.send(self.queue_summary());
+            },
+            JobMessage::GetQueueSummary(tx) => {
+                let _ = tx.send(self.queue_summary());
+            },
+            JobMessage::GetStats(tx) => {
+                let _ = tx.send(self.usage_stats());
+            },
+            JobMessage::GetGroupProgress(tx) => {
+                let _ = tx.send(self.group_progress());
+            },
+            JobMessage::CanRetry { id, resp } => {
+                let _ = resp.send(self.retry_eligibility(id, true));
+            },
+            JobMessage::GetGroupFailures { group_id, resp } => {
+                let failures = self.jobs.values()
+                    .filter(|j| j.group_id == Some(group_id) && j.status == JobStatus::Error)
+                    .map(|j| GroupFailure {
+                        id: j.id,
+                        url: j.url.clone(),
+                        is_fatal: !self.retry_eligibility(j.id, true).can_retry,
+                    })
+                    .collect();
+                let _ = resp.send(failures);
+            },
+            JobMessage::RequeueJob { id, immediate, split_streams, resp } => {
+                let eligibility = self.retry_eligibility(id, !immediate);
+                if !eligibility.can_retry {
+                    let _ = resp.send(Err(eligibility.reason.unwrap_or_else(|| "Job cannot be retried".into())));
+                    return;
+                }
+                let (Some(job), Some(entry)) = (self.jobs.get_mut(&id), self.persistence_registry.get_mut(&id)) else {
+                    let _ = resp.send(Err("Job not found".into()));
+                    return;
+                };
+                info!(target: "core::manager", job_id = ?id, immediate, attempt = job.retry_count + 1, "Re-queuing failed job");
+
+                entry.status = None;
+                entry.error = None;
+                entry.stderr = None;
+                if split_streams {
+                    entry.split_streams = true;
+                }
+                let queued = entry.clone();
+
+                job.status = JobStatus::Pending;
+                job.progress = 0.0;
+                job.pid = None;
+                job.error = None;
+                job.stderr = None;
+                job.exit_code = None;
+                job.logs = None;
+                job.speed = Some("--".to_string());
+                job.eta = Some("--".to_string());
+                job.phase = Some(JobPhase::Queued);
+                job.phase_detail = None;
+                if !immediate {
+                    job.retry_count += 1;
+                }
+                self.session_stats.retries += 1;
+                self.dirty_stats = true;
+                job.sequence_id += 1;
+
+                let payload = DownloadProgressPayload {
+                    job_id: id,
+                    percentage: 0.0,
+                    sequence_id: job.sequence_id,
+                    speed: "--".to_string(),
+                    eta: "--".to_string(),
+                    filename: job.filename.clone(),
+                    phase: job.phase,
+                    phase_detail: job.phase_detail.clone(),
+                    status: Some(JobStatus::Pending),
+                };
+                if let Some(group_id) = job.group_id {
+                    // Let the playlist file be rewritten once the retried items finish.
+                    self.finished_groups.remove(&group_id);
+                }
+
+                self.cancel_flags.insert(id, Arc::new(AtomicBool::new(false)));
+                if immediate {
+                    self.queue.push_front(queued);
+                } else {
+                    self.queue.push_back(queued);
+                }
+                self.sink.progress_batch(BatchProgressPayload { updates: vec![payload] });
+                self.mark_dirty();
+                self.process_queue();
+                let _ = resp.send(Ok(()));
+            },
+            JobMessage::SetMaxRetries { id, max_retries, resp } => {
+                let Some(job) = self.jobs.get_mut(&id) else {
+                    let _ = resp.send(Err("Job not found".into()));
+                    return;
+                };
+                debug!(target: "core::manager", job_id = ?id, max_retries, "Updating retry budget");
+                job.max_retries = max_retries;
+                job.sequence_id += 1;
+                let _ = resp.send(Ok(()));
+            },
+            JobMessage::SetOutputPath { id, output_path, resp } => {
+                let Some(job) = self.jobs.get_mut(&id) else {
+                    let _ = resp.send(Err("Job not found".into()));
+                    return;
+                };
+                debug!(target: "core::manager", job_id = ?id, "Output relocated to {}", output_path);
+                job.output_path = Some(output_path.clone());
+                job.sequence_id += 1;
+
+                self.sink.complete(DownloadCompletePayload {
+                    job_id: id,
+                    output_path,
+                    status: job.status.clone(),
+                    used_command: job.used_command.clone(),
+                });
+                let _ = resp.send(Ok(()));
+            },
             JobMessage::Shutdown(_) => {}
         }
     }
 
+    /// Retry needs an errored job whose failure isn't fatal and whose original
+    /// request is still in the persistence registry to re-queue from.
+    fn retry_eligibility(&self, id: Uuid, enforce_budget: bool) -> RetryEligibility {
+        let deny = |reason: &str| RetryEligibility { can_retry: false, reason: Some(reason.to_string()) };
+
+        let Some(job) = self.jobs.get(&id) else { return deny("Job not found") };
+        if job.status != JobStatus::Error {
+            return deny("Only failed jobs can be retried");
+        }
+
+        let patterns = self.config_manager.get_config().general.fatal_error_patterns.clone();
+        let error = job.error.as_deref().unwrap_or_default();
+        let stderr = job.stderr.as_deref().unwrap_or_default();
+        if is_fatal_error(error, &patterns) || is_fatal_error(stderr, &patterns) {
+            return deny(&format!("The error is permanent: {}", error));
+        }
+        if !self.persistence_registry.contains_key(&id) {
+            return deny("The original request is no longer available");
+        }
+        if enforce_budget && job.retry_count >= job.max_retries {
+            return deny(&format!("Retry budget exhausted ({} of {} attempts used)", job.retry_count, job.max_retries));
+        }
+
+        RetryEligibility { can_retry: true, reason: None }
+    }
+
     fn flush_updates(&mut self) {
         if self.pending_updates.is_empty() { return; }
 
         trace!(target: "core::manager", "Flushing {} pending progress updates to UI", self.pending_updates.len());
         let updates: Vec<DownloadProgressPayload> = self.pending_updates.values().cloned().collect();
         self.pending_updates.clear();
-        let _ = self.app_handle.emit_all("download-progress-batch", BatchProgressPayload { updates });
+        self.sink.progress_batch(BatchProgressPayload { updates });
     }
 
     fn process_queue(&mut self) {
-        let config_manager = self.app_handle.state::<Arc<ConfigManager>>();
-        let config = config_manager.get_config().general.clone();
+        if self.queue_paused {
+            trace!(target: "core::manager", queued = self.queue.len(), "Queue paused, not starting new jobs");
+            return;
+        }
+        if !self.queue.is_empty() && !self.low_disk_space {
+            self.check_disk_space();
+        }
+        if self.low_disk_space {
+            trace!(target: "core::manager", queued = self.queue.len(), "Low disk space, not starting new jobs");
+            return;
+        }
+
+        let config = self.config_manager.get_config().general.clone();
 
         let effective_concurrent_limit = if config.use_concurrent_fragments {
             1
@@ -714,15 +1618,11 @@ impl JobManagerActor {
                  self.active_network_jobs += 1;
                  self.active_process_instances += 1;
                  
-                 let tx = self.self_sender.clone();
-                 let app = self.app_handle.clone();
                  let cancel_flag = self.cancel_flags.get(&next_job.id)
                      .cloned()
                      .unwrap_or_else(|| Arc::new(AtomicBool::new(false)));
                  
-                 tauri::async_runtime::spawn(async move {
-                    run_download_process(next_job, app, tx, cancel_flag).await;
-                 });
+                 self.runner.spawn(next_job, self.self_sender.clone(), cancel_flag);
             } else {
                 break;
             }
@@ -737,7 +1637,7 @@ impl JobManagerActor {
         let active_count = active_jobs.len();
 
         if active_count == 0 {
-            native::clear_taskbar_progress(&self.app_handle);
+            self.sink.clear_overall_progress();
             return;
         }
 
@@ -745,25 +1645,21 @@ impl JobManagerActor {
         let aggregated = total_progress / (active_count as f32);
         let has_error = self.jobs.values().any(|j| j.status == JobStatus::Error || j.status == JobStatus::FileConflict);
 
-        let app_handle_for_closure = self.app_handle.clone();
-        
-        let _ = self.app_handle.run_on_main_thread(move || {
-            native::set_taskbar_progress(&app_handle_for_closure, (aggregated / 100.0) as f64, has_error);
-        });
+        let downloading: Vec<&&Job> = active_jobs.iter().filter(|j| j.status == JobStatus::Downloading).collect();
+        let bytes_per_sec: f64 = downloading.iter()
+            .filter_map(|j| j.speed.as_deref().and_then(parse_speed))
+            .sum();
+
+        self.sink.overall_progress((aggregated / 100.0) as f64, has_error, downloading.len() as u32, bytes_per_sec);
     }
 
     fn trigger_finished_notification(&mut self) {
-        use tauri::api::notification::Notification;
         let count = self.completed_session_count;
         if count == 0 { return; }
 
         debug!(target: "core::manager", "Triggering desktop notification for {} finished downloads", count);
 
-        let _ = Notification::new(self.app_handle.config().tauri.bundle.identifier.clone())
-            .title("Downloads Finished")
-            .body(format!("Queue processed. {} files handled.", count))
-            .icon("icons/128x128.png") 
-            .show();
+        self.sink.queue_finished(count);
 
         self.completed_session_count = 0;
     }
@@ -773,8 +1669,7 @@ impl JobManagerActor {
         if self.jobs.values().any(|j| j.status == JobStatus::FileConflict) { return; }
 
         info!(target: "core::manager", "No active tasks remaining. Cleaning temporary directory.");
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-        let temp_dir = home.join(".multiyt-dlp").join("temp_downloads");
+        let temp_dir = paths::temp_dir();
         
         if temp_dir.exists() {
             async fn robust_remove_dir(path: &Path) -> std::io::Result<()> {
@@ -826,3 +1721,119 @@ fn kill_process(pid: u32) {
         let _ = cmd.spawn();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::sync::atomic::AtomicUsize;
+    use crate::models::DownloadErrorPayload;
+
+    #[derive(Default)]
+    struct RecordingSink {
+        completed: Mutex<Vec<Uuid>>,
+        queue_finished: Mutex<Vec<u32>>,
+    }
+
+    impl ProgressSink for RecordingSink {
+        fn progress_batch(&self, _: BatchProgressPayload) {}
+        fn complete(&self, payload: DownloadCompletePayload) {
+            self.completed.lock().unwrap().push(payload.job_id);
+        }
+        fn error(&self, _: DownloadErrorPayload) {}
+        fn cancelled(&self, _: DownloadCancelledPayload) {}
+        fn batch_halted(&self, _: BatchHaltedPayload) {}
+        fn low_disk_space(&self, _: LowDiskSpacePayload) {}
+        fn overall_progress(&self, _: f64, _: bool, _: u32, _: f64) {}
+        fn clear_overall_progress(&self) {}
+        fn queue_finished(&self, handled: u32) {
+            self.queue_finished.lock().unwrap().push(handled);
+        }
+    }
+
+    /// Pretends to download for a moment and reports success, tracking how many
+    /// jobs ran at once.
+    #[derive(Default)]
+    struct FakeRunner {
+        running: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+    }
+
+    impl JobRunner for FakeRunner {
+        fn spawn(&self, job: QueuedJob, tx_actor: mpsc::Sender<JobMessage>, _cancel_flag: Arc<AtomicBool>) {
+            let running = self.running.clone();
+            let peak = self.peak.clone();
+            tokio::spawn(async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                time::sleep(Duration::from_millis(50)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                let _ = tx_actor.send(JobMessage::JobCompleted {
+                    id: job.id,
+                    output_path: format!("/nonexistent/{}.mp4", job.id),
+                    is_modified: false,
+                    used_command: "fake".into(),
+                }).await;
+                let _ = tx_actor.send(JobMessage::WorkerFinished).await;
+            });
+        }
+    }
+
+    fn queued_job(url: &str) -> QueuedJob {
+        serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "url": url,
+            "download_path": null,
+            "format_preset": "best",
+            "video_resolution": "best",
+            "embed_metadata": false,
+            "embed_thumbnail": false,
+            "filename_template": "%(title)s.%(ext)s",
+            "restrict_filenames": false,
+            "live_from_start": false,
+            "download_sections": null,
+            "status": null,
+            "error": null,
+            "stderr": null,
+        })).unwrap()
+    }
+
+    #[tokio::test]
+    async fn queue_runs_every_job_within_the_concurrency_limit() {
+        let config_manager = Arc::new(ConfigManager::new());
+        let mut general = config_manager.get_config().general.clone();
+        general.max_concurrent_downloads = 2;
+        general.use_concurrent_fragments = false;
+        general.min_free_space_mb = 0;
+        config_manager.update_general(general).unwrap();
+
+        let sink = Arc::new(RecordingSink::default());
+        let runner = Arc::new(FakeRunner::default());
+        let manager = JobManagerHandle::with_sink(config_manager, "0.0.0-test".into(), sink.clone(), runner.clone());
+
+        let jobs: Vec<QueuedJob> = (0..5).map(|i| queued_job(&format!("https://example.com/watch?v={}", i))).collect();
+        for job in &jobs {
+            manager.add_job(job.clone()).await.unwrap();
+        }
+        assert_eq!(manager.add_job(queued_job("https://example.com/watch?v=0")).await, Err(DUPLICATE_JOB_ERROR.to_string()));
+
+        let deadline = time::Instant::now() + Duration::from_secs(10);
+        while sink.completed.lock().unwrap().len() < jobs.len() {
+            assert!(time::Instant::now() < deadline, "queue did not drain");
+            time::sleep(Duration::from_millis(20)).await;
+        }
+
+        assert!(runner.peak.load(Ordering::SeqCst) <= 2, "ran {} jobs at once", runner.peak.load(Ordering::SeqCst));
+        for job in &jobs {
+            let status = manager.get_job(job.id).await.map(|j| j.status);
+            assert_eq!(status, Some(JobStatus::Completed));
+        }
+        assert_eq!(manager.get_active_count().await, 0);
+        // Workers report WorkerFinished after JobCompleted; give the last one a moment.
+        while sink.queue_finished.lock().unwrap().is_empty() {
+            assert!(time::Instant::now() < deadline, "queue_finished never fired");
+            time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(sink.queue_finished.lock().unwrap().as_slice(), &[5]);
+    }
+}
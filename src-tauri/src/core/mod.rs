@@ -5,4 +5,10 @@ pub mod logging;
 pub mod deps;
 pub mod native;
 pub mod history;
-pub mod transport;
\ No newline at end of file
+pub mod transport;
+pub mod secrets;
+pub mod cookies;
+pub mod sink;
+pub mod playlist;
+pub mod hooks;
+pub mod paths;
@@ -1,5 +1,7 @@
 use tauri::{AppHandle, Manager, Window};
 
+#[cfg(target_os = "windows")]
+use windows::core::PCWSTR;
 #[cfg(target_os = "windows")]
 use windows::Win32::{
     System::Com::{CoCreateInstance, CoInitialize, CLSCTX_ALL},
@@ -17,17 +19,25 @@ use cocoa::foundation::NSString;
 /// Updates the taskbar progress.
 /// `progress` should be between 0.0 and 1.0
 /// `is_error` determines if the bar should be colored red (Windows only)
-pub fn set_taskbar_progress(app: &AppHandle, progress: f64, is_error: bool) {
+/// `status` is shown as the taskbar thumbnail tooltip on Windows and replaces the
+/// percentage in the dock badge on macOS
+pub fn set_taskbar_progress(app: &AppHandle, progress: f64, is_error: bool, status: Option<&str>) {
     let main_window = match app.get_window("main") {
         Some(w) => w,
         None => return,
     };
 
     #[cfg(target_os = "windows")]
-    let _ = set_windows_progress(&main_window, progress, is_error);
+    {
+        let _ = set_windows_progress(&main_window, progress, is_error);
+        let _ = set_windows_tooltip(&main_window, status);
+    }
 
     #[cfg(target_os = "macos")]
-    let _ = set_mac_badge(progress);
+    let _ = set_mac_badge(progress, status);
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let _ = (main_window, progress, is_error, status);
 }
 
 /// Removes progress bar/badge
@@ -38,7 +48,10 @@ pub fn clear_taskbar_progress(app: &AppHandle) {
     };
 
     #[cfg(target_os = "windows")]
-    let _ = set_windows_progress_state(&main_window, false);
+    {
+        let _ = set_windows_progress_state(&main_window, false);
+        let _ = set_windows_tooltip(&main_window, None);
+    }
 
     #[cfg(target_os = "macos")]
     let _ = clear_mac_badge();
@@ -82,10 +95,27 @@ fn set_windows_progress_state(window: &Window, visible: bool) -> Result<(), Stri
     Ok(())
 }
 
+/// `None` restores the default tooltip (the window title).
+#[cfg(target_os = "windows")]
+fn set_windows_tooltip(window: &Window, text: Option<&str>) -> Result<(), String> {
+    let hwnd = window.hwnd().map_err(|e| e.to_string())?;
+    let wide: Option<Vec<u16>> = text.map(|t| t.encode_utf16().chain(std::iter::once(0)).collect());
+    unsafe {
+        let _ = CoInitialize(None);
+        let taskbar_list: ITaskbarList3 = CoCreateInstance(&TaskbarList, None, CLSCTX_ALL)
+            .map_err(|e| e.to_string())?;
+
+        let hwnd_raw = HWND(hwnd.0 as isize);
+        let tip = wide.as_ref().map_or(PCWSTR::null(), |w| PCWSTR(w.as_ptr()));
+        taskbar_list.SetThumbnailTooltip(hwnd_raw, tip).ok();
+    }
+    Ok(())
+}
+
 #[cfg(target_os = "macos")]
-fn set_mac_badge(progress: f64) -> Result<(), String> {
+fn set_mac_badge(progress: f64, status: Option<&str>) -> Result<(), String> {
     let percent = (progress * 100.0) as u32;
-    let label = format!("{}%", percent);
+    let label = status.map_or_else(|| format!("{}%", percent), str::to_string);
     
     unsafe {
         let dock_tile = NSApp().dockTile();
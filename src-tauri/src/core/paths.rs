@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Bundle identifier from tauri.conf.json; Tauri's own app-data folder uses the same name.
+const APP_IDENTIFIER: &str = "net.syntaxfree.multiyt-dlp";
+
+static APP_DATA_ROOT: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
+/// The `.multiyt-dlp` folder holding config, history, logs and temp downloads.
+/// Falls back from the home directory to the platform app-data directory and
+/// finally the system temp dir, so a missing home never panics at startup.
+pub fn app_data_root() -> PathBuf {
+    APP_DATA_ROOT.get_or_init(|| {
+        // Tests that drive the manager or ConfigManager must never touch the user's files.
+        if cfg!(test) {
+            return std::env::temp_dir().join(format!("multiyt-dlp-test-{}", std::process::id()));
+        }
+        if let Some(home) = dirs::home_dir() {
+            return home.join(".multiyt-dlp");
+        }
+        if let Some(data) = dirs::data_dir() {
+            warn!(target: "core::paths", "Home directory unavailable, using app data directory");
+            return data.join(APP_IDENTIFIER);
+        }
+        warn!(target: "core::paths", "Home and app data directories unavailable, falling back to the temp directory");
+        std::env::temp_dir().join(".multiyt-dlp")
+    }).clone()
+}
+
+pub fn config_file() -> PathBuf {
+    app_data_root().join("config.json")
+}
+
+pub fn log_dir() -> PathBuf {
+    app_data_root().join("logs")
+}
+
+/// `downloads.txt`, one downloaded URL per line.
+pub fn history_file() -> PathBuf {
+    app_data_root().join("downloads.txt")
+}
+
+/// `jobs.json`, the queue restored by "resume pending".
+pub fn persistence_file() -> PathBuf {
+    app_data_root().join("jobs.json")
+}
+
+/// Marker file; its presence means the queue was paused when the app last ran.
+pub fn queue_paused_marker() -> PathBuf {
+    app_data_root().join("queue_paused")
+}
+
+/// Parent of every job's working directory. Its contents are deleted on cleanup.
+pub fn temp_dir() -> PathBuf {
+    app_data_root().join("temp_downloads")
+}
+
+/// `stats.json`, lifetime usage counters.
+pub fn stats_file() -> PathBuf {
+    app_data_root().join("stats.json")
+}
+
+/// Managed cookie jar written from pasted text.
+pub fn cookies_file() -> PathBuf {
+    app_data_root().join("cookies.txt")
+}
+
+/// Creates and removes a probe file in `dir`. Permission bits alone can't answer
+/// this (ACLs, read-only mounts, sync-client locks), so actually try a write.
+pub fn check_writable(dir: &Path) -> Result<(), String> {
+    let probe = dir.join(format!(".multiyt-dlp-write-test-{}", uuid::Uuid::new_v4()));
+    std::fs::write(&probe, b"").map_err(|e| format!("Folder is not writable: {}", e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+/// Binaries shared by all Syntax Free apps (yt-dlp, ffmpeg, aria2, JS runtimes).
+pub fn bin_dir() -> PathBuf {
+    let base = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
+    base.join("Syntax Free").join("Common").join("bin")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_writable_leaves_no_probe_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(check_writable(dir.path()), Ok(()));
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+        assert!(check_writable(&dir.path().join("missing")).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::debug;
+
+use crate::config::GeneralConfig;
+
+/// Folder image name picked up by Jellyfin, Plex and Kodi.
+const COVER_FILE_NAME: &str = "cover.jpg";
+const COVER_TIMEOUT_SECS: u64 = 60;
+
+/// One finished item of a playlist group, in playlist order.
+pub struct PlaylistItem {
+    pub title: String,
+    pub path: PathBuf,
+}
+
+/// Strips characters that aren't allowed in file names on any supported OS.
+fn sanitize_file_stem(title: &str) -> String {
+    let cleaned: String = title.chars()
+        .map(|c| if c.is_control() || matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') { '_' } else { c })
+        .collect();
+    let cleaned = cleaned.trim().trim_end_matches('.').trim();
+    let stem: String = cleaned.chars().take(120).collect();
+    if stem.is_empty() { "playlist".to_string() } else { stem }
+}
+
+/// Entries under `dir` are written relative to it so the library can be moved as a whole.
+fn entry_path(dir: &Path, path: &Path) -> String {
+    path.strip_prefix(dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Writes `<dir>/<title>.m3u8` listing `items`, replacing any previous file of that name.
+pub fn write_m3u8(dir: &Path, title: &str, items: &[PlaylistItem]) -> std::io::Result<PathBuf> {
+    let target = dir.join(format!("{}.m3u8", sanitize_file_stem(title)));
+
+    let mut content = String::from("#EXTM3U\n");
+    for item in items {
+        content.push_str(&format!("#EXTINF:-1,{}\n{}\n", item.title.replace(['\r', '\n'], " "), entry_path(dir, &item.path)));
+    }
+
+    fs::create_dir_all(dir)?;
+    let tmp = target.with_extension("m3u8.tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, &target)?;
+    debug!(target: "core::playlist", "Wrote {} entries to {:?}", items.len(), target);
+    Ok(target)
+}
+
+/// Saves the thumbnail of `url` as `<dir>/cover.jpg`. An existing cover is left alone
+/// unless `overwrite` is set; `Ok(None)` means it was kept.
+pub async fn write_cover_art(config: &GeneralConfig, url: &str, dir: &Path, overwrite: bool) -> Result<Option<PathBuf>, String> {
+    let target = dir.join(COVER_FILE_NAME);
+    if target.exists() && !overwrite {
+        debug!(target: "core::playlist", "Keeping existing cover art at {:?}", target);
+        return Ok(None);
+    }
+
+    let mut cmd = crate::commands::downloader::build_probe_command(config)?;
+    cmd.arg("--skip-download")
+       .arg("--no-playlist")
+       .arg("--write-thumbnail")
+       .arg("--convert-thumbnails").arg("jpg")
+       .arg("-P").arg(dir)
+       .arg("-o").arg("thumbnail:cover.%(ext)s");
+    if overwrite {
+        cmd.arg("--force-overwrites");
+    }
+    cmd.arg(url);
+
+    let output = match tokio::time::timeout(Duration::from_secs(COVER_TIMEOUT_SECS), cmd.output()).await {
+        Ok(Ok(out)) => out,
+        Ok(Err(e)) => return Err(e.to_string()),
+        Err(_) => return Err(format!("Thumbnail download timed out after {} seconds", COVER_TIMEOUT_SECS)),
+    };
+    if !output.status.success() || !target.exists() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    debug!(target: "core::playlist", "Wrote cover art to {:?}", target);
+    Ok(Some(target))
+}
@@ -16,12 +16,38 @@ use walkdir::WalkDir;
 use std::collections::VecDeque;
 
 use crate::config::{ConfigManager, GeneralConfig};
-use crate::models::{DownloadFormatPreset, QueuedJob, JobMessage, DownloadErrorPayload};
+use crate::models::{DownloadFormatPreset, JobPhase, QueuedJob, JobMessage, DownloadErrorPayload};
 use crate::commands::system::get_js_runtime_info;
+use crate::core::transport::aria::AriaEngine;
+use crate::core::transport::network;
 
 static FIXUP_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[(?:Fixup\w+)\]").unwrap());
 static DOWNLOAD_START_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[download\]\s+Destination:").unwrap());
+static COMMENTS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[\w+\] (?:Downloading comment|Extracting comments)").unwrap());
+static ARIA2_PROGRESS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[#\w+\s+[^\s(]+\((\d+)%\)(?:.*?\bDL:(\S+?))?(?:.*?\bETA:(\S+?))?\]").unwrap());
+static FILESIZE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\d+(?:\.\d+)?[kKmMgGtT]?$").unwrap());
+static TEMPLATE_FIELD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"%\([^)]*\)[-#0+ ]*\d*(?:\.\d+)?[a-zA-Z]").unwrap());
+static EXPLICIT_EXT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\.[A-Za-z0-9]{1,5}$").unwrap());
+/// One `--sub-langs` entry: a language code or regex, optionally negated with a leading `-`.
+static SUBTITLE_LANG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^-?[A-Za-z0-9_.*+?|()\[\]^$-]+$").unwrap());
+static AUDIO_QUALITY_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:[0-9]|[1-9]\d{1,3}[kK])$").unwrap());
+static FORMAT_UNAVAILABLE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)Requested format (?:is )?not available").unwrap());
 static FILESYSTEM_ERROR_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)(No such file|Invalid argument|cannot be written|WinError 123|Postprocessing: Error opening input files)").unwrap());
+/// ffmpeg/post-processor failures, as opposed to failures fetching the media.
+static POSTPROCESS_ERROR_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)(ERROR: Postprocessing|ffmpeg exited with code|Conversion failed|ffprobe and ffmpeg not found|ffmpeg not found|ffmpeg is not installed|Unable to (?:merge|embed|extract))").unwrap());
+
+/// Audio-only streams first; a combined `best` is only fetched when the site offers nothing else.
+const AUDIO_ONLY_SELECTOR: &str = "bestaudio[acodec!=none]/bestaudio/best";
+/// Native m4a avoids a lossy re-encode when the site already serves AAC.
+const M4A_AUDIO_SELECTOR: &str = "bestaudio[ext=m4a]/bestaudio[acodec!=none]/bestaudio/best";
+/// Upper bound for yt-dlp's `-N` per process.
+const MAX_CONCURRENT_FRAGMENTS: u32 = 16;
+/// Extensions the separate video and audio streams of a split download can have.
+const SPLIT_STREAM_EXTENSIONS: [&str; 7] = ["mp4", "webm", "mkv", "m4a", "opus", "ogg", "mp3"];
+/// Subtitle sidecars yt-dlp can write; moved next to the media like the other sidecars.
+const SUBTITLE_EXTENSIONS: [&str; 10] = ["vtt", "srt", "ass", "ssa", "lrc", "ttml", "srv1", "srv2", "srv3", "json3"];
+/// Fragment connections shared across all concurrently running downloads.
+const FRAGMENT_CONNECTION_BUDGET: u32 = 32;
 
 #[derive(Deserialize, Debug)]
 struct YtDlpJsonProgress {
@@ -31,6 +57,8 @@ struct YtDlpJsonProgress {
     speed: Option<f64>,
     eta: Option<u64>, 
     filename: Option<String>,
+    fragment_index: Option<u64>,
+    fragment_count: Option<u64>,
 }
 
 #[cfg(target_os = "windows")]
@@ -40,8 +68,19 @@ mod win_job {
         JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JobObjectExtendedLimitInformation,
         JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
     };
-    use windows::Win32::Foundation::{HANDLE, CloseHandle};
-
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Thread32First, Thread32Next, THREADENTRY32, TH32CS_SNAPTHREAD,
+    };
+    use windows::Win32::System::Threading::{OpenThread, ResumeThread, SuspendThread, THREAD_SUSPEND_RESUME};
+    use windows::Win32::Foundation::{HANDLE, BOOL, CloseHandle};
+
+    /// Kill-on-close job for one yt-dlp process tree.
+    ///
+    /// The handle is created without a security descriptor and is therefore not
+    /// inheritable, so the app holds the only reference. When the app exits for any
+    /// reason, including a crash or being killed from Task Manager, the kernel closes
+    /// that handle and terminates yt-dlp together with any ffmpeg it spawned.
+    /// BREAKAWAY_OK is deliberately not set, so descendants cannot leave the job.
     pub struct JobObject(HANDLE);
 
     impl JobObject {
@@ -76,6 +115,190 @@ mod win_job {
     impl Drop for JobObject {
         fn drop(&mut self) { unsafe { let _ = CloseHandle(self.0); } }
     }
+
+    /// Applies `op` to every thread of `pid`, returning how many calls succeeded.
+    fn for_each_thread(pid: u32, op: unsafe fn(HANDLE) -> u32) -> Result<u32, String> {
+        unsafe {
+            let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0).map_err(|e| e.to_string())?;
+            let mut entry = THREADENTRY32::default();
+            entry.dwSize = std::mem::size_of::<THREADENTRY32>() as u32;
+
+            let mut touched = 0;
+            if Thread32First(snapshot, &mut entry).as_bool() {
+                loop {
+                    if entry.th32OwnerProcessID == pid {
+                        if let Ok(thread) = OpenThread(THREAD_SUSPEND_RESUME, BOOL(0), entry.th32ThreadID) {
+                            if op(thread) != u32::MAX {
+                                touched += 1;
+                            }
+                            let _ = CloseHandle(thread);
+                        }
+                    }
+                    if !Thread32Next(snapshot, &mut entry).as_bool() {
+                        break;
+                    }
+                }
+            }
+            let _ = CloseHandle(snapshot);
+            Ok(touched)
+        }
+    }
+
+    unsafe fn resume_thread(thread: HANDLE) -> u32 { ResumeThread(thread) }
+    unsafe fn suspend_thread(thread: HANDLE) -> u32 { SuspendThread(thread) }
+
+    /// Resumes the threads of a process that was spawned with CREATE_SUSPENDED
+    /// or frozen by [`suspend_process`].
+    pub fn resume_process(pid: u32) -> Result<(), String> {
+        if for_each_thread(pid, resume_thread)? == 0 {
+            return Err(format!("No threads resumed for process {}", pid));
+        }
+        Ok(())
+    }
+
+    pub fn suspend_process(pid: u32) -> Result<(), String> {
+        if for_each_thread(pid, suspend_thread)? == 0 {
+            return Err(format!("No threads suspended for process {}", pid));
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::os::windows::io::AsRawHandle;
+        use std::os::windows::process::CommandExt;
+        use std::time::{Duration, Instant};
+        use windows::Win32::System::Diagnostics::ToolHelp::{
+            Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+        };
+        use windows::Win32::System::Threading::{
+            GetExitCodeProcess, OpenProcess, TerminateProcess, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_TERMINATE,
+        };
+
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        const CREATE_SUSPENDED: u32 = 0x00000004;
+        const STILL_ACTIVE: u32 = 259;
+
+        /// Finds a process named `exe` whose parent is `parent`. Matching on the name
+        /// skips the conhost.exe that Windows attaches to console children.
+        fn child_of(parent: u32, exe: &str) -> Option<u32> {
+            unsafe {
+                let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0).ok()?;
+                let mut entry = PROCESSENTRY32W::default();
+                entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+                let mut found = None;
+                if Process32FirstW(snapshot, &mut entry).as_bool() {
+                    loop {
+                        let len = entry.szExeFile.iter().position(|&c| c == 0).unwrap_or(entry.szExeFile.len());
+                        let name = String::from_utf16_lossy(&entry.szExeFile[..len]);
+                        if entry.th32ParentProcessID == parent && name.eq_ignore_ascii_case(exe) {
+                            found = Some(entry.th32ProcessID);
+                            break;
+                        }
+                        if !Process32NextW(snapshot, &mut entry).as_bool() {
+                            break;
+                        }
+                    }
+                }
+                let _ = CloseHandle(snapshot);
+                found
+            }
+        }
+
+        /// True if the process exited on its own within `timeout`; kills it otherwise.
+        fn exited_within(child: &mut std::process::Child, timeout: Duration) -> bool {
+            let deadline = Instant::now() + timeout;
+            while Instant::now() < deadline {
+                if let Ok(Some(_)) = child.try_wait() {
+                    return true;
+                }
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            let _ = child.kill();
+            false
+        }
+
+        fn still_running(process: HANDLE) -> bool {
+            let mut code = 0u32;
+            unsafe { GetExitCodeProcess(process, &mut code).as_bool() && code == STILL_ACTIVE }
+        }
+
+        /// Same sequence as `assign_windows_job_object`: spawn suspended, assign,
+        /// resume. Dropping the job must take the grandchild down with the child.
+        #[test]
+        fn dropping_job_kills_child_and_grandchild() {
+            let mut child = std::process::Command::new("cmd")
+                .args(["/c", "ping -n 60 127.0.0.1 >NUL"])
+                .creation_flags(CREATE_NO_WINDOW | CREATE_SUSPENDED)
+                .spawn()
+                .expect("spawn cmd");
+
+            let job = JobObject::new().expect("create job");
+            job.assign_process(child.as_raw_handle()).expect("assign child");
+            resume_process(child.id()).expect("resume child");
+
+            let deadline = Instant::now() + Duration::from_secs(10);
+            let grandchild_pid = loop {
+                if let Some(pid) = child_of(child.id(), "PING.EXE") {
+                    break pid;
+                }
+                assert!(Instant::now() < deadline, "cmd never started ping");
+                std::thread::sleep(Duration::from_millis(50));
+            };
+            // Holding a handle keeps the exit code readable after the process dies.
+            let grandchild = unsafe {
+                OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION | PROCESS_TERMINATE, BOOL(0), grandchild_pid)
+            }
+            .expect("open grandchild");
+            assert!(still_running(grandchild));
+
+            drop(job);
+
+            let deadline = Instant::now() + Duration::from_secs(10);
+            while still_running(grandchild) && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            let grandchild_alive = still_running(grandchild);
+            if grandchild_alive {
+                unsafe { let _ = TerminateProcess(grandchild, 1); }
+            }
+            unsafe { let _ = CloseHandle(grandchild); }
+            let child_exited = exited_within(&mut child, Duration::from_secs(10));
+
+            assert!(!grandchild_alive, "grandchild outlived the job object");
+            assert!(child_exited, "child outlived the job object");
+        }
+    }
+}
+
+/// Freezes or thaws a running download. On Unix the whole process group is
+/// stopped, so ffmpeg/aria2c children pause along with yt-dlp.
+pub fn set_process_suspended(pid: u32, suspended: bool) -> Result<(), String> {
+    #[cfg(not(target_os = "windows"))]
+    {
+        use nix::sys::signal::{self, Signal};
+        use nix::unistd::Pid;
+        let sig = if suspended { Signal::SIGSTOP } else { Signal::SIGCONT };
+        signal::kill(Pid::from_raw(-(pid as i32)), sig).map_err(|e| e.to_string())
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        if suspended { win_job::suspend_process(pid) } else { win_job::resume_process(pid) }
+    }
+}
+
+/// Force-kills whatever is left of a cancelled download's process group. yt-dlp dies on
+/// the manager's SIGTERM, but ffmpeg treats SIGTERM as "finish up" and can still complete
+/// a merge after the temp dir has been cleaned. On Windows the job object
+/// (`JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE`) already takes the whole tree down.
+#[cfg(not(target_os = "windows"))]
+fn kill_process_group(pid: u32) {
+    use nix::sys::signal::{self, Signal};
+    use nix::unistd::Pid;
+    // ESRCH just means every member has already exited.
+    let _ = signal::kill(Pid::from_raw(-(pid as i32)), Signal::SIGKILL);
 }
 
 struct WorkerGuard {
@@ -102,6 +325,9 @@ pub async fn run_download_process(
     let job_id = job_data.id;
     let mut preserve_temp_file = false;
     let mut fallback_level = 0;
+    // Set only by the filesystem-error path, so a job that starts out with
+    // restrict_filenames isn't labelled as a retry.
+    let mut is_retry = false;
 
     let _ = tx_actor.send(JobMessage::UpdateProgress {
         id: job_id,
@@ -109,11 +335,19 @@ pub async fn run_download_process(
         speed: "Starting...".to_string(),
         eta: "Calculating...".to_string(),
         filename: None,
-        phase: "Initializing Process...".to_string(),
+        phase: JobPhase::Initializing,
+        detail: None,
     }).await;
 
     let config_manager = app_handle.state::<Arc<ConfigManager>>();
 
+    if config_manager.get_config().general.is_lazy_provisioning() {
+        if let Err(e) = provision_core_dependencies(job_id, &app_handle, &tx_actor).await {
+            let _ = tx_actor.send(construct_error(job_id, e, None, String::new(), VecDeque::new())).await;
+            return;
+        }
+    }
+
     loop {
         if cancel_flag.load(Ordering::Relaxed) {
             debug!(target: "core::process", job_id = ?job_id, "Job cancellation detected. Aborting outer process loop.");
@@ -122,7 +356,7 @@ pub async fn run_download_process(
 
         info!(target: "core::process", job_id = ?job_id, "Preparing execution environment for URL (Fallback Level {})", fallback_level);
         
-        let (target_dir, unique_temp_dir) = match prepare_directories(job_id, &job_data.download_path) {
+        let (target_dir, unique_temp_dir) = match prepare_directories(job_id, &job_data.download_path, job_data.resume_live) {
             Ok(dirs) => dirs,
             Err(e) => {
                 let _ = tx_actor.send(construct_error(job_id, e.clone(), None, String::new(), VecDeque::new())).await;
@@ -131,9 +365,16 @@ pub async fn run_download_process(
         };
 
         let general_config = config_manager.get_config().general.clone();
-        let bin_dir = crate::core::deps::get_common_bin_dir();
+        let bin_dir = crate::core::paths::bin_dir();
         
-        let (mut cmd, used_command) = build_command(&job_data, &unique_temp_dir, &general_config, &bin_dir);
+        let (mut cmd, used_command) = match build_command(&job_data, &unique_temp_dir, &general_config, &bin_dir) {
+            Ok(built) => built,
+            Err(e) => {
+                let _ = tx_actor.send(construct_error(job_id, e.clone(), None, String::new(), VecDeque::new())).await;
+                let _ = std::fs::remove_dir_all(&unique_temp_dir);
+                return;
+            }
+        };
 
         info!(target: "core::process", job_id = ?job_id, "Spawning yt-dlp: {}", used_command);
 
@@ -148,16 +389,17 @@ pub async fn run_download_process(
         };
 
         #[cfg(target_os = "windows")]
-        let _job_object = assign_windows_job_object(&child, job_id);
+        let _job_object = assign_windows_job_object(&mut child, job_id);
 
-        if let Some(pid) = child.id() {
-             let _ = tx_actor.send(JobMessage::ProcessStarted { id: job_id, pid }).await;
+        let child_pid = child.id();
+        if let Some(pid) = child_pid {
+             let _ = tx_actor.send(JobMessage::ProcessStarted { id: job_id, pid, used_command: used_command.clone() }).await;
         }
 
-        if job_data.restrict_filenames && fallback_level == 0 {
+        if std::mem::take(&mut is_retry) {
             let _ = tx_actor.send(JobMessage::UpdateProgress {
                 id: job_id, percentage: 0.0, speed: "Retrying...".to_string(), eta: "--".to_string(), filename: None,
-                phase: "Sanitizing Filenames (Retry)".to_string(),
+                phase: JobPhase::RetryingSanitized, detail: None,
             }).await;
         }
 
@@ -166,23 +408,50 @@ pub async fn run_download_process(
         let stderr = child.stderr.take().expect("Failed to capture stderr");
         let rx = spawn_io_readers(stdout, stderr);
 
-        let telemetry = monitor_process(job_id, rx, &unique_temp_dir, &tx_actor).await;
+        let telemetry = monitor_process(job_id, rx, &unique_temp_dir, &tx_actor, general_config.speed_smoothing).await;
 
         let status = child.wait().await.expect("Child process error");
 
         if cancel_flag.load(Ordering::Relaxed) {
             debug!(target: "core::process", job_id = ?job_id, "Job cancellation detected. Aborting outer process loop.");
+            #[cfg(not(target_os = "windows"))]
+            if let Some(pid) = child_pid {
+                kill_process_group(pid);
+            }
             break;
         }
 
         if status.success() {
             preserve_temp_file = handle_process_success(
-                job_id, &job_data, telemetry, &unique_temp_dir, &target_dir, &tx_actor, fallback_level, used_command
+                job_id, &job_data, telemetry, &unique_temp_dir, &target_dir, &tx_actor, fallback_level, used_command, &cancel_flag
             ).await;
             break;
+        } else if job_data.resume_live {
+            // The stream most likely ended (or went private) while the app was down:
+            // keep what was captured rather than retrying into nothing.
+            job_data.resume_live = false;
+            if let Some(partial) = finalize_partial_recording(job_id, &unique_temp_dir) {
+                warn!(target: "core::process", job_id = ?job_id, "Live stream could not be resumed, finalizing captured portion {:?}", partial);
+                let telemetry = ProcessTelemetry {
+                    detected_output_path: Some(partial.to_string_lossy().to_string()),
+                    detected_filename_only: partial.file_name().map(|n| n.to_string_lossy().to_string()),
+                    skip_reason: None,
+                    ..telemetry
+                };
+                preserve_temp_file = handle_process_success(
+                    job_id, &job_data, telemetry, &unique_temp_dir, &target_dir, &tx_actor, fallback_level, used_command, &cancel_flag
+                ).await;
+                break;
+            }
+            let should_continue = handle_process_error(
+                job_id, &mut job_data, status, telemetry, &tx_actor, &mut fallback_level, &mut is_retry
+            ).await;
+            if !should_continue {
+                break;
+            }
         } else {
             let should_continue = handle_process_error(
-                job_id, &mut job_data, status, telemetry, &tx_actor, &mut fallback_level
+                job_id, &mut job_data, status, telemetry, &tx_actor, &mut fallback_level, &mut is_retry
             ).await;
             
             if !should_continue {
@@ -202,7 +471,8 @@ pub async fn run_download_process(
 
 fn prepare_directories(
     job_id: uuid::Uuid,
-    download_path: &Option<String>
+    download_path: &Option<String>,
+    keep_temp: bool
 ) -> Result<(PathBuf, PathBuf), String> {
     let target_dir = if let Some(ref path) = download_path {
         PathBuf::from(path)
@@ -216,16 +486,19 @@ fn prepare_directories(
         }
     };
     
+    crate::config::validate_download_path(&target_dir)?;
+
     if !target_dir.exists() { 
         trace!(target: "core::process", job_id = ?job_id, "Creating target directory: {:?}", target_dir);
         let _ = std::fs::create_dir_all(&target_dir); 
     }
     
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    let base_temp_dir = home.join(".multiyt-dlp").join("temp_downloads");
+    let base_temp_dir = crate::core::paths::temp_dir();
     let unique_temp_dir = base_temp_dir.join(job_id.to_string());
 
-    if unique_temp_dir.exists() { 
+    if unique_temp_dir.exists() && keep_temp {
+        info!(target: "core::process", job_id = ?job_id, "Keeping partial live recording in {:?}", unique_temp_dir);
+    } else if unique_temp_dir.exists() { 
         trace!(target: "core::process", job_id = ?job_id, "Wiping existing unique temp directory");
         let _ = std::fs::remove_dir_all(&unique_temp_dir); 
     }
@@ -234,12 +507,25 @@ fn prepare_directories(
     Ok((target_dir, unique_temp_dir))
 }
 
+/// The job's `-o` template. Split downloads tag each file with its format id, since
+/// the video and audio stream may share an extension.
+fn output_template(job_data: &QueuedJob) -> String {
+    let template = &job_data.filename_template;
+    if !job_data.split_streams || job_data.format_preset.is_audio() {
+        return template.clone();
+    }
+    match template.strip_suffix(".%(ext)s") {
+        Some(stem) => format!("{}.f%(format_id)s.%(ext)s", stem),
+        None => template.clone(),
+    }
+}
+
 fn build_command(
     job_data: &QueuedJob,
     unique_temp_dir: &Path,
     general_config: &GeneralConfig,
     bin_dir: &Path,
-) -> (Command, String) {
+) -> Result<(Command, String), String> {
     let mut yt_dlp_cmd = "yt-dlp".to_string();
     let local_exe = bin_dir.join(if cfg!(windows) { "yt-dlp.exe" } else { "yt-dlp" });
     if local_exe.exists() { yt_dlp_cmd = local_exe.to_string_lossy().to_string(); }
@@ -280,40 +566,129 @@ fn build_command(
         if !browser.trim().is_empty() && browser != "none" { cmd.arg("--cookies-from-browser").arg(browser); }
     }
 
-    if general_config.use_concurrent_fragments {
-        cmd.arg("-N").arg(general_config.concurrent_fragments.to_string());
-    } else {
-        cmd.arg("-N").arg("1");
+    if general_config.no_check_certificate {
+        warn!(target: "core::process", job_id = ?job_data.id, "TLS certificate verification disabled for this download");
+        cmd.arg("--no-check-certificates");
+    }
+
+    // yt-dlp hands the same proxy to aria2c when that is the external downloader.
+    if let Some(proxy) = network::external_proxy(&network::NetworkOptions::from_config(general_config))? {
+        cmd.arg("--proxy").arg(proxy);
+    }
+
+    let aria_exe = bin_dir.join(if cfg!(windows) { "aria2c.exe" } else { "aria2c" });
+    let use_aria = general_config.use_aria2_for_media && aria_exe.exists() && !job_data.live_from_start;
+    let plan = concurrency_plan(general_config, use_aria);
+    trace!(target: "core::process", job_id = ?job_data.id, fragments = plan.fragments, aria2_connections = ?plan.aria2_connections, "Connection plan");
+
+    // -N only parallelises fragmented (HLS/DASH) formats; 1 is yt-dlp's default.
+    if plan.fragments > 1 {
+        cmd.arg("-N").arg(plan.fragments.to_string());
+    }
+
+    if let Some(connections) = plan.aria2_connections {
+        // HLS/DASH stay on the native downloader; aria2 handles fragmented manifests poorly.
+        debug!(target: "core::process", job_id = ?job_data.id, "Using aria2c as external downloader for media");
+        cmd.arg("--downloader").arg("aria2c")
+            .arg("--downloader").arg("m3u8,dash:native")
+            .arg("--downloader-args").arg(format!("aria2c:-x {0} -s {0} -k 1M --show-console-readout=false --summary-interval=1 --console-log-level=warn", connections));
     }
 
     cmd.arg("--ignore-config");
 
     cmd.arg(&job_data.url)
-        .arg("-o").arg(&job_data.filename_template) 
+        .args(naming_args(job_data))
         .arg("--no-playlist")
         .arg("--no-simulate") 
         .arg("--newline")
-        .arg("--windows-filenames")
         .arg("--encoding").arg("utf-8")
         .arg("--progress") 
         .arg("--progress-template").arg("download:%(progress)j")
         .arg("--print").arg("after_move:filepath");
 
+    // yt-dlp has no global "non-interactive" switch; the only prompts it can raise
+    // (login/2FA/video password via getpass) read stdin, so a closed stdin makes them
+    // fail immediately instead of hanging the worker. Browser cookie extraction talks
+    // to the OS keyring directly and is unaffected.
+    cmd.stdin(Stdio::null());
     cmd.stdout(Stdio::piped());
     cmd.stderr(Stdio::piped());
 
+    // CREATE_NO_WINDOW | CREATE_SUSPENDED: the process is resumed only after it has been
+    // placed in its job object, so nothing it spawns can start outside the job.
     #[cfg(target_os = "windows")]
-    { cmd.creation_flags(0x08000000); } 
-
-    if job_data.restrict_filenames {
-        cmd.arg("--restrict-filenames").arg("--trim-filenames").arg("200");
-    }
+    { cmd.creation_flags(0x08000000 | 0x00000004); } 
 
     if job_data.embed_metadata { cmd.arg("--embed-metadata"); }
+
+    let music_tags = job_data.music_metadata && job_data.format_preset.is_audio();
+    if music_tags {
+        // Fill music tags from the richest field available, falling back to generic video metadata.
+        cmd.arg("--parse-metadata").arg("%(artist,creator,uploader)s:%(meta_artist)s")
+            .arg("--parse-metadata").arg("%(track,title)s:%(meta_title)s")
+            .arg("--parse-metadata").arg("%(album,playlist_title)s:%(meta_album)s");
+        if !job_data.embed_metadata { cmd.arg("--embed-metadata"); }
+    }
+    if job_data.embed_source_url {
+        cmd.arg("--parse-metadata").arg("webpage_url:%(meta_comment)s")
+            .arg("--parse-metadata").arg("webpage_url:%(meta_purl)s");
+        if !job_data.embed_metadata && !music_tags { cmd.arg("--embed-metadata"); }
+    }
     if job_data.embed_thumbnail { cmd.arg("--embed-thumbnail"); }
 
+    // Audio containers can't carry subtitle tracks.
+    let embed_subs = job_data.embed_subtitles && !job_data.format_preset.is_audio();
+    let write_subs = job_data.download_subtitles || embed_subs;
+    let mut sub_langs: Vec<String> = Vec::new();
+    if write_subs || job_data.auto_subtitles {
+        if write_subs { cmd.arg("--write-subs"); }
+        if job_data.auto_subtitles { cmd.arg("--write-auto-subs"); }
+        if embed_subs { cmd.arg("--embed-subs"); }
+        match &job_data.subtitle_langs {
+            Some(langs) if !langs.is_empty() => sub_langs.extend(langs.iter().cloned()),
+            // Passing only "live_chat" below would otherwise drop yt-dlp's English default.
+            _ if job_data.write_live_chat => sub_langs.push("en.*".to_string()),
+            _ => {}
+        }
+    }
+    if job_data.write_live_chat {
+        if !write_subs { cmd.arg("--write-subs"); }
+        sub_langs.push("live_chat".to_string());
+    }
+    if !sub_langs.is_empty() {
+        cmd.arg("--sub-langs").arg(sub_langs.join(","));
+    }
+    if job_data.write_comments {
+        cmd.arg("--write-comments").arg("--write-info-json");
+    }
+
     if job_data.live_from_start {
         cmd.arg("--live-from-start");
+        if job_data.resume_live {
+            // Picks up the .part/.ytdl fragment state left by the interrupted run.
+            cmd.arg("--continue");
+        }
+    }
+
+    if let Some(ref max_size) = general_config.max_filesize {
+        let max_size = max_size.trim();
+        if FILESIZE_REGEX.is_match(max_size) {
+            cmd.arg("--max-filesize").arg(max_size);
+        } else if !max_size.is_empty() {
+            warn!(target: "core::process", job_id = ?job_data.id, "Ignoring invalid max_filesize value: {}", max_size);
+        }
+    }
+
+    if let Some(limit) = effective_rate_limit(job_data.rate_limit.as_deref(), general_config) {
+        cmd.arg("--limit-rate").arg(limit.to_string());
+    }
+
+    if let Some(limit) = general_config.age_limit {
+        cmd.arg("--age-limit").arg(limit.to_string());
+    }
+
+    if let Some(filter) = duration_match_filter(general_config.min_duration, general_config.max_duration) {
+        cmd.arg("--match-filters").arg(filter);
     }
 
     if let Some(ref sections) = job_data.download_sections {
@@ -322,50 +697,219 @@ fn build_command(
         }
     }
 
-    let height_filter = if job_data.video_resolution != "best" {
-        let number_part: String = job_data.video_resolution.chars().filter(|c| c.is_numeric()).collect();
-        if !number_part.is_empty() { format!("[height<={}]", number_part) } else { String::new() }
-    } else { String::new() };
+    let args: Vec<String> = cmd.as_std().get_args().map(|s| s.to_string_lossy().to_string()).collect();
+    let used_command = format!("{} {}", yt_dlp_cmd, args.join(" "));
+
+    Ok((cmd, used_command))
+}
+
+/// Arguments that decide what the finished file is called: the output template,
+/// filename sanitisation, and the format and container choice. The download and the
+/// filename previews both use them, so a preview resolves to the name the download gets.
+pub fn naming_args(job_data: &QueuedJob) -> Vec<String> {
+    let mut args = vec!["-o".to_string(), output_template(job_data), "--windows-filenames".to_string()];
+    let mut push = |items: &[&str]| args.extend(items.iter().map(|a| a.to_string()));
+
+    if job_data.restrict_filenames {
+        push(&["--restrict-filenames", "--trim-filenames", "200"]);
+    }
+
+    let split_streams = job_data.split_streams && !job_data.format_preset.is_audio();
+    if split_streams {
+        // "," instead of "+" downloads both formats as separate files, skipping the merge.
+        push(&["-f", &format!("bestvideo{},bestaudio", height_filter(&job_data.video_resolution))]);
+    } else if job_data.relax_format {
+        push(&["-f", if job_data.format_preset.is_audio() { "bestaudio/best" } else { "bestvideo*+bestaudio/best" }]);
+    } else if let Some(selector) = format_selector(&job_data.format_preset, &job_data.video_resolution) {
+        push(&["-f", &selector]);
+    }
 
+    // -S only orders the formats the preset's -f already admits.
+    if let Some(sort) = job_data.format_sort.as_deref().filter(|s| !s.trim().is_empty()) {
+        push(&["-S", sort]);
+    }
+
+    let audio_quality = job_data.audio_quality.as_deref().unwrap_or("0");
     match job_data.format_preset {
-        DownloadFormatPreset::Best => {
-            if !height_filter.is_empty() { cmd.arg("-f").arg(format!("bestvideo{}+bestaudio/best{}", height_filter, height_filter)); }
+        DownloadFormatPreset::Best => {}
+        _ if split_streams => {}
+        DownloadFormatPreset::BestMp4 | DownloadFormatPreset::BestMkv | DownloadFormatPreset::BestWebm if job_data.remux_only => {
+            push(&["--remux-video", job_data.format_preset.container().unwrap_or_default()]);
         }
-        DownloadFormatPreset::BestMp4 => {
-            cmd.arg("-f").arg(format!("bestvideo{}+bestaudio", height_filter));
-            cmd.args(["--merge-output-format", "mp4"]);
-        }
-        DownloadFormatPreset::BestMkv => {
-            cmd.arg("-f").arg(format!("bestvideo{}+bestaudio", height_filter));
-            cmd.args(["--merge-output-format", "mkv"]);
+        DownloadFormatPreset::BestMp4 => push(&["--merge-output-format", "mp4"]),
+        DownloadFormatPreset::BestMkv => push(&["--merge-output-format", "mkv"]),
+        DownloadFormatPreset::BestWebm => push(&["--merge-output-format", "webm"]),
+        DownloadFormatPreset::AudioBest => push(&["-x"]),
+        DownloadFormatPreset::AudioMp3 => push(&["-x", "--audio-format", "mp3", "--audio-quality", audio_quality]),
+        DownloadFormatPreset::AudioFlac => push(&["-x", "--audio-format", "flac", "--audio-quality", audio_quality]),
+        DownloadFormatPreset::AudioM4a => push(&["-x", "--audio-format", "m4a", "--audio-quality", audio_quality]),
+    }
+    args
+}
+
+/// `--print filename` reports the name before post-processing. Presets that convert
+/// to a fixed container afterwards get that container's extension here; `AudioBest`
+/// keeps whatever codec the source has, so its name is left as printed.
+pub fn post_processed_name(name: &str, job_data: &QueuedJob) -> String {
+    let container = match job_data.format_preset {
+        DownloadFormatPreset::AudioMp3 => Some("mp3"),
+        DownloadFormatPreset::AudioFlac => Some("flac"),
+        DownloadFormatPreset::AudioM4a => Some("m4a"),
+        _ if job_data.remux_only && !job_data.split_streams => job_data.format_preset.container(),
+        _ => None,
+    };
+    match container {
+        Some(ext) => Path::new(name).with_extension(ext).to_string_lossy().to_string(),
+        None => name.to_string(),
+    }
+}
+
+/// `[height<=N]` for a resolution such as "1080p"; empty for "best".
+fn height_filter(video_resolution: &str) -> String {
+    if video_resolution == "best" {
+        return String::new();
+    }
+    let number_part: String = video_resolution.chars().filter(|c| c.is_numeric()).collect();
+    if number_part.is_empty() { String::new() } else { format!("[height<={}]", number_part) }
+}
+
+/// yt-dlp `-f` selector for a preset. `None` for `Best` at any resolution, which
+/// leaves yt-dlp's own default in charge.
+pub fn format_selector(preset: &DownloadFormatPreset, video_resolution: &str) -> Option<String> {
+    let height_filter = height_filter(video_resolution);
+
+    match preset {
+        DownloadFormatPreset::Best => {
+            if height_filter.is_empty() { None } else { Some(format!("bestvideo{}+bestaudio/best{}", height_filter, height_filter)) }
         }
-        DownloadFormatPreset::BestWebm => {
-            cmd.arg("-f").arg(format!("bestvideo{}+bestaudio", height_filter));
-            cmd.args(["--merge-output-format", "webm"]);
+        DownloadFormatPreset::BestMp4 | DownloadFormatPreset::BestMkv | DownloadFormatPreset::BestWebm => {
+            Some(format!("bestvideo{}+bestaudio", height_filter))
         }
-        DownloadFormatPreset::AudioBest => { cmd.arg("-x").args(["-f", "bestaudio/best"]); }
-        DownloadFormatPreset::AudioMp3 => { cmd.arg("-x").args(["--audio-format", "mp3", "--audio-quality", "0"]); }
-        DownloadFormatPreset::AudioFlac => { cmd.arg("-x").args(["--audio-format", "flac", "--audio-quality", "0"]); }
-        DownloadFormatPreset::AudioM4a => { cmd.arg("-x").args(["--audio-format", "m4a", "--audio-quality", "0"]); }
+        DownloadFormatPreset::AudioBest | DownloadFormatPreset::AudioMp3 | DownloadFormatPreset::AudioFlac => Some(AUDIO_ONLY_SELECTOR.to_string()),
+        DownloadFormatPreset::AudioM4a => Some(M4A_AUDIO_SELECTOR.to_string()),
     }
+}
 
-    let args: Vec<String> = cmd.as_std().get_args().map(|s| s.to_string_lossy().to_string()).collect();
-    let used_command = format!("{} {}", yt_dlp_cmd, args.join(" "));
+/// Whether `--max-filesize` would accept `size`, e.g. "500M" or "1.5G".
+pub fn is_valid_filesize(size: &str) -> bool {
+    FILESIZE_REGEX.is_match(size.trim())
+}
+
+/// Rejects output templates whose file name would lack an extension or consist of
+/// nothing but one. The worker's fallback search finds finished files by extension,
+/// so an extensionless output would be reported as missing.
+pub fn validate_filename_template(template: &str) -> Result<(), String> {
+    let file_part = template.rsplit(['/', '\\']).next().unwrap_or(template);
+    let has_ext_field = file_part.contains("%(ext)s");
+    if !has_ext_field && !EXPLICIT_EXT_REGEX.is_match(file_part) {
+        return Err(format!("Filename template '{}' has no extension. End it with .%(ext)s", template));
+    }
+
+    let stem = if has_ext_field {
+        file_part.replacen("%(ext)s", "", 1)
+    } else {
+        EXPLICIT_EXT_REGEX.replace(file_part, "").to_string()
+    };
+    let has_name = TEMPLATE_FIELD_REGEX.is_match(&stem)
+        || stem.chars().any(|c| !matches!(c, '.' | ' ' | '-' | '_'));
+    if !has_name {
+        return Err(format!("Filename template '{}' produces a file with only an extension. Add a field such as %(title)s", template));
+    }
+    Ok(())
+}
 
-    (cmd, used_command)
+/// Checks a `--limit-rate` value such as "500K" or "2.5M" (bytes per second).
+pub fn validate_rate_limit(limit: &str) -> Result<String, String> {
+    let limit = limit.trim();
+    if FILESIZE_REGEX.is_match(limit) {
+        Ok(limit.to_string())
+    } else {
+        Err(format!("Invalid rate limit '{}'. Use a number with an optional K, M or G suffix.", limit))
+    }
+}
+
+/// Trims and checks `--sub-langs` entries. Commas are rejected because the list is
+/// comma-joined on the command line.
+pub fn validate_subtitle_langs(langs: &[String]) -> Result<Vec<String>, String> {
+    langs.iter()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(|l| if SUBTITLE_LANG_REGEX.is_match(l) {
+            Ok(l.to_string())
+        } else {
+            Err(format!("Invalid subtitle language '{}'. Use codes like en or de.*, without commas or spaces.", l))
+        })
+        .collect()
 }
 
+/// Accepts a VBR level from 0 (best) to 9, or a bitrate such as "128K".
+pub fn validate_audio_quality(quality: &str) -> Result<String, String> {
+    let quality = quality.trim();
+    if AUDIO_QUALITY_REGEX.is_match(quality) {
+        Ok(quality.to_uppercase())
+    } else {
+        Err(format!("Invalid audio quality '{}'. Use a VBR level from 0 to 9 or a bitrate like 128K.", quality))
+    }
+}
+
+/// Sort fields yt-dlp understands for `-S` (see "Sorting Formats" in its README).
+const FORMAT_SORT_FIELDS: [&str; 27] = [
+    "hasvid", "hasaud", "ie_pref", "lang", "quality", "source", "proto",
+    "vcodec", "acodec", "codec", "vext", "aext", "ext", "filesize", "fs_approx", "size",
+    "height", "width", "res", "fps", "hdr", "channels", "tbr", "vbr", "abr", "br", "asr",
+];
+
+/// Checks a `-S` string such as `res:1080,fps,+size,codec:av1` and returns it with
+/// whitespace removed. Each item is an optional `+`, a known field, and an optional
+/// `:value` or `~value` preference.
+pub fn validate_format_sort(sort: &str) -> Result<String, String> {
+    let cleaned: String = sort.chars().filter(|c| !c.is_whitespace()).collect();
+    if cleaned.is_empty() {
+        return Err("Format sort is empty".into());
+    }
+    for item in cleaned.split(',') {
+        let body = item.strip_prefix('+').unwrap_or(item);
+        let field = body.split([':', '~']).next().unwrap_or_default();
+        if field.is_empty() {
+            return Err(format!("Empty sort field in '{}'", cleaned));
+        }
+        if !FORMAT_SORT_FIELDS.contains(&field) {
+            return Err(format!("Unknown sort field '{}'", field));
+        }
+    }
+    Ok(cleaned)
+}
+
+/// Places the (suspended) child in a kill-on-close job object, then lets it run.
+/// The child is resumed even if the job can't be set up; if it can't be resumed
+/// it is killed rather than left hanging.
 #[cfg(target_os = "windows")]
-fn assign_windows_job_object(child: &tokio::process::Child, job_id: uuid::Uuid) -> Option<win_job::JobObject> {
-    if let Ok(job) = win_job::JobObject::new() {
-        if let Some(handle) = child.raw_handle() {
-             let _ = job.assign_process(handle);
+fn assign_windows_job_object(child: &mut tokio::process::Child, job_id: uuid::Uuid) -> Option<win_job::JobObject> {
+    let job = match win_job::JobObject::new() {
+        Ok(job) => {
+            match child.raw_handle() {
+                Some(handle) => {
+                    if let Err(e) = job.assign_process(handle) {
+                        warn!(target: "core::process", job_id = ?job_id, "{}. Child processes may outlive the app.", e);
+                    }
+                },
+                None => warn!(target: "core::process", job_id = ?job_id, "Child exited before it could be assigned to a job object"),
+            }
+            Some(job)
+        },
+        Err(e) => {
+            warn!(target: "core::process", job_id = ?job_id, "Failed to create Windows Job Object for subprocess management: {}", e);
+            None
+        }
+    };
+
+    if let Some(pid) = child.id() {
+        if let Err(e) = win_job::resume_process(pid) {
+            error!(target: "core::process", job_id = ?job_id, "Failed to resume suspended subprocess: {}", e);
+            let _ = child.start_kill();
         }
-        Some(job)
-    } else {
-        warn!(target: "core::process", job_id = ?job_id, "Failed to create Windows Job Object for subprocess management");
-        None
     }
+    job
 }
 
 fn spawn_io_readers(
@@ -389,52 +933,103 @@ fn spawn_io_readers(
     rx
 }
 
+/// Exponential moving average of the reported download speed, so the UI readout
+/// doesn't jump between every sample yt-dlp prints.
+struct SpeedSmoother {
+    /// Weight of the newest sample, in `(0, 1]`; `1.0` disables smoothing.
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl SpeedSmoother {
+    fn new(alpha: f64) -> Self {
+        let alpha = if alpha.is_finite() { alpha.clamp(0.01, 1.0) } else { 1.0 };
+        Self { alpha, value: None }
+    }
+
+    fn update(&mut self, sample: f64) -> f64 {
+        if !sample.is_finite() || sample < 0.0 {
+            return self.value.unwrap_or(0.0);
+        }
+        let next = match self.value {
+            Some(prev) => self.alpha * sample + (1.0 - self.alpha) * prev,
+            None => sample,
+        };
+        self.value = Some(next);
+        next
+    }
+}
+
 struct ProcessTelemetry {
+    /// Phase the job was in when output stopped.
+    last_phase: JobPhase,
+    skip_reason: Option<String>,
     detected_output_path: Option<String>,
     detected_filename_only: Option<String>,
     captured_logs: VecDeque<String>,
     captured_stderr: VecDeque<String>,
 }
 
+/// Longest line kept verbatim in the captured logs and stderr tail.
+const MAX_STORED_LINE_LEN: usize = 2048;
+
+/// Largest incomplete JSON progress object held back waiting for its continuation.
+const MAX_PARTIAL_JSON_LEN: usize = 64 * 1024;
+
+/// Caps a line for log storage, marking how much was cut.
+fn truncate_for_log(line: &str) -> String {
+    if line.len() <= MAX_STORED_LINE_LEN {
+        return line.to_string();
+    }
+    let mut cut = MAX_STORED_LINE_LEN;
+    while !line.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    format!("{}… [truncated {} bytes]", &line[..cut], line.len() - cut)
+}
+
 async fn monitor_process(
     job_id: uuid::Uuid,
     mut rx: mpsc::Receiver<(String, bool)>,
     unique_temp_dir: &Path,
     tx_actor: &mpsc::Sender<JobMessage>,
+    speed_smoothing: f64,
 ) -> ProcessTelemetry {
     let mut state_percentage: f32 = 0.0;
-    let mut state_phase: String = "Initializing".to_string();
+    let mut state_phase = JobPhase::Initializing;
+    let mut state_detail: Option<String> = None;
     let mut detected_output_path: Option<String> = None;
     let mut detected_filename_only: Option<String> = None;
+    let mut skip_reason: Option<String> = None;
+    let mut speed = SpeedSmoother::new(speed_smoothing);
     
     let mut last_ipc_update = Instant::now();
-    let mut last_emitted_phase = state_phase.clone();
+    let mut last_emitted_phase = state_phase;
     
     let mut captured_logs = VecDeque::with_capacity(100);
     let mut captured_stderr = VecDeque::with_capacity(50);
+    // Start of a JSON progress object whose line ended before the object did.
+    let mut partial_json: Option<String> = None;
     
     while let Some((line, is_stderr)) = rx.recv().await {
-        if line.len() > 2048 { 
-            trace!(target: "core::process", job_id = ?job_id, "Skipped extremely long line (>2048 chars)");
-            continue; 
-        }
-        
         let trimmed = line.trim();
         if trimmed.is_empty() { continue; }
         
-        captured_logs.push_back(trimmed.to_string());
+        // The full line is still parsed below; only the stored copy is capped.
+        let stored = truncate_for_log(trimmed);
+        captured_logs.push_back(stored.clone());
         if captured_logs.len() > 100 { 
             captured_logs.pop_front(); 
         }
         
         if is_stderr {
-            trace!(target: "core::process::stderr", job_id = ?job_id, "{}", trimmed);
-            captured_stderr.push_back(trimmed.to_string());
+            trace!(target: "core::process::stderr", job_id = ?job_id, "{}", stored);
+            captured_stderr.push_back(stored);
             if captured_stderr.len() > 50 { 
                 captured_stderr.pop_front(); 
             }
         } else {
-            trace!(target: "core::process::stdout", job_id = ?job_id, "{}", trimmed);
+            trace!(target: "core::process::stdout", job_id = ?job_id, "{}", stored);
         }
 
         if !is_stderr {
@@ -453,64 +1048,140 @@ async fn monitor_process(
         let mut speed_str = "N/A".to_string();
         let mut eta_str = "N/A".to_string();
 
+        // A continuation never looks like the start of a new record; if one arrives
+        // instead, the held fragment is abandoned and the line handled on its own.
+        let rejoined;
+        let trimmed = match partial_json.take() {
+            Some(prefix) if is_stderr => {
+                partial_json = Some(prefix);
+                trimmed
+            }
+            Some(prefix) if !trimmed.starts_with(['{', '[']) => {
+                rejoined = format!("{}{}", prefix, trimmed);
+                rejoined.as_str()
+            }
+            Some(prefix) => {
+                debug!(target: "core::process", job_id = ?job_id, "Dropping incomplete JSON progress line: {}", truncate_for_log(&prefix));
+                trimmed
+            }
+            None => trimmed,
+        };
+
         if trimmed.starts_with('{') {
-            if let Ok(progress_json) = serde_json::from_str::<YtDlpJsonProgress>(trimmed) {
-                if let Some(d) = progress_json.downloaded_bytes {
-                     let t = progress_json.total_bytes.or(progress_json.total_bytes_estimate);
-                     if let Some(total) = t { 
-                         if total > 0 {
-                             state_percentage = (d as f32 / total as f32) * 100.0; 
+            match serde_json::from_str::<YtDlpJsonProgress>(trimmed) {
+                Ok(progress_json) => {
+                    if let Some(d) = progress_json.downloaded_bytes {
+                         let t = progress_json.total_bytes.or(progress_json.total_bytes_estimate);
+                         if let Some(total) = t { 
+                             if total > 0 {
+                                 state_percentage = (d as f32 / total as f32) * 100.0; 
+                             }
                          }
-                     }
-                }
-                if let Some(s) = progress_json.speed { speed_str = format_speed(s); }
-                if let Some(e) = progress_json.eta { eta_str = format_eta(e); }
-                if let Some(f) = progress_json.filename {
-                     if let Some(n) = Path::new(&f).file_name() {
-                         detected_filename_only = detected_filename_only.or(Some(n.to_string_lossy().to_string()));
-                     }
-                }
+                    }
+                    if let Some(s) = progress_json.speed { speed_str = format_speed(speed.update(s)); }
+                    if let Some(e) = progress_json.eta { eta_str = format_eta(e); }
+                    if let Some(f) = progress_json.filename.filter(|f| !f.ends_with(".json")) {
+                         if let Some(n) = Path::new(&f).file_name() {
+                             detected_filename_only = detected_filename_only.or(Some(n.to_string_lossy().to_string()));
+                         }
+                    }
                 
-                if !state_phase.contains("Merging") && !state_phase.contains("Extracting") 
-                   && !state_phase.contains("Writing") && !state_phase.contains("Embedding") 
-                   && !state_phase.contains("Fixing") && !state_phase.contains("Moving") {
-                    if state_phase != "Downloading" {
-                        trace!(target: "core::process", job_id = ?job_id, "Phase changed implicitly to Downloading via JSON telemetry");
+                    if !state_phase.is_post_processing() {
+                        if state_phase != JobPhase::Downloading {
+                            trace!(target: "core::process", job_id = ?job_id, "Phase changed implicitly to Downloading via JSON telemetry");
+                        }
+                        state_phase = JobPhase::Downloading;
+                        state_detail = match (progress_json.fragment_index, progress_json.fragment_count) {
+                            (Some(i), Some(n)) if n > 0 => Some(format!("{}/{}", i, n)),
+                            _ => None,
+                        };
                     }
-                    state_phase = "Downloading".to_string();
+                    emit_update = true;
+                }
+                Err(e) if e.is_eof() && !is_stderr && trimmed.len() < MAX_PARTIAL_JSON_LEN => {
+                    trace!(target: "core::process", job_id = ?job_id, "Holding incomplete JSON progress line ({} bytes)", trimmed.len());
+                    partial_json = Some(trimmed.to_string());
+                }
+                Err(e) => {
+                    debug!(target: "core::process", job_id = ?job_id, "Discarding unparseable JSON progress line ({}): {}", e, truncate_for_log(trimmed));
                 }
-                emit_update = true;
             }
         } else {
             if trimmed.starts_with("[download]") {
-                 if DOWNLOAD_START_REGEX.is_match(trimmed) {
+                 if let Some(reason) = detect_skip_reason(trimmed) {
+                    debug!(target: "core::process", job_id = ?job_id, "yt-dlp skipped item: {}", trimmed);
+                    skip_reason = Some(reason.to_string());
+                 } else if DOWNLOAD_START_REGEX.is_match(trimmed) {
                     trace!(target: "core::process", job_id = ?job_id, "Regex matched: DOWNLOAD_START_REGEX");
-                    state_phase = "Starting Download".to_string();
+                    state_phase = JobPhase::StartingDownload;
+                    state_detail = None;
                     emit_update = true;
                 }
             }
+            else if trimmed.starts_with("[#") {
+                // aria2c summary line, e.g. "[#2089b0 4.1MiB/33MiB(12%) CN:16 DL:2.3MiB ETA:12s]"
+                if let Some(caps) = ARIA2_PROGRESS_REGEX.captures(trimmed) {
+                    if let Ok(p) = caps[1].parse::<f32>() { state_percentage = p; }
+                    if let Some(dl) = caps.get(2) {
+                        speed_str = match AriaEngine::parse_aria_size(dl.as_str()) {
+                            Some(bytes) => format_speed(speed.update(bytes)),
+                            None => format!("{}/s", dl.as_str()),
+                        };
+                    }
+                    if let Some(eta) = caps.get(3) { eta_str = eta.as_str().to_string(); }
+                    state_phase = JobPhase::Downloading;
+                    state_detail = None;
+                    emit_update = true;
+                }
+            }
+            else if COMMENTS_REGEX.is_match(trimmed) {
+                if state_phase != JobPhase::DownloadingComments {
+                    trace!(target: "core::process", job_id = ?job_id, "Matched comment extraction phase string");
+                }
+                state_phase = JobPhase::DownloadingComments;
+                state_detail = None;
+                emit_update = true;
+            }
+            else if trimmed.starts_with("[MetadataParser]") {
+                trace!(target: "core::process", job_id = ?job_id, "Matched MetadataParser phase string");
+                state_phase = JobPhase::ParsingMetadata;
+                state_detail = None;
+                emit_update = true;
+            }
             else if trimmed.starts_with("[Metadata]") {
                 trace!(target: "core::process", job_id = ?job_id, "Matched Metadata phase string");
-                state_phase = "Writing Metadata".to_string();
+                state_phase = JobPhase::WritingMetadata;
+                state_detail = None;
                 state_percentage = 99.0;
                 emit_update = true;
             }
             else if trimmed.starts_with("[Thumbnails]") || trimmed.starts_with("[EmbedThumbnail]") {
                 trace!(target: "core::process", job_id = ?job_id, "Matched Thumbnail phase string");
-                state_phase = "Embedding Thumbnail".to_string();
+                state_phase = JobPhase::EmbeddingThumbnail;
+                state_detail = None;
                 state_percentage = 99.0;
                 emit_update = true;
             }
             else if trimmed.starts_with("[Merger]") {
                 trace!(target: "core::process", job_id = ?job_id, "Matched Merger phase string");
-                state_phase = "Merging Formats".to_string();
+                state_phase = JobPhase::Merging;
+                state_detail = None;
+                state_percentage = 100.0;
+                eta_str = "Done".to_string();
+                emit_update = true;
+            }
+            else if trimmed.starts_with("[VideoRemuxer]") {
+                trace!(target: "core::process", job_id = ?job_id, "Matched VideoRemuxer phase string");
+                state_phase = JobPhase::Remuxing;
+                state_detail = None;
                 state_percentage = 100.0;
                 eta_str = "Done".to_string();
                 emit_update = true;
             }
             else if trimmed.starts_with("[ExtractAudio]") {
                 trace!(target: "core::process", job_id = ?job_id, "Matched ExtractAudio phase string");
-                state_phase = "Extracting Audio".to_string();
+                state_phase = JobPhase::ExtractingAudio;
+                state_detail = None;
                 state_percentage = 100.0;
                 eta_str = "Done".to_string();
                 emit_update = true;
@@ -518,21 +1189,24 @@ async fn monitor_process(
             else if trimmed.starts_with("[Fixup") {
                 if FIXUP_REGEX.is_match(trimmed) {
                     trace!(target: "core::process", job_id = ?job_id, "Regex matched: FIXUP_REGEX");
-                    state_phase = "Fixing Container".to_string();
+                    state_phase = JobPhase::FixingContainer;
+                    state_detail = None;
                     state_percentage = 100.0;
                     emit_update = true;
                 }
             }
             else if trimmed.starts_with("[MoveFiles]") {
                 trace!(target: "core::process", job_id = ?job_id, "Matched MoveFiles phase string");
-                state_phase = "Finalizing".to_string();
+                state_phase = JobPhase::Finalizing;
+                state_detail = None;
                 state_percentage = 100.0;
                 emit_update = true;
             }
             else if trimmed.starts_with("[ffmpeg]") {
-                 if !state_phase.contains("Merging") && !state_phase.contains("Extracting") {
+                 if !matches!(state_phase, JobPhase::Merging | JobPhase::ExtractingAudio) {
                      trace!(target: "core::process", job_id = ?job_id, "Matched generic ffmpeg phase string");
-                     state_phase = "Processing (FFmpeg)".to_string();
+                     state_phase = JobPhase::PostProcessing;
+                     state_detail = None;
                      emit_update = true;
                 }
             }
@@ -550,16 +1224,19 @@ async fn monitor_process(
                     speed: speed_str,
                     eta: eta_str,
                     filename: detected_filename_only.clone(),
-                    phase: state_phase.clone()
+                    phase: state_phase,
+                    detail: state_detail.clone(),
                  }).await;
                  
                  last_ipc_update = Instant::now();
-                 last_emitted_phase = state_phase.clone();
+                 last_emitted_phase = state_phase;
              }
         }
     }
 
     ProcessTelemetry {
+        last_phase: state_phase,
+        skip_reason,
         detected_output_path,
         detected_filename_only,
         captured_logs,
@@ -569,15 +1246,23 @@ async fn monitor_process(
 
 async fn handle_process_success(
     job_id: uuid::Uuid,
-    _job_data: &QueuedJob,
+    job_data: &QueuedJob,
     telemetry: ProcessTelemetry,
     unique_temp_dir: &Path,
     target_dir: &Path,
     tx_actor: &mpsc::Sender<JobMessage>,
     fallback_level: u32,
     used_command: String,
+    cancel_flag: &AtomicBool,
 ) -> bool {
     debug!(target: "core::process", job_id = ?job_id, "Subprocess returned success exit code (0)");
+
+    if let Some(reason) = telemetry.skip_reason {
+        info!(target: "core::process", job_id = ?job_id, "Job skipped by yt-dlp: {}", reason);
+        let _ = tx_actor.send(JobMessage::JobSkipped { id: job_id, reason }).await;
+        return false;
+    }
+
     let mut final_src_path: Option<PathBuf> = None;
 
     if let Some(p) = telemetry.detected_output_path {
@@ -601,6 +1286,7 @@ async fn handle_process_success(
     }
 
     if final_src_path.is_none() {
+        // Only media extensions qualify, so subtitle and other sidecars are never picked.
         debug!(target: "core::process", job_id = ?job_id, "Initiating deep temp dir scan for valid media file...");
         for entry in WalkDir::new(unique_temp_dir).min_depth(1).max_depth(3) {
             if let Ok(e) = entry {
@@ -628,16 +1314,34 @@ async fn handle_process_success(
             speed: "Finalizing".to_string(),
             eta: "00:00".to_string(),
             filename: telemetry.detected_filename_only.clone(),
-            phase: "Moving to Library".to_string()
+            phase: JobPhase::MovingToLibrary,
+            detail: None,
         }).await;
 
         tokio::time::sleep(Duration::from_millis(50)).await;
         
         let is_modified = fallback_level > 0;
 
+        // A cancel that lands after yt-dlp exited must not leave the file in the library.
+        if cancel_flag.load(Ordering::Relaxed) {
+            debug!(target: "core::process", job_id = ?job_id, "Job cancelled before its output was moved, discarding {:?}", src_path);
+            return false;
+        }
+
         match robust_move_file(&src_path, &dest_path).await {
+            Ok(_) if cancel_flag.load(Ordering::Relaxed) => {
+                debug!(target: "core::process", job_id = ?job_id, "Job cancelled while its output was being moved, removing {:?}", dest_path);
+                let _ = tokio::fs::remove_file(&dest_path).await;
+                false
+            },
             Ok(_) => {
                 info!(target: "core::process", job_id = ?job_id, "Successfully moved completed file to target directory: {:?}", dest_path);
+                if job_data.write_live_chat || job_data.write_comments || job_data.download_subtitles || job_data.auto_subtitles {
+                    move_sidecars(job_id, &src_path, unique_temp_dir, target_dir).await;
+                }
+                if job_data.split_streams {
+                    move_split_streams(job_id, &src_path, &dest_path, target_dir).await;
+                }
                 let _ = tx_actor.send(JobMessage::JobCompleted { 
                     id: job_id, 
                     output_path: dest_path.to_string_lossy().to_string(),
@@ -678,35 +1382,60 @@ async fn handle_process_error(
     telemetry: ProcessTelemetry,
     tx_actor: &mpsc::Sender<JobMessage>,
     fallback_level: &mut u32,
+    is_retry: &mut bool,
 ) -> bool {
     let log_blob = Vec::from(telemetry.captured_logs.clone()).join("\n");
     let stderr_blob = Vec::from(telemetry.captured_stderr.clone()).join("\n");
     
     warn!(target: "core::process", job_id = ?job_id, exit_code = ?status.code(), "Process exited with error status");
+
+    // An aborted oversized HTTP download exits nonzero; retrying can't change the outcome.
+    if let Some(reason) = telemetry.skip_reason {
+        info!(target: "core::process", job_id = ?job_id, "Job skipped by yt-dlp: {}", reason);
+        let _ = tx_actor.send(JobMessage::JobSkipped { id: job_id, reason }).await;
+        return false;
+    }
     
     let is_filesystem_error = FILESYSTEM_ERROR_REGEX.is_match(&log_blob);
     if !job_data.restrict_filenames && is_filesystem_error {
         warn!(target: "core::process", job_id = ?job_id, "Filesystem error detected in logs. Enabling restrict_filenames and retrying.");
         job_data.restrict_filenames = true;
+        *is_retry = true;
         return true; 
     }
 
+    // Formats rotate; a job resumed after a while can ask for one that no longer exists.
+    if !job_data.relax_format && FORMAT_UNAVAILABLE_REGEX.is_match(&stderr_blob) {
+        warn!(target: "core::process", job_id = ?job_id, preset = job_data.format_preset.to_preset_string(), resolution = %job_data.video_resolution,
+            "Requested format is not available. Retrying with the best available format of the same kind.");
+        job_data.relax_format = true;
+        let _ = tx_actor.send(JobMessage::UpdateProgress {
+            id: job_id, percentage: 0.0, speed: "Retrying...".to_string(), eta: "--".to_string(), filename: None,
+            phase: JobPhase::FormatFallback, detail: Some("format".to_string()),
+        }).await;
+        return true;
+    }
+
     let is_fatal_auth_js = stderr_blob.contains("No supported JavaScript runtime") 
         || stderr_blob.contains("Sign in to confirm") 
         || stderr_blob.contains("confirm you're not a bot");
 
-    if !is_fatal_auth_js {
+    if job_data.remux_only && telemetry.last_phase == JobPhase::Remuxing {
+        // A looser format would just be remuxed again; report it instead of retrying.
+        warn!(target: "core::process", job_id = ?job_id, "Remux-only job failed in the remuxer, not falling back");
+    } else if !is_fatal_auth_js {
         if *fallback_level == 0 {
             warn!(target: "core::process", job_id = ?job_id, "Download failed natively, escalating to Fallback Level 1 (Loose Format)");
             *fallback_level = 1;
             job_data.video_resolution = "best".to_string();
             job_data.embed_metadata = false;
+            job_data.embed_source_url = false;
             job_data.embed_thumbnail = false;
             job_data.live_from_start = false;
             
             let _ = tx_actor.send(JobMessage::UpdateProgress {
                 id: job_id, percentage: 0.0, speed: "Retrying...".to_string(), eta: "--".to_string(), filename: None,
-                phase: "Fallback Level 1 (Loose Format)".to_string(),
+                phase: JobPhase::FormatFallback, detail: Some("1".to_string()),
             }).await;
             return true;
         } else if *fallback_level == 1 {
@@ -716,7 +1445,7 @@ async fn handle_process_error(
             
             let _ = tx_actor.send(JobMessage::UpdateProgress {
                 id: job_id, percentage: 0.0, speed: "Retrying...".to_string(), eta: "--".to_string(), filename: None,
-                phase: "Fallback Level 2 (Any Format)".to_string(),
+                phase: JobPhase::FormatFallback, detail: Some("2".to_string()),
             }).await;
             return true;
         }
@@ -724,10 +1453,22 @@ async fn handle_process_error(
         error!(target: "core::process", job_id = ?job_id, "Fatal unrecoverable error detected in logs (Auth or Runtime requirement)");
     }
 
+    // The media was fetched if the job got as far as ffmpeg; the fix is then FFmpeg, not the network.
+    let in_post_processing = telemetry.last_phase.is_post_processing();
     let short_msg = if stderr_blob.contains("No supported JavaScript runtime") {
         "Missing compliant JS Runtime".to_string()
     } else if stderr_blob.contains("Sign in to confirm") {
         "Authentication Required".to_string()
+    } else if telemetry.last_phase == JobPhase::Merging {
+        // The UI offers "retry without merging" for this message.
+        warn!(target: "core::process", job_id = ?job_id, "Failure occurred while merging streams");
+        "Merging failed (check FFmpeg)".to_string()
+    } else if telemetry.last_phase == JobPhase::Remuxing {
+        warn!(target: "core::process", job_id = ?job_id, "Failure occurred while remuxing");
+        "Remux failed (codecs need re-encoding for this container)".to_string()
+    } else if in_post_processing || POSTPROCESS_ERROR_REGEX.is_match(&stderr_blob) {
+        warn!(target: "core::process", job_id = ?job_id, phase = ?telemetry.last_phase, "Failure occurred during post-processing");
+        "Post-processing failed (check FFmpeg)".to_string()
     } else {
         format!("Process Failed (Exit Code {})", status.code().unwrap_or(-1))
     };
@@ -736,9 +1477,97 @@ async fn handle_process_error(
     false
 }
 
+/// Moves `<stem>.live_chat.json` / `<stem>.info.json` next to the media. Failures are
+/// logged and otherwise ignored; the media itself is already in place.
+async fn move_sidecars(job_id: uuid::Uuid, media_path: &Path, unique_temp_dir: &Path, target_dir: &Path) {
+    let Some(stem) = media_path.file_stem().map(|s| s.to_string_lossy().to_string()) else { return };
+    let Ok(entries) = std::fs::read_dir(unique_temp_dir) else { return };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let is_subtitle = Path::new(&name).extension()
+            .is_some_and(|ext| SUBTITLE_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()));
+        let is_sidecar = name.starts_with(&stem) && (name.ends_with(".live_chat.json") || name.ends_with(".info.json") || is_subtitle);
+        if !is_sidecar {
+            continue;
+        }
+        let dest = target_dir.join(&name);
+        match robust_move_file(&entry.path(), &dest).await {
+            Ok(_) => debug!(target: "core::process", job_id = ?job_id, "Moved sidecar {:?}", dest),
+            Err(e) => warn!(target: "core::process", job_id = ?job_id, "Failed to move sidecar {}: {}", name, e),
+        }
+    }
+}
+
+/// Moves the other stream of a split download next to `dest_path` and writes a
+/// `<stem>.merge.txt` with the ffmpeg command that combines them.
+async fn move_split_streams(job_id: uuid::Uuid, src_path: &Path, dest_path: &Path, target_dir: &Path) {
+    let Some(src_dir) = src_path.parent() else { return };
+    let Ok(entries) = std::fs::read_dir(src_dir) else { return };
+
+    let mut streams = vec![dest_path.to_path_buf()];
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_media = path.extension()
+            .is_some_and(|ext| SPLIT_STREAM_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()));
+        if !is_media || path == src_path {
+            continue;
+        }
+        let dest = target_dir.join(entry.file_name());
+        match robust_move_file(&path, &dest).await {
+            Ok(_) => streams.push(dest),
+            Err(e) => warn!(target: "core::process", job_id = ?job_id, "Failed to move split stream {:?}: {}", path, e),
+        }
+    }
+
+    let stem = dest_path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let inputs: Vec<String> = streams.iter()
+        .filter_map(|p| p.file_name())
+        .map(|n| format!("-i \"{}\"", n.to_string_lossy()))
+        .collect();
+    let note = format!(
+        "These streams were downloaded separately because merging them failed.\n\
+         To combine them, run this in this folder:\n\n  ffmpeg {} -c copy \"{}.mkv\"\n",
+        inputs.join(" "), stem
+    );
+    if let Err(e) = std::fs::write(target_dir.join(format!("{}.merge.txt", stem)), note) {
+        warn!(target: "core::process", job_id = ?job_id, "Failed to write merge note: {}", e);
+    }
+    info!(target: "core::process", job_id = ?job_id, count = streams.len(), "Moved unmerged streams to the target directory");
+}
+
+/// Turns the largest partial media file of an interrupted live recording into a
+/// finished one by dropping its `.part` suffix. Returns the resulting path.
+fn finalize_partial_recording(job_id: uuid::Uuid, unique_temp_dir: &Path) -> Option<PathBuf> {
+    const MEDIA_EXTS: [&str; 5] = ["mp4", "mkv", "webm", "ts", "m4a"];
+    let is_media = |p: &Path| p.extension().map(|e| MEDIA_EXTS.contains(&e.to_string_lossy().as_ref())).unwrap_or(false);
+
+    let largest = WalkDir::new(unique_temp_dir).min_depth(1).max_depth(3).into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            let p = e.path();
+            is_media(p) || (p.extension().map(|x| x == "part").unwrap_or(false) && is_media(&p.with_extension("")))
+        })
+        .max_by_key(|e| e.metadata().map(|m| m.len()).unwrap_or(0))?;
+
+    let path = largest.path().to_path_buf();
+    if path.metadata().map(|m| m.len()).unwrap_or(0) == 0 {
+        return None;
+    }
+    if path.extension().map(|x| x == "part").unwrap_or(false) {
+        let finished = path.with_extension("");
+        if let Err(e) = fs::rename(&path, &finished) {
+            warn!(target: "core::process", job_id = ?job_id, "Failed to finalize partial recording {:?}: {}", path, e);
+            return None;
+        }
+        return Some(finished);
+    }
+    Some(path)
+}
+
 async fn cleanup_temp_dir(job_id: uuid::Uuid) {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    let base_temp_dir = home.join(".multiyt-dlp").join("temp_downloads");
+    let base_temp_dir = crate::core::paths::temp_dir();
     let unique_temp_dir = base_temp_dir.join(job_id.to_string());
     
     async fn robust_remove_dir_internal(path: &Path) {
@@ -760,7 +1589,7 @@ async fn cleanup_temp_dir(job_id: uuid::Uuid) {
     }
 }
 
-fn format_speed(bytes_per_sec: f64) -> String {
+pub(crate) fn format_speed(bytes_per_sec: f64) -> String {
     if bytes_per_sec.is_nan() || bytes_per_sec.is_infinite() { return "N/A".to_string(); }
     const KIB: f64 = 1024.0;
     const MIB: f64 = KIB * 1024.0;
@@ -771,6 +1600,21 @@ fn format_speed(bytes_per_sec: f64) -> String {
     else { format!("{:.0} B/s", bytes_per_sec) }
 }
 
+/// Reads a speed back from a progress readout such as "1.50 MiB/s". `None` for "N/A" and "--".
+pub(crate) fn parse_speed(speed: &str) -> Option<f64> {
+    let value = speed.trim().strip_suffix("/s")?;
+    let split = value.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+    let number: f64 = value[..split].parse().ok()?;
+    let multiplier = match value[split..].trim() {
+        "B" => 1.0,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some(number * multiplier)
+}
+
 fn format_eta(seconds: u64) -> String {
     let h = seconds / 3600;
     let m = (seconds % 3600) / 60;
@@ -779,6 +1623,104 @@ fn format_eta(seconds: u64) -> String {
     else { format!("{:02}:{:02}", m, s) }
 }
 
+/// Lazy provisioning: make sure yt-dlp and FFmpeg exist before the first spawn,
+/// installing them on demand and surfacing the step as the job phase.
+async fn provision_core_dependencies(job_id: uuid::Uuid, app_handle: &AppHandle, tx_actor: &mpsc::Sender<JobMessage>) -> Result<(), String> {
+    for name in ["yt-dlp", "ffmpeg"] {
+        let _ = tx_actor.send(JobMessage::UpdateProgress {
+            id: job_id, percentage: 0.0, speed: "--".to_string(), eta: "--".to_string(), filename: None,
+            phase: JobPhase::CheckingDependency, detail: Some(name.to_string()),
+        }).await;
+        crate::commands::system::ensure_dependency(app_handle.clone(), name).await
+            .map_err(|e| format!("Failed to install {}: {}", name, e))?;
+    }
+    Ok(())
+}
+
+/// How many connections one yt-dlp process may open, decided in one place so `-N`
+/// and aria2's `-x`/`-s` can't each claim the full budget.
+struct ConcurrencyPlan {
+    /// `-N`, used for fragmented formats on the native downloader.
+    fragments: u32,
+    /// aria2 connections for progressive files; `None` when aria2 isn't used.
+    aria2_connections: Option<u32>,
+}
+
+/// A download goes through exactly one of the two paths (fragmented formats are
+/// pinned to the native downloader), so the effective concurrency per job is
+/// `fragments` for HLS/DASH and `aria2_connections` for everything else. Both are
+/// capped by the same per-job share of `FRAGMENT_CONNECTION_BUDGET`.
+fn concurrency_plan(config: &GeneralConfig, use_aria: bool) -> ConcurrencyPlan {
+    let per_job_budget = (FRAGMENT_CONNECTION_BUDGET / config.max_concurrent_downloads.max(1)).max(1);
+    ConcurrencyPlan {
+        fragments: fragment_concurrency(config, per_job_budget),
+        aria2_connections: use_aria.then(|| per_job_budget.min(MAX_CONCURRENT_FRAGMENTS)),
+    }
+}
+
+/// Effective `-N` for one process. The configured value is clamped to
+/// `1..=MAX_CONCURRENT_FRAGMENTS`, then to `per_job_budget` so that
+/// `max_concurrent_downloads` parallel jobs together stay within
+/// `FRAGMENT_CONNECTION_BUDGET` instead of multiplying their connections.
+/// Progressive downloads handed to aria2c are unaffected: `--downloader m3u8,dash:native`
+/// keeps fragmented formats on the native downloader, so the two never stack.
+fn fragment_concurrency(config: &GeneralConfig, per_job_budget: u32) -> u32 {
+    if !config.use_concurrent_fragments {
+        return 1;
+    }
+    config.concurrent_fragments.clamp(1, MAX_CONCURRENT_FRAGMENTS).min(per_job_budget)
+}
+
+/// `--limit-rate` for one process, in bytes per second. Every yt-dlp process throttles
+/// on its own, so the global `rate_limit` is split evenly across
+/// `max_concurrent_downloads` to keep parallel jobs together under it, the same way
+/// `concurrency_plan` shares out connections. A job's own limit can only lower its
+/// share, never raise it past the global cap.
+fn effective_rate_limit(job_limit: Option<&str>, config: &GeneralConfig) -> Option<u64> {
+    let share = config.rate_limit.as_deref()
+        .and_then(network::parse_rate_limit)
+        .map(|global| (global / config.max_concurrent_downloads.max(1) as u64).max(1));
+    let own = job_limit.and_then(network::parse_rate_limit);
+    match (own, share) {
+        (Some(own), Some(share)) => Some(own.min(share)),
+        (own, share) => own.or(share),
+    }
+}
+
+/// yt-dlp exits 0 when it deliberately skips an item; recognise those lines so the
+/// job isn't reported as "file not found".
+fn detect_skip_reason(line: &str) -> Option<&'static str> {
+    if line.contains("larger than max-filesize") {
+        return Some("too large");
+    }
+    if line.contains("because it is age restricted") {
+        return Some("age-restricted");
+    }
+    if line.contains("does not pass filter") {
+        return Some("duration filter");
+    }
+    None
+}
+
+/// Builds the `--match-filters` expression for the duration bounds (seconds).
+/// `>=?` / `<=?` let items with unknown duration (e.g. live streams) through.
+fn duration_match_filter(min: Option<u32>, max: Option<u32>) -> Option<String> {
+    let min = min.filter(|&m| m > 0);
+    let max = max.filter(|&m| m > 0);
+    if let (Some(lo), Some(hi)) = (min, max) {
+        if lo > hi {
+            warn!(target: "core::process", "Ignoring duration filter: min_duration ({}) exceeds max_duration ({})", lo, hi);
+            return None;
+        }
+    }
+    match (min, max) {
+        (Some(lo), Some(hi)) => Some(format!("duration >=? {} & duration <=? {}", lo, hi)),
+        (Some(lo), None) => Some(format!("duration >=? {}", lo)),
+        (None, Some(hi)) => Some(format!("duration <=? {}", hi)),
+        (None, None) => None,
+    }
+}
+
 fn construct_error(
     job_id: uuid::Uuid, 
     msg: String, 
@@ -802,7 +1744,7 @@ fn construct_error(
     }
 }
 
-async fn robust_move_file(src: &Path, dest: &Path) -> Result<(), std::io::Error> {
+pub(crate) async fn robust_move_file(src: &Path, dest: &Path) -> Result<(), std::io::Error> {
     if dest.exists() {
         warn!(target: "core::process", "Destination file already exists during robust move: {:?}", dest);
         return Err(std::io::Error::new(std::io::ErrorKind::AlreadyExists, "Destination file already exists"));
@@ -851,4 +1793,106 @@ async fn robust_move_file(src: &Path, dest: &Path) -> Result<(), std::io::Error>
             }
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// `formats` as yt-dlp lists them in the info JSON, worst first.
+    const FORMATS: &str = r#"[
+        {"format_id": "18",  "ext": "mp4",  "vcodec": "avc1.42001E", "acodec": "mp4a.40.2"},
+        {"format_id": "139", "ext": "m4a",  "vcodec": "none",        "acodec": "mp4a.40.5"},
+        {"format_id": "140", "ext": "m4a",  "vcodec": "none",        "acodec": "mp4a.40.2"},
+        {"format_id": "251", "ext": "webm", "vcodec": "none",        "acodec": "opus"},
+        {"format_id": "137", "ext": "mp4",  "vcodec": "avc1.640028", "acodec": "none"},
+        {"format_id": "22",  "ext": "mp4",  "vcodec": "avc1.64001F", "acodec": "mp4a.40.2"}
+    ]"#;
+
+    static FILTER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[(\w+)(!?=)([^\]]+)\]").unwrap());
+
+    /// Enough of yt-dlp's selector rules for the audio selectors: `/` alternatives,
+    /// `bestaudio` (audio-only) and `best` (combined), `[key=value]` / `[key!=value]`
+    /// filters. Later formats rank higher, as in yt-dlp's sorted list.
+    fn select(selector: &str, formats: &[Value]) -> Option<String> {
+        let field = |f: &Value, key: &str| f.get(key).and_then(Value::as_str).unwrap_or("none").to_string();
+        selector.split('/').find_map(|alt| {
+            let kind = alt.split('[').next().unwrap();
+            formats.iter().rev().find(|f| {
+                let audio_only = field(f, "vcodec") == "none" && field(f, "acodec") != "none";
+                let combined = field(f, "vcodec") != "none" && field(f, "acodec") != "none";
+                let kind_ok = match kind {
+                    "bestaudio" => audio_only,
+                    "best" => combined,
+                    other => panic!("selector kind '{}' not modelled", other),
+                };
+                kind_ok && FILTER_REGEX.captures_iter(alt).all(|c| (field(f, &c[1]) == c[3]) == (&c[2] == "="))
+            }).map(|f| field(f, "format_id"))
+        })
+    }
+
+    fn fixture(ids: &[&str]) -> Vec<Value> {
+        let all: Vec<Value> = serde_json::from_str(FORMATS).unwrap();
+        all.into_iter().filter(|f| ids.contains(&f["format_id"].as_str().unwrap())).collect()
+    }
+
+    #[test]
+    fn audio_presets_prefer_audio_only_over_combined_best() {
+        let formats = fixture(&["18", "139", "140", "251", "137", "22"]);
+        for preset in [DownloadFormatPreset::AudioBest, DownloadFormatPreset::AudioMp3, DownloadFormatPreset::AudioFlac] {
+            let selector = format_selector(&preset, "best").unwrap();
+            assert_eq!(select(&selector, &formats).as_deref(), Some("251"), "{:?}", preset);
+        }
+        let m4a = format_selector(&DownloadFormatPreset::AudioM4a, "best").unwrap();
+        assert_eq!(select(&m4a, &formats).as_deref(), Some("140"));
+    }
+
+    #[test]
+    fn audio_presets_fall_back_to_combined_without_audio_only_streams() {
+        let formats = fixture(&["18", "137", "22"]);
+        assert_eq!(select(AUDIO_ONLY_SELECTOR, &formats).as_deref(), Some("22"));
+        assert_eq!(select(M4A_AUDIO_SELECTOR, &formats).as_deref(), Some("22"));
+
+        // No m4a: any audio-only stream still beats downloading video.
+        let formats = fixture(&["18", "251", "22"]);
+        assert_eq!(select(M4A_AUDIO_SELECTOR, &formats).as_deref(), Some("251"));
+    }
+
+    #[test]
+    fn global_rate_limit_is_shared_across_concurrent_slots() {
+        let config = GeneralConfig { rate_limit: Some("4M".into()), max_concurrent_downloads: 4, ..GeneralConfig::default() };
+        assert_eq!(effective_rate_limit(None, &config), Some(1 << 20));
+        // A job's own limit may lower its share but never raise it.
+        assert_eq!(effective_rate_limit(Some("512K"), &config), Some(512 << 10));
+        assert_eq!(effective_rate_limit(Some("10M"), &config), Some(1 << 20));
+
+        let unlimited = GeneralConfig { rate_limit: None, ..config };
+        assert_eq!(effective_rate_limit(None, &unlimited), None);
+        assert_eq!(effective_rate_limit(Some("10M"), &unlimited), Some(10 << 20));
+    }
+
+    #[test]
+    fn naming_args_match_what_the_download_is_called() {
+        let job = QueuedJob {
+            filename_template: "%(uploader)s/%(title)s.%(ext)s".into(),
+            format_preset: DownloadFormatPreset::AudioMp3,
+            restrict_filenames: true,
+            ..QueuedJob::default()
+        };
+        let args = naming_args(&job);
+        for expected in [
+            &["-o", "%(uploader)s/%(title)s.%(ext)s"][..],
+            &["--windows-filenames"],
+            &["--restrict-filenames", "--trim-filenames", "200"],
+            &["-f", AUDIO_ONLY_SELECTOR],
+            &["-x", "--audio-format", "mp3"],
+        ] {
+            assert!(args.windows(expected.len()).any(|w| w == expected), "{:?} missing from {:?}", expected, args);
+        }
+
+        // --print filename shows the pre-extraction name.
+        assert_eq!(post_processed_name("Someone/Song.webm", &job), "Someone/Song.mp3");
+        let best = QueuedJob { format_preset: DownloadFormatPreset::AudioBest, ..job };
+        assert_eq!(post_processed_name("Someone/Song.webm", &best), "Someone/Song.webm");
+    }
+}
@@ -0,0 +1,61 @@
+use keyring::Entry;
+use tracing::{debug, warn};
+
+const KEYRING_SERVICE: &str = "multiyt-dlp";
+const GITHUB_TOKEN_USER: &str = "github_token";
+
+/// Light sanity check: GitHub's prefixed token formats or a classic 40-char hex token.
+pub fn is_plausible_github_token(token: &str) -> bool {
+    const PREFIXES: [&str; 6] = ["ghp_", "gho_", "ghu_", "ghs_", "ghr_", "github_pat_"];
+    let token = token.trim();
+    if token.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return false;
+    }
+    if let Some(prefix) = PREFIXES.iter().find(|p| token.starts_with(**p)) {
+        let body = &token[prefix.len()..];
+        return body.len() >= 20 && body.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    }
+    token.len() == 40 && token.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn github_entry() -> Result<Entry, String> {
+    Entry::new(KEYRING_SERVICE, GITHUB_TOKEN_USER).map_err(|e| e.to_string())
+}
+
+pub fn get_github_token() -> Option<String> {
+    let entry = match github_entry() {
+        Ok(e) => e,
+        Err(e) => {
+            warn!(target: "core::secrets", "Keyring unavailable: {}", e);
+            return None;
+        }
+    };
+    match entry.get_password() {
+        Ok(token) if !token.trim().is_empty() => Some(token),
+        Ok(_) | Err(keyring::Error::NoEntry) => None,
+        Err(e) => {
+            warn!(target: "core::secrets", "Failed to read GitHub token from keyring: {}", e);
+            None
+        }
+    }
+}
+
+pub fn set_github_token(token: &str) -> Result<(), String> {
+    let token = token.trim();
+    if !is_plausible_github_token(token) {
+        return Err("Token does not look like a GitHub personal access token".into());
+    }
+    github_entry()?.set_password(token).map_err(|e| e.to_string())?;
+    debug!(target: "core::secrets", "GitHub token stored in keyring");
+    Ok(())
+}
+
+pub fn clear_github_token() -> Result<(), String> {
+    match github_entry()?.delete_password() {
+        Ok(_) | Err(keyring::Error::NoEntry) => {
+            debug!(target: "core::secrets", "GitHub token removed from keyring");
+            Ok(())
+        },
+        Err(e) => Err(e.to_string()),
+    }
+}
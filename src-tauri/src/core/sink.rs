@@ -0,0 +1,97 @@
+use tauri::{AppHandle, Manager};
+
+use crate::core::native;
+use crate::core::process::format_speed;
+use crate::models::{
+    BatchHaltedPayload, BatchProgressPayload, DownloadCancelledPayload, DownloadCompletePayload, DownloadErrorPayload,
+    LowDiskSpacePayload,
+};
+
+/// Everything the job manager reports to the outside world. The GUI uses
+/// [`TauriSink`]; a headless front end or a test harness can supply its own
+/// implementation through `JobManagerHandle::with_sink`.
+pub trait ProgressSink: Send + Sync {
+    fn progress_batch(&self, payload: BatchProgressPayload);
+    fn complete(&self, payload: DownloadCompletePayload);
+    fn error(&self, payload: DownloadErrorPayload);
+    fn cancelled(&self, payload: DownloadCancelledPayload);
+    fn batch_halted(&self, payload: BatchHaltedPayload);
+    fn low_disk_space(&self, payload: LowDiskSpacePayload);
+    /// Aggregate progress of all active jobs, 0.0-1.0, with the number of running
+    /// downloads and their combined speed.
+    fn overall_progress(&self, progress: f64, has_error: bool, downloading: u32, bytes_per_sec: f64);
+    fn clear_overall_progress(&self);
+    fn queue_finished(&self, handled: u32);
+}
+
+/// Emits window events and drives the taskbar/dock progress and desktop notifications.
+pub struct TauriSink {
+    app_handle: AppHandle,
+}
+
+impl TauriSink {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self { app_handle }
+    }
+}
+
+impl ProgressSink for TauriSink {
+    fn progress_batch(&self, payload: BatchProgressPayload) {
+        let _ = self.app_handle.emit_all("download-progress-batch", payload);
+    }
+
+    fn complete(&self, payload: DownloadCompletePayload) {
+        let _ = self.app_handle.emit_all("download-complete", payload);
+    }
+
+    fn error(&self, payload: DownloadErrorPayload) {
+        let _ = self.app_handle.emit_all("download-error", payload);
+    }
+
+    fn cancelled(&self, payload: DownloadCancelledPayload) {
+        let _ = self.app_handle.emit_all("download-cancelled", payload);
+    }
+
+    fn batch_halted(&self, payload: BatchHaltedPayload) {
+        let _ = self.app_handle.emit_all("batch-halted", payload);
+    }
+
+    fn low_disk_space(&self, payload: LowDiskSpacePayload) {
+        use tauri::api::notification::Notification;
+        let body = format!(
+            "Only {} MB left on {}. New downloads are on hold until space is freed.",
+            payload.free_bytes / (1024 * 1024), payload.path
+        );
+        let _ = self.app_handle.emit_all("low-disk-space", payload);
+        let _ = Notification::new(self.app_handle.config().tauri.bundle.identifier.clone())
+            .title("Low Disk Space")
+            .body(body)
+            .icon("icons/128x128.png")
+            .show();
+    }
+
+    fn overall_progress(&self, progress: f64, has_error: bool, downloading: u32, bytes_per_sec: f64) {
+        let status = match downloading {
+            0 => None,
+            n if bytes_per_sec > 0.0 => Some(format!("{} downloading \u{2014} {}", n, format_speed(bytes_per_sec))),
+            n => Some(format!("{} downloading", n)),
+        };
+        let app_handle = self.app_handle.clone();
+        let _ = self.app_handle.run_on_main_thread(move || {
+            native::set_taskbar_progress(&app_handle, progress, has_error, status.as_deref());
+        });
+    }
+
+    fn clear_overall_progress(&self) {
+        native::clear_taskbar_progress(&self.app_handle);
+    }
+
+    fn queue_finished(&self, handled: u32) {
+        use tauri::api::notification::Notification;
+        let _ = Notification::new(self.app_handle.config().tauri.bundle.identifier.clone())
+            .title("Downloads Finished")
+            .body(format!("Queue processed. {} files handled.", handled))
+            .icon("icons/128x128.png")
+            .show();
+    }
+}
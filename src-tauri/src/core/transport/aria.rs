@@ -6,7 +6,13 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use regex::Regex;
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
+
+// Mirrors the native engine's CHUNK_THRESHOLD: below this, extra connections cost
+// more in handshakes than they win in throughput.
+const SMALL_FILE_THRESHOLD: u64 = 10 * 1024 * 1024; // 10 MB
+const SMALL_FILE_CONNECTIONS: u32 = 2;
+const MAX_CONNECTIONS: u32 = 16;
 
 pub struct AriaEngine {
     url: String,
@@ -29,7 +35,7 @@ impl AriaEngine {
     }
 
     /// Parses Aria2 size strings (e.g., "53MiB", "5.9KiB", "100B") into bytes
-    fn parse_aria_size(input: &str) -> Option<f64> {
+    pub(crate) fn parse_aria_size(input: &str) -> Option<f64> {
         let clean = input.trim();
         let units =[
             ("GiB", 1024.0 * 1024.0 * 1024.0), 
@@ -51,6 +57,14 @@ impl AriaEngine {
         clean.parse::<f64>().ok()
     }
 
+    /// Connection count for `-s`/`-x`. Unknown sizes get the full count.
+    fn connections(&self) -> u32 {
+        match self.fallback_size {
+            Some(size) if size > 0 && size < SMALL_FILE_THRESHOLD => SMALL_FILE_CONNECTIONS,
+            _ => MAX_CONNECTIONS,
+        }
+    }
+
     pub async fn execute<F>(&self, on_progress: F) -> Result<(), TransportError>
     where
         F: Fn(u64, u64, f64) + Send + Sync + 'static,
@@ -69,6 +83,9 @@ impl AriaEngine {
             let _ = tokio::fs::remove_file(&tmp_path).await;
         }
 
+        let connections = self.connections().to_string();
+        debug!(target: "core::transport::aria", "Using {} connections (size hint: {:?})", connections, self.fallback_size);
+
         let mut cmd = Command::new(&self.aria_bin);
         
         #[cfg(target_os = "windows")]
@@ -79,8 +96,8 @@ impl AriaEngine {
         cmd.arg(&self.url)
            .arg("-d").arg(dir)
            .arg("-o").arg(&tmp_filename)
-           .arg("-s").arg("16") // 16 connections
-           .arg("-x").arg("16") // 16 connections per server
+           .arg("-s").arg(&connections)
+           .arg("-x").arg(&connections) // connections per server
            .arg("-j").arg("1") // 1 download at a time
            .arg("--min-split-size=1M")
            .arg("--allow-overwrite=true")
@@ -90,6 +107,26 @@ impl AriaEngine {
            .stdout(Stdio::piped())
            .stderr(Stdio::piped());
 
+        match super::network::external_proxy(&super::network::current()).map_err(TransportError::Proxy)?.as_deref() {
+            Some("") => {
+                // aria2c reads these itself; clear them to force a direct connection.
+                for var in ["http_proxy", "https_proxy", "ftp_proxy", "all_proxy", "HTTP_PROXY", "HTTPS_PROXY", "FTP_PROXY", "ALL_PROXY"] {
+                    cmd.env_remove(var);
+                }
+            },
+            Some(proxy) => { cmd.arg(format!("--all-proxy={}", proxy)); },
+            None => {},
+        }
+
+        if let Some(rate) = super::network::current().rate_limit {
+            cmd.arg(format!("--max-overall-download-limit={}", rate));
+        }
+
+        if super::network::current().no_check_certificate {
+            warn!(target: "core::transport::aria", "TLS certificate verification disabled for aria2c");
+            cmd.arg("--check-certificate=false");
+        }
+
         debug!(target: "core::transport::aria", "Spawning Aria2 with args: {:?}", cmd);
         let mut child = cmd.spawn().map_err(TransportError::FileSystem)?;
         
@@ -185,3 +222,20 @@ impl AriaEngine {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine(fallback_size: Option<u64>) -> AriaEngine {
+        AriaEngine::new("https://example.com/f", "f".into(), "aria2c".into(), fallback_size, Arc::new(AtomicBool::new(false)))
+    }
+
+    #[test]
+    fn small_files_use_fewer_connections() {
+        assert_eq!(engine(Some(SMALL_FILE_THRESHOLD - 1)).connections(), SMALL_FILE_CONNECTIONS);
+        assert_eq!(engine(Some(SMALL_FILE_THRESHOLD)).connections(), MAX_CONNECTIONS);
+        assert_eq!(engine(None).connections(), MAX_CONNECTIONS);
+        assert_eq!(engine(Some(0)).connections(), MAX_CONNECTIONS);
+    }
+}
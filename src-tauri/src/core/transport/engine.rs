@@ -4,11 +4,13 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use reqwest::{Client, header};
 use tokio::fs::{self, OpenOptions};
-use tokio::io::{AsyncWriteExt, BufWriter}; 
+use tokio::io::{AsyncSeekExt, AsyncWriteExt, BufWriter}; 
 use futures_util::StreamExt;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, trace, warn};
+use crate::core::transport::network;
 use crate::core::transport::retry::{RetryPolicy, TransportError};
 
 // Constants
@@ -16,10 +18,21 @@ const IO_TIMEOUT: Duration = Duration::from_secs(15);
 const CHUNK_THRESHOLD: u64 = 10 * 1024 * 1024; // 10 MB
 const DEFAULT_CONCURRENCY: usize = 4;
 const PROGRESS_INTERVAL_MS: u128 = 100; 
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+const TCP_KEEPALIVE: Duration = Duration::from_secs(30);
+const HTTP2_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(20);
 
 // Kernel-friendly buffer size: 4MB
 const IO_BUFFER_SIZE: usize = 4 * 1024 * 1024;
 
+/// Bytes of each chunk already flushed into the pre-allocated file. Saved next to it
+/// when an attempt fails so the next attempt resumes instead of starting over.
+#[derive(Debug, Serialize, Deserialize)]
+struct PreallocProgress {
+    total_size: u64,
+    written: Vec<u64>,
+}
+
 #[derive(Debug, Clone)]
 struct Chunk {
     index: usize,
@@ -35,19 +48,37 @@ pub struct TransportEngine {
     concurrency: usize,
     chunk_threshold: u64,
     fallback_size: Option<u64>,
+    overwrite: bool,
     cancel_flag: Arc<AtomicBool>,
 }
 
 impl TransportEngine {
-    pub fn new(url: &str, target_path: PathBuf, cancel_flag: Arc<AtomicBool>) -> Self {
+    pub fn new(url: &str, target_path: PathBuf, cancel_flag: Arc<AtomicBool>) -> Result<Self, TransportError> {
         trace!(target: "core::transport", "Building HTTP client for Native Transport Engine");
-        let client = Client::builder()
+        let builder = Client::builder()
             .user_agent("Multiyt-dlp/2.2 (Resumable-Engine)")
             .connect_timeout(Duration::from_secs(10))
-            .redirect(reqwest::redirect::Policy::limited(10)) 
+            .redirect(reqwest::redirect::Policy::limited(10))
+            // Keep one warm connection per chunk worker so range requests against the same
+            // host reuse sockets instead of re-handshaking on every retry. The linear path
+            // only ever holds one, so these are harmless there.
+            .pool_max_idle_per_host(DEFAULT_CONCURRENCY)
+            .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+            .tcp_keepalive(TCP_KEEPALIVE)
+            .http2_keep_alive_interval(HTTP2_KEEPALIVE_INTERVAL)
+            .http2_keep_alive_timeout(IO_TIMEOUT)
+            .http2_keep_alive_while_idle(true);
+        let client = network::apply_network_options(builder, &network::current())
+            .map_err(TransportError::Proxy)?
             .build()
             .expect("Failed to build HTTP client");
 
+        Ok(Self::with_client(client, url, target_path, cancel_flag))
+    }
+
+    /// Uses a pre-built client instead of the default one, e.g. to point the engine
+    /// at a local server. Network options from the config are not applied.
+    pub fn with_client(client: Client, url: &str, target_path: PathBuf, cancel_flag: Arc<AtomicBool>) -> Self {
         Self {
             client,
             url: url.to_string(),
@@ -55,6 +86,7 @@ impl TransportEngine {
             concurrency: DEFAULT_CONCURRENCY,
             chunk_threshold: CHUNK_THRESHOLD,
             fallback_size: Option::None,
+            overwrite: false,
             cancel_flag,
         }
     }
@@ -65,6 +97,13 @@ impl TransportEngine {
         self
     }
 
+    /// Off by default, in which case `finalize` refuses to replace an existing target
+    /// instead of clobbering it.
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
     pub async fn execute<F>(&self, on_progress: F) -> Result<(), TransportError>
     where
         F: Fn(u64, u64, f64) + Send + Sync + 'static + Clone,
@@ -113,12 +152,17 @@ impl TransportEngine {
              return Ok((Option::None, false));
         }
 
-        let len = resp.content_length()
+        // Read the headers: reqwest's content_length() is the body size, which is 0 for
+        // HEAD and 1 for the bytes=0-0 fallback. Content-Range carries the full size there.
+        let len = resp.headers()
+            .get(header::CONTENT_RANGE)
+            .and_then(|val| val.to_str().ok())
+            .and_then(|s| s.rsplit('/').next())
+            .and_then(|s| s.parse::<u64>().ok())
             .or_else(|| {
                 resp.headers()
-                    .get(header::CONTENT_RANGE)
+                    .get(header::CONTENT_LENGTH)
                     .and_then(|val| val.to_str().ok())
-                    .and_then(|s| s.split('/').last())
                     .and_then(|s| s.parse::<u64>().ok())
             });
 
@@ -159,7 +203,7 @@ impl TransportEngine {
                     return Ok(());
                 },
                 Err(e) => {
-                    error!(target: "core::transport", "Linear download chunk attempt failed: {}", e);
+                    error!(target: "core::transport", url = %self.url, attempt = retry_policy.attempt(), error = %e, "Linear download attempt failed");
                     let _ = fs::remove_file(&part_path).await;
                     
                     if let TransportError::Cancelled = e { return Err(e); }
@@ -167,11 +211,19 @@ impl TransportEngine {
                     
                     match retry_policy.next_backoff() {
                         Some(delay) => {
-                            warn!(target: "core::transport", "Retrying linear download after delay of {:?}", delay);
+                            warn!(
+                                target: "core::transport",
+                                url = %self.url,
+                                attempt = retry_policy.attempt(),
+                                max_retries = retry_policy.max_retries(),
+                                delay_ms = delay.as_millis() as u64,
+                                error = %e,
+                                "Linear download interrupted, retrying"
+                            );
                             tokio::time::sleep(delay).await;
                         },
                         Option::None => {
-                            error!(target: "core::transport", "Maximum linear retries exhausted");
+                            error!(target: "core::transport", url = %self.url, attempts = retry_policy.attempt(), error = %e, "Maximum linear retries exhausted");
                             return Err(TransportError::MaxRetriesExceeded);
                         }
                     }
@@ -216,6 +268,7 @@ impl TransportEngine {
                             let len = chunk.len() as u64;
                             trace!(target: "core::transport", "Writing {} bytes to linear output buffer", len);
                             file.write_all(&chunk).await?;
+                            network::throttle(len).await;
                             downloaded += len;
                             bytes_since_update += len;
 
@@ -229,12 +282,12 @@ impl TransportEngine {
                         },
                         Ok(Some(Err(e))) => {
                             error!(target: "core::transport", "Network stream error during linear read: {}", e);
-                            return Err(TransportError::Network(e))
+                            return Err(e.into())
                         },
                         Ok(None) => break,
                         Err(_) => {
                             error!(target: "core::transport", "Network stream read timed out");
-                            return Err(TransportError::Validation("Connection timed out".into()));
+                            return Err(TransportError::Timeout);
                         }
                     }
                 }
@@ -257,10 +310,7 @@ impl TransportEngine {
         Ok(())
     }
 
-    async fn download_concurrent<F>(&self, total_size: u64, on_progress: F) -> Result<(), TransportError>
-    where
-        F: Fn(u64, u64, f64) + Send + Sync + 'static + Clone,
-    {
+    fn plan_chunks(&self, total_size: u64) -> Vec<Chunk> {
         let chunk_size = total_size / (self.concurrency as u64);
         let mut chunks = Vec::new();
 
@@ -274,28 +324,16 @@ impl TransportEngine {
             chunks.push(Chunk { index: i, start, end, len: end - start + 1 });
             trace!(target: "core::transport", "Defined Chunk {}: Start={}, End={}, Length={}", i, start, end, end - start + 1);
         }
+        chunks
+    }
 
-        let bytes_downloaded = Arc::new(AtomicU64::new(0));
-        let hash = self.calculate_deterministic_hash();
-        
-        let mut initial_progress = 0;
-        for i in 0..self.concurrency {
-            let p = self.target_path.with_extension(format!("part.{}.{}", hash, i));
-            if let Ok(m) = fs::metadata(&p).await {
-                initial_progress += m.len();
-                debug!(target: "core::transport", "Resuming Chunk {} from offset {}", i, m.len());
-            }
-        }
-        bytes_downloaded.store(initial_progress, Ordering::Relaxed);
-
-        let mut tasks = Vec::new();
-        let bytes_downloaded_monitor = bytes_downloaded.clone();
-        let on_progress_monitor = on_progress.clone();
+    fn spawn_progress_monitor<F>(&self, bytes_downloaded: Arc<AtomicU64>, initial_progress: u64, total_size: u64, on_progress: F) -> tokio::task::JoinHandle<()>
+    where
+        F: Fn(u64, u64, f64) + Send + Sync + 'static,
+    {
         let cancel_flag_monitor = self.cancel_flag.clone();
-        
-        on_progress(initial_progress, total_size, 0.0);
 
-        let monitor_handle = tokio::spawn(async move {
+        tokio::spawn(async move {
             let mut last_bytes = initial_progress;
             let mut last_time = Instant::now();
             
@@ -303,7 +341,7 @@ impl TransportEngine {
                 tokio::time::sleep(Duration::from_millis(100)).await;
                 if cancel_flag_monitor.load(Ordering::Relaxed) { break; }
 
-                let current = bytes_downloaded_monitor.load(Ordering::Relaxed);
+                let current = bytes_downloaded.load(Ordering::Relaxed);
                 
                 let now = Instant::now();
                 let elapsed = now.duration_since(last_time).as_secs_f64();
@@ -312,14 +350,308 @@ impl TransportEngine {
                     (current.saturating_sub(last_bytes) as f64) / elapsed
                 } else { 0.0 };
 
-                on_progress_monitor(current, total_size, speed);
+                on_progress(current, total_size, speed);
                 
                 last_bytes = current;
                 last_time = now;
                 
                 if current >= total_size { break; }
             }
-        });
+        })
+    }
+
+    /// Prefers positioned writes into one pre-allocated file; falls back to per-chunk
+    /// part files when the target filesystem refuses the pre-allocation. Part files
+    /// left by an earlier fallback attempt are resumed rather than abandoned.
+    async fn download_concurrent<F>(&self, total_size: u64, on_progress: F) -> Result<(), TransportError>
+    where
+        F: Fn(u64, u64, f64) + Send + Sync + 'static + Clone,
+    {
+        if self.has_part_files().await {
+            debug!(target: "core::transport", "Found part files from an earlier attempt, resuming them");
+            return self.download_concurrent_parts(total_size, on_progress).await;
+        }
+
+        let prealloc_path = self.target_path.with_extension(format!("prealloc.{}", self.calculate_deterministic_hash()));
+
+        match self.preallocate(&prealloc_path, total_size).await {
+            Ok(written) => self.download_concurrent_positioned(&prealloc_path, total_size, written, on_progress).await,
+            Err(e) => {
+                warn!(target: "core::transport", "Pre-allocation of {} bytes failed ({}). Falling back to part files.", total_size, e);
+                let _ = fs::remove_file(&prealloc_path).await;
+                let _ = fs::remove_file(Self::progress_sidecar(&prealloc_path)).await;
+                self.download_concurrent_parts(total_size, on_progress).await
+            }
+        }
+    }
+
+    async fn has_part_files(&self) -> bool {
+        let hash = self.calculate_deterministic_hash();
+        for i in 0..self.concurrency {
+            if fs::metadata(self.target_path.with_extension(format!("part.{}.{}", hash, i))).await.is_ok() {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn progress_sidecar(prealloc_path: &Path) -> PathBuf {
+        let mut path = prealloc_path.as_os_str().to_owned();
+        path.push(".progress");
+        PathBuf::from(path)
+    }
+
+    /// Reopens the pre-allocated file of an earlier attempt when its progress sidecar
+    /// matches this download, otherwise creates it from scratch. Returns the bytes
+    /// already written per chunk.
+    async fn preallocate(&self, path: &Path, total_size: u64) -> std::io::Result<Vec<u64>> {
+        let chunks = self.plan_chunks(total_size);
+        if let Some(written) = self.saved_progress(path, total_size, &chunks).await {
+            debug!(target: "core::transport", "Resuming pre-allocated file {:?} at {} bytes", path, written.iter().sum::<u64>());
+            return Ok(written);
+        }
+
+        let file = OpenOptions::new().create(true).write(true).truncate(true).open(path).await?;
+        file.set_len(total_size).await?;
+        file.sync_all().await?;
+        Ok(vec![0; chunks.len()])
+    }
+
+    async fn saved_progress(&self, path: &Path, total_size: u64, chunks: &[Chunk]) -> Option<Vec<u64>> {
+        if fs::metadata(path).await.ok()?.len() != total_size {
+            return None;
+        }
+        let content = fs::read(Self::progress_sidecar(path)).await.ok()?;
+        let progress: PreallocProgress = serde_json::from_slice(&content).ok()?;
+        if progress.total_size != total_size || progress.written.len() != chunks.len() {
+            return None;
+        }
+        Some(progress.written.iter().zip(chunks).map(|(w, c)| (*w).min(c.len)).collect())
+    }
+
+    async fn save_progress(prealloc_path: &Path, total_size: u64, written: Vec<u64>) {
+        let progress = PreallocProgress { total_size, written };
+        let result = match serde_json::to_vec(&progress) {
+            Ok(json) => fs::write(Self::progress_sidecar(prealloc_path), json).await,
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = result {
+            warn!(target: "core::transport", "Failed to save download progress, the next attempt starts over: {}", e);
+        }
+    }
+
+    async fn download_concurrent_positioned<F>(&self, prealloc_path: &Path, total_size: u64, resume_from: Vec<u64>, on_progress: F) -> Result<(), TransportError>
+    where
+        F: Fn(u64, u64, f64) + Send + Sync + 'static + Clone,
+    {
+        debug!(target: "core::transport", "Using positioned writes into pre-allocated file {:?}", prealloc_path);
+        let chunks = self.plan_chunks(total_size);
+        let initial_progress: u64 = resume_from.iter().sum();
+        let bytes_downloaded = Arc::new(AtomicU64::new(initial_progress));
+        // Flushed bytes per chunk, read back to write the sidecar if the attempt fails.
+        let offsets: Arc<Vec<AtomicU64>> = Arc::new(resume_from.into_iter().map(AtomicU64::new).collect());
+
+        on_progress(initial_progress, total_size, 0.0);
+        let monitor_handle = self.spawn_progress_monitor(bytes_downloaded.clone(), initial_progress, total_size, on_progress.clone());
+
+        let mut tasks = Vec::new();
+        for chunk in chunks {
+            let client = self.client.clone();
+            let url = self.url.clone();
+            let total_bytes_atomic = bytes_downloaded.clone();
+            let path = prealloc_path.to_path_buf();
+            let cancel_flag_task = self.cancel_flag.clone();
+            let offsets = offsets.clone();
+
+            tasks.push(tokio::spawn(async move {
+                let mut retry_policy = RetryPolicy::new(15); // Elevated chunk retries
+                // Bytes of this chunk already on disk; retries resume from here.
+                let mut written = offsets[chunk.index].load(Ordering::Relaxed);
+                loop {
+                    let result = Self::download_chunk_positioned(&client, &url, &path, &chunk, &mut written, &total_bytes_atomic, &cancel_flag_task).await;
+                    offsets[chunk.index].store(written, Ordering::Relaxed);
+                    match result {
+                        Ok(_) => {
+                            debug!(target: "core::transport", "Chunk {} completed successfully", chunk.index);
+                            return Ok(());
+                        },
+                        Err(e) => {
+                            error!(target: "core::transport", url = %url, chunk = chunk.index, attempt = retry_policy.attempt(), error = %e, "Chunk download failed");
+                            if let TransportError::Cancelled = e { return Err(e); }
+
+                            match retry_policy.next_backoff() {
+                                Some(delay) => {
+                                    warn!(
+                                        target: "core::transport",
+                                        url = %url,
+                                        chunk = chunk.index,
+                                        offset = written,
+                                        attempt = retry_policy.attempt(),
+                                        max_retries = retry_policy.max_retries(),
+                                        delay_ms = delay.as_millis() as u64,
+                                        error = %e,
+                                        "Chunk interrupted, retrying"
+                                    );
+                                    tokio::time::sleep(delay).await;
+                                },
+                                Option::None => {
+                                    error!(target: "core::transport", url = %url, chunk = chunk.index, attempts = retry_policy.attempt(), error = %e, "Maximum retries exhausted for chunk");
+                                    return Err(e);
+                                }
+                            }
+                        }
+                    }
+                }
+            }));
+        }
+
+        let results = futures_util::future::join_all(tasks).await;
+
+        let mut failed = false;
+        let mut cancelled = false;
+        for res in results {
+            match res {
+                Ok(Ok(_)) => {},
+                Ok(Err(TransportError::Cancelled)) => cancelled = true,
+                _ => failed = true,
+            }
+        }
+
+        // The monitor only stops by itself once every byte has arrived.
+        if cancelled || failed {
+            monitor_handle.abort();
+        }
+        let _ = monitor_handle.await;
+
+        if cancelled || self.cancel_flag.load(Ordering::Relaxed) {
+            error!(target: "core::transport", "Concurrent download aborted due to cancellation");
+            let _ = fs::remove_file(prealloc_path).await;
+            let _ = fs::remove_file(Self::progress_sidecar(prealloc_path)).await;
+            return Err(TransportError::Cancelled);
+        }
+
+        if failed {
+            error!(target: "core::transport", "Concurrent download aborted due to chunk failures");
+            let written = offsets.iter().map(|o| o.load(Ordering::Relaxed)).collect();
+            Self::save_progress(prealloc_path, total_size, written).await;
+            return Err(TransportError::Validation("One or more chunks failed".to_string()));
+        }
+
+        info!(target: "core::transport", "All chunks written in place. Finalizing.");
+        self.finalize(prealloc_path).await?;
+        let _ = fs::remove_file(Self::progress_sidecar(prealloc_path)).await;
+        on_progress(total_size, total_size, 0.0);
+        Ok(())
+    }
+
+    async fn download_chunk_positioned(
+        client: &Client,
+        url: &str,
+        path: &Path,
+        chunk: &Chunk,
+        written: &mut u64,
+        global_bytes: &AtomicU64,
+        cancel_flag: &AtomicBool
+    ) -> Result<(), TransportError> {
+        if *written >= chunk.len {
+            return Ok(());
+        }
+
+        let range_start = chunk.start + *written;
+        trace!(target: "core::transport", "Chunk {} requesting HTTP RANGE bytes={}-{}", chunk.index, range_start, chunk.end);
+        let response = client.get(url)
+            .header(header::RANGE, format!("bytes={}-{}", range_start, chunk.end))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(TransportError::HttpStatus(response.status().as_u16()));
+        }
+
+        let mut raw_file = OpenOptions::new().write(true).open(path).await?;
+        raw_file.seek(std::io::SeekFrom::Start(range_start)).await?;
+        let mut file = BufWriter::with_capacity(IO_BUFFER_SIZE, raw_file);
+
+        let mut stream = response.bytes_stream();
+        let remaining_for_chunk = chunk.len - *written;
+        let mut session_bytes = 0u64;
+
+        let stream_result: Result<(), TransportError> = loop {
+            if cancel_flag.load(Ordering::Relaxed) {
+                break Err(TransportError::Cancelled);
+            }
+
+            let chunk_fut = tokio::time::timeout(IO_TIMEOUT, stream.next());
+            let sleep_fut = tokio::time::sleep(Duration::from_millis(500));
+
+            tokio::select! {
+                chunk_res = chunk_fut => {
+                    match chunk_res {
+                        Ok(Some(Ok(bytes))) => {
+                            let len = bytes.len() as u64;
+                            if session_bytes + len > remaining_for_chunk {
+                                error!(target: "core::transport", "Chunk {} received out-of-bounds bytes from server", chunk.index);
+                                break Err(TransportError::Validation("Server exceeded requested byte range".into()));
+                            }
+                            if let Err(e) = file.write_all(&bytes).await {
+                                break Err(e.into());
+                            }
+                            network::throttle(len).await;
+                            session_bytes += len;
+                            global_bytes.fetch_add(len, Ordering::Relaxed);
+                        },
+                        Ok(Some(Err(e))) => break Err(e.into()),
+                        Ok(None) => break Ok(()),
+                        Err(_) => {
+                            error!(target: "core::transport", "Chunk stream read timed out");
+                            break Err(TransportError::Timeout);
+                        }
+                    }
+                }
+                _ = sleep_fut => {
+                    // Unblock to recheck cancel_flag
+                }
+            }
+        };
+
+        // Flush even on failure so the bytes we counted are really on disk for the retry.
+        if let Err(e) = file.flush().await {
+            global_bytes.fetch_sub(session_bytes, Ordering::Relaxed);
+            return Err(e.into());
+        }
+        *written += session_bytes;
+        stream_result?;
+
+        if *written != chunk.len {
+            return Err(TransportError::Validation(format!("Chunk {} incomplete. Got {}, expected {}", chunk.index, *written, chunk.len)));
+        }
+
+        Ok(())
+    }
+
+    async fn download_concurrent_parts<F>(&self, total_size: u64, on_progress: F) -> Result<(), TransportError>
+    where
+        F: Fn(u64, u64, f64) + Send + Sync + 'static + Clone,
+    {
+        let chunks = self.plan_chunks(total_size);
+
+        let bytes_downloaded = Arc::new(AtomicU64::new(0));
+        let hash = self.calculate_deterministic_hash();
+        
+        let mut initial_progress = 0;
+        for i in 0..self.concurrency {
+            let p = self.target_path.with_extension(format!("part.{}.{}", hash, i));
+            if let Ok(m) = fs::metadata(&p).await {
+                initial_progress += m.len();
+                debug!(target: "core::transport", "Resuming Chunk {} from offset {}", i, m.len());
+            }
+        }
+        bytes_downloaded.store(initial_progress, Ordering::Relaxed);
+
+        let mut tasks = Vec::new();
+        
+        on_progress(initial_progress, total_size, 0.0);
+        let monitor_handle = self.spawn_progress_monitor(bytes_downloaded.clone(), initial_progress, total_size, on_progress.clone());
 
         for chunk in chunks {
             let client = self.client.clone();
@@ -337,16 +669,25 @@ impl TransportEngine {
                             return Ok(part_path)
                         },
                         Err(e) => {
-                            error!(target: "core::transport", "Chunk {} failed with error: {}", chunk.index, e);
+                            error!(target: "core::transport", url = %url, chunk = chunk.index, attempt = retry_policy.attempt(), error = %e, "Chunk download failed");
                             if let TransportError::Cancelled = e { return Err(e); }
                             
                             match retry_policy.next_backoff() {
                                 Some(delay) => {
-                                    warn!(target: "core::transport", "Retrying Chunk {} after delay of {:?}", chunk.index, delay);
+                                    warn!(
+                                        target: "core::transport",
+                                        url = %url,
+                                        chunk = chunk.index,
+                                        attempt = retry_policy.attempt(),
+                                        max_retries = retry_policy.max_retries(),
+                                        delay_ms = delay.as_millis() as u64,
+                                        error = %e,
+                                        "Chunk interrupted, retrying"
+                                    );
                                     tokio::time::sleep(delay).await;
                                 },
                                 Option::None => {
-                                    error!(target: "core::transport", "Maximum retries exhausted for Chunk {}", chunk.index);
+                                    error!(target: "core::transport", url = %url, chunk = chunk.index, attempts = retry_policy.attempt(), error = %e, "Maximum retries exhausted for chunk");
                                     return Err(e);
                                 }
                             }
@@ -357,7 +698,6 @@ impl TransportEngine {
         }
 
         let results = futures_util::future::join_all(tasks).await;
-        let _ = monitor_handle.await; 
 
         let mut part_paths = Vec::new();
         let mut failed = false;
@@ -371,6 +711,11 @@ impl TransportEngine {
             }
         }
 
+        if cancelled || failed {
+            monitor_handle.abort();
+        }
+        let _ = monitor_handle.await;
+
         if cancelled || self.cancel_flag.load(Ordering::Relaxed) {
             error!(target: "core::transport", "Concurrent download aborted due to cancellation");
             for p in &part_paths {
@@ -466,14 +811,15 @@ impl TransportEngine {
                             }
                             trace!(target: "core::transport", "Chunk {} writing {} bytes", chunk.index, len);
                             file.write_all(&bytes).await?;
+                            network::throttle(len).await;
                             downloaded_in_this_session += len;
                             global_bytes.fetch_add(len, Ordering::Relaxed);
                         },
-                        Ok(Some(Err(e))) => return Err(TransportError::Network(e)),
+                        Ok(Some(Err(e))) => return Err(e.into()),
                         Ok(None) => break,
                         Err(_) => {
                             error!(target: "core::transport", "Chunk stream read timed out");
-                            return Err(TransportError::Validation("Connection timed out".into()));
+                            return Err(TransportError::Timeout);
                         }
                     }
                 }
@@ -540,7 +886,256 @@ impl TransportEngine {
 
     async fn finalize(&self, source_path: &Path) -> Result<(), TransportError> {
         debug!(target: "core::transport", "Finalizing TransportEngine payload to destination: {:?}", self.target_path);
+        if !self.overwrite {
+            // Plain rename only; the locked-file swap below exists purely for replacing binaries.
+            if self.target_path.exists() {
+                let _ = fs::remove_file(source_path).await;
+                return Err(TransportError::AlreadyExists(self.target_path.display().to_string()));
+            }
+            fs::rename(source_path, &self.target_path).await?;
+            return Ok(());
+        }
         crate::core::deps::replace_dependency_robust_sync(source_path, &self.target_path).map_err(TransportError::FileSystem)?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    /// What the mock server saw: method and `Range` header of each request.
+    type RequestLog = Arc<Mutex<Vec<(String, Option<String>)>>>;
+
+    #[derive(Clone, Default)]
+    struct ServerBehaviour {
+        ranges: bool,
+        not_found: bool,
+        /// Answer the first ranged GET starting at this offset with a 500.
+        fail_once_at: Option<u64>,
+    }
+
+    /// Minimal HTTP/1.1 server: serves `body` for HEAD and GET, honouring single
+    /// `bytes=a-b` ranges when `ranges` is set. Every response closes the connection.
+    async fn serve(body: Vec<u8>, behaviour: ServerBehaviour) -> (String, RequestLog) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/file.bin", listener.local_addr().unwrap());
+        let log: RequestLog = Arc::default();
+        let body = Arc::new(body);
+        let failed = Arc::new(AtomicBool::new(false));
+
+        let log_server = log.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { return };
+                let (body, behaviour, log, failed) = (body.clone(), behaviour.clone(), log_server.clone(), failed.clone());
+                tokio::spawn(async move {
+                    let mut raw = Vec::new();
+                    let mut buf = [0u8; 1024];
+                    while !raw.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => raw.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let head = String::from_utf8_lossy(&raw).to_string();
+                    let method = head.split(' ').next().unwrap_or_default().to_string();
+                    let range = head.lines()
+                        .find_map(|l| l.split_once(':').filter(|(k, _)| k.eq_ignore_ascii_case("range")))
+                        .map(|(_, v)| v.trim().to_string());
+                    log.lock().unwrap().push((method.clone(), range.clone()));
+
+                    let len = body.len() as u64;
+                    let parsed = range.as_deref()
+                        .and_then(|r| r.strip_prefix("bytes="))
+                        .and_then(|r| r.split_once('-'))
+                        .and_then(|(a, b)| Some((a.parse::<u64>().ok()?, b.parse::<u64>().ok()?.min(len.saturating_sub(1)))));
+
+                    let (status, headers, payload): (&str, String, &[u8]) = if behaviour.not_found {
+                        ("404 Not Found", "Content-Length: 0\r\n".into(), &[])
+                    } else if method == "HEAD" {
+                        let accept = if behaviour.ranges { "Accept-Ranges: bytes\r\n" } else { "" };
+                        ("200 OK", format!("Content-Length: {}\r\n{}", len, accept), &[])
+                    } else if let (true, Some((start, end))) = (behaviour.ranges, parsed) {
+                        if behaviour.fail_once_at == Some(start) && !failed.swap(true, Ordering::SeqCst) {
+                            ("500 Internal Server Error", "Content-Length: 0\r\n".into(), &[])
+                        } else {
+                            let slice = &body[start as usize..=end as usize];
+                            ("206 Partial Content", format!("Content-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\n", slice.len(), start, end, len), slice)
+                        }
+                    } else {
+                        ("200 OK", format!("Content-Length: {}\r\n", len), &body[..])
+                    };
+
+                    let response = format!("HTTP/1.1 {}\r\n{}Connection: close\r\n\r\n", status, headers);
+                    let _ = socket.write_all(response.as_bytes()).await;
+                    let _ = socket.write_all(payload).await;
+                    let _ = socket.shutdown().await;
+                });
+            }
+        });
+        (url, log)
+    }
+
+    /// Deterministic, non-repeating-looking payload with a size that doesn't split evenly.
+    fn payload(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i.wrapping_mul(31) ^ (i >> 7)) as u8).collect()
+    }
+
+    fn engine(url: &str, target: PathBuf) -> TransportEngine {
+        let client = Client::builder().no_proxy().build().unwrap();
+        let mut engine = TransportEngine::with_client(client, url, target, Arc::new(AtomicBool::new(false)));
+        engine.chunk_threshold = 1024;
+        engine
+    }
+
+    fn gets(log: &RequestLog) -> Vec<Option<String>> {
+        log.lock().unwrap().iter().filter(|(m, _)| m == "GET").map(|(_, r)| r.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn ranged_server_uses_concurrent_path() {
+        let body = payload(64 * 1024 + 3);
+        let (url, log) = serve(body.clone(), ServerBehaviour { ranges: true, ..Default::default() }).await;
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out.bin");
+
+        engine(&url, target.clone()).execute(|_, _, _| {}).await.unwrap();
+
+        let gets = gets(&log);
+        assert_eq!(gets.len(), DEFAULT_CONCURRENCY);
+        assert!(gets.iter().all(|r| r.is_some()), "every GET should be ranged: {:?}", gets);
+        assert_eq!(fs::read(&target).await.unwrap(), body);
+    }
+
+    #[tokio::test]
+    async fn server_without_ranges_uses_linear_path() {
+        let body = payload(64 * 1024 + 3);
+        let (url, log) = serve(body.clone(), ServerBehaviour::default()).await;
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out.bin");
+
+        engine(&url, target.clone()).execute(|_, _, _| {}).await.unwrap();
+
+        assert_eq!(gets(&log), vec![None]);
+        assert_eq!(fs::read(&target).await.unwrap(), body);
+    }
+
+    #[tokio::test]
+    async fn not_found_fails_without_retrying() {
+        let (url, log) = serve(Vec::new(), ServerBehaviour { not_found: true, ..Default::default() }).await;
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out.bin");
+
+        let started = Instant::now();
+        let result = engine(&url, target.clone()).execute(|_, _, _| {}).await;
+
+        assert!(matches!(result, Err(TransportError::HttpStatus(404))), "{:?}", result);
+        assert!(started.elapsed() < Duration::from_secs(1), "404 must not back off");
+        // The HEAD probe, then its ranged-GET fallback; nothing after that.
+        assert_eq!(log.lock().unwrap().len(), 2);
+        assert!(!target.exists());
+    }
+
+    #[tokio::test]
+    async fn failed_chunk_is_retried_then_succeeds() {
+        let body = payload(64 * 1024 + 3);
+        let engine = engine("", PathBuf::new());
+        let second_chunk = engine.plan_chunks(body.len() as u64)[1].start;
+        let (url, log) = serve(body.clone(), ServerBehaviour { ranges: true, fail_once_at: Some(second_chunk), ..Default::default() }).await;
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out.bin");
+
+        let engine = TransportEngine { url: url.clone(), target_path: target.clone(), ..engine };
+        engine.execute(|_, _, _| {}).await.unwrap();
+
+        let prefix = format!("bytes={}-", second_chunk);
+        let retried = gets(&log).iter().filter(|r| r.as_deref().is_some_and(|r| r.starts_with(&prefix))).count();
+        assert_eq!(retried, 2, "chunk should be requested once, fail, then be requested again");
+        assert_eq!(fs::read(&target).await.unwrap(), body);
+    }
+
+    #[tokio::test]
+    async fn merged_parts_match_served_body() {
+        let body = payload(256 * 1024 + 7);
+        let (url, _log) = serve(body.clone(), ServerBehaviour { ranges: true, ..Default::default() }).await;
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out.bin");
+
+        engine(&url, target.clone()).download_concurrent_parts(body.len() as u64, |_, _, _| {}).await.unwrap();
+
+        assert_eq!(fs::read(&target).await.unwrap(), body);
+        let mut leftovers = fs::read_dir(dir.path()).await.unwrap();
+        let mut names = Vec::new();
+        while let Some(entry) = leftovers.next_entry().await.unwrap() {
+            names.push(entry.file_name());
+        }
+        assert_eq!(names, vec![std::ffi::OsString::from("out.bin")], "part files should be cleaned up");
+    }
+
+    #[tokio::test]
+    async fn positioned_writes_match_linear_download() {
+        let body = payload(512 * 1024 + 13);
+        let len = body.len() as u64;
+        let (plain_url, _) = serve(body.clone(), ServerBehaviour::default()).await;
+        let (ranged_url, _) = serve(body.clone(), ServerBehaviour { ranges: true, ..Default::default() }).await;
+        let dir = tempfile::tempdir().unwrap();
+
+        let linear = dir.path().join("linear.bin");
+        engine(&plain_url, linear.clone()).download_linear(Some(len), |_, _, _| {}).await.unwrap();
+
+        let positioned = dir.path().join("positioned.bin");
+        engine(&ranged_url, positioned.clone()).download_concurrent(len, |_, _, _| {}).await.unwrap();
+
+        // A directory squatting on the pre-allocation path makes it fail, forcing part files.
+        let fallback = dir.path().join("fallback.bin");
+        let fallback_engine = engine(&ranged_url, fallback.clone());
+        let prealloc = fallback.with_extension(format!("prealloc.{}", fallback_engine.calculate_deterministic_hash()));
+        fs::create_dir(&prealloc).await.unwrap();
+        assert!(fallback_engine.preallocate(&prealloc, len).await.is_err());
+        fallback_engine.download_concurrent(len, |_, _, _| {}).await.unwrap();
+
+        let linear = fs::read(&linear).await.unwrap();
+        assert_eq!(linear, body);
+        assert_eq!(fs::read(&positioned).await.unwrap(), linear);
+        assert_eq!(fs::read(&fallback).await.unwrap(), linear);
+    }
+
+    #[tokio::test]
+    async fn positioned_download_resumes_from_saved_progress() {
+        let body = payload(256 * 1024 + 5);
+        let len = body.len() as u64;
+        let (url, log) = serve(body.clone(), ServerBehaviour { ranges: true, ..Default::default() }).await;
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out.bin");
+        let engine = engine(&url, target.clone());
+        let chunks = engine.plan_chunks(len);
+
+        // An earlier attempt finished chunks 0 and 2 and got 100 bytes into chunk 3.
+        // Everything else in the file is garbage that must be overwritten.
+        let prealloc = target.with_extension(format!("prealloc.{}", engine.calculate_deterministic_hash()));
+        let mut partial = vec![0xAAu8; body.len()];
+        for (chunk, done) in chunks.iter().zip([chunks[0].len, 0, chunks[2].len, 100]) {
+            let (start, end) = (chunk.start as usize, (chunk.start + done) as usize);
+            partial[start..end].copy_from_slice(&body[start..end]);
+        }
+        fs::write(&prealloc, &partial).await.unwrap();
+        TransportEngine::save_progress(&prealloc, len, vec![chunks[0].len, 0, chunks[2].len, 100]).await;
+
+        engine.download_concurrent(len, |_, _, _| {}).await.unwrap();
+
+        let mut expected = vec![
+            Some(format!("bytes={}-{}", chunks[1].start, chunks[1].end)),
+            Some(format!("bytes={}-{}", chunks[3].start + 100, chunks[3].end)),
+        ];
+        let mut requested = gets(&log);
+        requested.sort();
+        expected.sort();
+        assert_eq!(requested, expected);
+        assert_eq!(fs::read(&target).await.unwrap(), body);
+        assert!(!prealloc.exists());
+        assert!(!TransportEngine::progress_sidecar(&prealloc).exists());
+    }
+}
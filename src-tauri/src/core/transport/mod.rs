@@ -1,6 +1,7 @@
 pub mod engine;
 pub mod retry;
 pub mod aria;
+pub mod network;
 
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -9,15 +10,10 @@ use tauri::{AppHandle, Manager};
 use self::engine::TransportEngine;
 use self::aria::AriaEngine;
 use self::retry::TransportError;
-use serde::Serialize;
+use crate::core::deps::{emit_install_progress, InstallPhase};
 use tracing::{info, warn, debug};
+use crate::config::{ConfigManager, TransportEngineChoice};
 
-#[derive(Clone, Serialize)]
-struct InstallProgressPayload {
-    name: String,
-    percentage: u64,
-    status: String,
-}
 
 pub async fn download_file_robust(
     url: &str,
@@ -32,18 +28,20 @@ pub async fn download_file_robust(
     let app_handle_clone = app_handle.clone();
     
     // Initial UI Update
-    let _ = app_handle_clone.emit_all("install-progress", InstallProgressPayload {
-        name: name_arc.to_string(),
-        percentage: 0,
-        status: "Initializing...".to_string()
-    });
+    emit_install_progress(&app_handle_clone, &name_arc, 0, InstallPhase::Downloading, "Initializing...");
 
-    // 1. Check if Aria2 is available
-    let bin_dir = crate::core::deps::get_common_bin_dir();
+    // 1. Check if Aria2 is available and allowed
+    let bin_dir = crate::core::paths::bin_dir();
     let aria_exe = if cfg!(windows) { "aria2c.exe" } else { "aria2c" };
     let aria_path = bin_dir.join(aria_exe);
     
+    let choice = app_handle.state::<Arc<ConfigManager>>().get_config().general.transport_engine();
     let aria_exists = aria_path.exists() && name.to_lowercase() != "aria2";
+    let use_aria = aria_exists && choice != TransportEngineChoice::Native;
+    if choice == TransportEngineChoice::Aria2 && !aria_exists {
+        warn!(target: "core::transport", "transport_engine is aria2 but aria2c is unavailable for {}, using native engine", name);
+    }
+    debug!(target: "core::transport", engine = ?choice, use_aria, "Selected transport engine for {}", name);
 
     // Shared state for the progress closure
     let last_percentage = Arc::new(AtomicU64::new(0));
@@ -70,15 +68,12 @@ pub async fn download_file_robust(
                 format!("{:.1} MB/s", speed_mb) 
             };
             
-            let _ = app_handle_clone.emit_all("install-progress", InstallProgressPayload {
-                name: name_arc.to_string(),
-                percentage,
-                status: status_msg
-            });
+            let phase = if percentage == 100 { InstallPhase::Verifying } else { InstallPhase::Downloading };
+            emit_install_progress(&app_handle_clone, &name_arc, percentage, phase, status_msg);
         }
     };
 
-    if aria_exists {
+    if use_aria {
         info!(target: "core::transport", "Attempting Aria2 robust download: {}", name);
         let engine = AriaEngine::new(url, destination.clone(), aria_path, fallback_size, cancel_flag.clone());
         
@@ -91,7 +86,12 @@ pub async fn download_file_robust(
                 if matches!(e, TransportError::Cancelled) {
                     return Err(e);
                 }
-                warn!(target: "core::transport", "Aria2 failed, falling back to internal engine: {}", e);
+                if choice == TransportEngineChoice::Aria2 {
+                    warn!(target: "core::transport", name, url, error = %e, "Aria2 failed and the native fallback is disabled");
+                    emit_install_progress(app_handle, name, 0, InstallPhase::Failed, e.to_string());
+                    return Err(e);
+                }
+                warn!(target: "core::transport", name, url, error = %e, "Aria2 failed, falling back to internal engine");
                 let _ = tokio::fs::remove_file(&destination).await;
                 let aria_tmp = format!("{}.aria2", destination.display());
                 let _ = tokio::fs::remove_file(std::path::Path::new(&aria_tmp)).await;
@@ -104,19 +104,24 @@ pub async fn download_file_robust(
     }
 
     info!(target: "core::transport", "Using native internal engine to download: {}", name);
-    let _ = app_handle.emit_all("install-progress", InstallProgressPayload {
-        name: name.to_string(),
-        percentage: 0,
-        status: "Downloading (Native Fallback)...".to_string()
-    });
+    emit_install_progress(app_handle, name, 0, InstallPhase::Downloading, if use_aria { "Downloading (Native Fallback)..." } else { "Downloading..." });
 
     let dummy_callback = |_: u64, _: u64, _: f64| {};
-    let mut engine = TransportEngine::new(url, destination, cancel_flag.clone());
+    // Dependency updates always replace the previous binary.
+    let mut engine = TransportEngine::new(url, destination, cancel_flag.clone())
+        .inspect_err(|e| emit_install_progress(app_handle, name, 0, InstallPhase::Failed, e.to_string()))?
+        .with_overwrite(true);
     if let Some(s) = fallback_size {
         engine = engine.with_fallback_size(s);
     }
     
-    engine.execute(dummy_callback).await?;
+    if let Err(e) = engine.execute(dummy_callback).await {
+        warn!(target: "core::transport", name, url, error = %e, "Native download failed");
+        if !matches!(e, TransportError::Cancelled) {
+            emit_install_progress(app_handle, name, 0, InstallPhase::Failed, e.to_string());
+        }
+        return Err(e);
+    }
     debug!(target: "core::transport", "Native download completed successfully: {}", name);
 
     Ok(())
@@ -0,0 +1,205 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use arc_swap::ArcSwap;
+use once_cell::sync::Lazy;
+use reqwest::{ClientBuilder, Proxy};
+use tracing::{debug, error, info, warn};
+use crate::config::GeneralConfig;
+
+/// Process-wide network settings shared by every reqwest client the app builds.
+/// Refreshed from `GeneralConfig` on startup and whenever the config is saved.
+static CURRENT: Lazy<ArcSwap<NetworkOptions>> = Lazy::new(|| ArcSwap::from_pointee(NetworkOptions::default()));
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkOptions {
+    pub proxy_url: Option<String>,
+    pub no_proxy: bool,
+    pub offline_mode: bool,
+    /// Skip TLS certificate verification. Insecure; only set when the user opts in.
+    pub no_check_certificate: bool,
+    /// Bytes per second shared by all native transfers; `None` is unlimited.
+    pub rate_limit: Option<u64>,
+}
+
+/// Where a client's proxy comes from, in order of precedence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProxySource {
+    /// `no_proxy` is set, or `proxy_url` is an empty string: ignore both the config
+    /// and the environment (yt-dlp reads `--proxy ""` the same way).
+    Direct,
+    /// `proxy_url` from the app config.
+    Explicit(String),
+    /// reqwest's default: `HTTP_PROXY` / `HTTPS_PROXY` / `ALL_PROXY` / `NO_PROXY`.
+    System,
+}
+
+impl NetworkOptions {
+    pub fn from_config(config: &GeneralConfig) -> Self {
+        Self {
+            proxy_url: config.proxy_url.clone(),
+            no_proxy: config.no_proxy,
+            offline_mode: config.offline_mode,
+            no_check_certificate: config.no_check_certificate,
+            rate_limit: config.rate_limit.as_deref().and_then(parse_rate_limit),
+        }
+    }
+
+    pub fn proxy_source(&self) -> ProxySource {
+        if self.no_proxy {
+            return ProxySource::Direct;
+        }
+        match self.proxy_url.as_deref().map(str::trim) {
+            Some("") => ProxySource::Direct,
+            Some(url) => ProxySource::Explicit(url.to_string()),
+            None => ProxySource::System,
+        }
+    }
+}
+
+pub fn current() -> Arc<NetworkOptions> {
+    CURRENT.load_full()
+}
+
+/// Converts a `rate_limit` such as "500K" or "2.5M" to bytes per second (binary units,
+/// matching yt-dlp). Returns `None` for empty, zero or malformed values.
+pub fn parse_rate_limit(limit: &str) -> Option<u64> {
+    let limit = limit.trim();
+    let (number, multiplier) = match limit.chars().last()?.to_ascii_uppercase() {
+        'K' => (&limit[..limit.len() - 1], 1u64 << 10),
+        'M' => (&limit[..limit.len() - 1], 1u64 << 20),
+        'G' => (&limit[..limit.len() - 1], 1u64 << 30),
+        'T' => (&limit[..limit.len() - 1], 1u64 << 40),
+        _ => (limit, 1),
+    };
+    let bytes = number.parse::<f64>().ok()? * multiplier as f64;
+    (bytes >= 1.0).then_some(bytes as u64)
+}
+
+/// When the next byte may be sent under `rate_limit`, shared by every transfer.
+static THROTTLE_CLOCK: Lazy<Mutex<Instant>> = Lazy::new(|| Mutex::new(Instant::now()));
+
+/// Accounts for `bytes` just received and sleeps long enough to keep all native
+/// transfers together under `rate_limit`. Returns immediately when unlimited.
+pub async fn throttle(bytes: u64) {
+    let Some(rate) = CURRENT.load().rate_limit else { return };
+    let wait = {
+        let mut next = THROTTLE_CLOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let start = (*next).max(now);
+        *next = start + Duration::from_secs_f64(bytes as f64 / rate as f64);
+        start - now
+    };
+    if !wait.is_zero() {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// True when the user has asked the app to stay off the network for update checks.
+pub fn is_offline() -> bool {
+    CURRENT.load().offline_mode
+}
+
+pub fn apply_config(config: &GeneralConfig) {
+    let opts = NetworkOptions::from_config(config);
+    info!(target: "core::transport::network", source = ?opts.proxy_source(), offline = opts.offline_mode, rate_limit = ?opts.rate_limit, "Network options updated");
+    if opts.no_check_certificate {
+        warn!(target: "core::transport::network", "TLS CERTIFICATE VERIFICATION IS DISABLED (no_check_certificate). Connections can be intercepted.");
+    }
+    CURRENT.store(Arc::new(opts));
+}
+
+/// Parses a `proxy_url` the way every client will. Saving rejects a malformed one, and
+/// anything that still gets through fails the connection instead of going direct.
+pub fn validate_proxy_url(url: &str) -> Result<Proxy, String> {
+    Proxy::all(url).map_err(|e| format!("Proxy URL '{}' is malformed: {}", url, e))
+}
+
+/// Applies the proxy and TLS policy to a client builder. Fails on a malformed
+/// `proxy_url` so traffic meant for the proxy never leaves directly.
+pub fn apply_network_options(builder: ClientBuilder, opts: &NetworkOptions) -> Result<ClientBuilder, String> {
+    let builder = if opts.no_check_certificate {
+        warn!(target: "core::transport::network", "Building HTTP client without TLS certificate verification");
+        builder.danger_accept_invalid_certs(true)
+    } else {
+        builder
+    };
+
+    match opts.proxy_source() {
+        ProxySource::Direct => {
+            debug!(target: "core::transport::network", "Proxy disabled, forcing direct connection");
+            Ok(builder.no_proxy())
+        },
+        ProxySource::Explicit(url) => {
+            let proxy = validate_proxy_url(&url).inspect_err(|e| error!(target: "core::transport::network", "{}", e))?;
+            debug!(target: "core::transport::network", "Routing through configured proxy");
+            Ok(builder.proxy(proxy))
+        },
+        ProxySource::System => Ok(builder),
+    }
+}
+
+/// Value for yt-dlp's `--proxy` (aria2c's `--all-proxy`): the configured proxy, an empty
+/// string to force a direct connection, or `None` to leave the proxy environment
+/// variables in charge. A malformed `proxy_url` is an error, never a direct connection.
+pub fn external_proxy(opts: &NetworkOptions) -> Result<Option<String>, String> {
+    match opts.proxy_source() {
+        ProxySource::Direct => Ok(Some(String::new())),
+        ProxySource::Explicit(url) => {
+            validate_proxy_url(&url).inspect_err(|e| error!(target: "core::transport::network", "{}", e))?;
+            Ok(Some(url))
+        },
+        ProxySource::System => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Client;
+
+    fn opts(proxy_url: Option<&str>, no_proxy: bool) -> NetworkOptions {
+        NetworkOptions { proxy_url: proxy_url.map(str::to_string), no_proxy, ..Default::default() }
+    }
+
+    #[test]
+    fn proxy_source_follows_precedence() {
+        assert_eq!(opts(None, false).proxy_source(), ProxySource::System);
+        assert_eq!(opts(Some(""), false).proxy_source(), ProxySource::Direct);
+        assert_eq!(opts(Some("   "), false).proxy_source(), ProxySource::Direct);
+        assert_eq!(opts(Some(" http://proxy.local:3128 "), false).proxy_source(), ProxySource::Explicit("http://proxy.local:3128".into()));
+        assert_eq!(opts(Some("http://proxy.local:3128"), true).proxy_source(), ProxySource::Direct);
+        assert_eq!(opts(None, true).proxy_source(), ProxySource::Direct);
+    }
+
+    #[test]
+    fn from_config_carries_proxy_and_offline_settings() {
+        let config = GeneralConfig {
+            proxy_url: Some("socks5://127.0.0.1:1080".into()),
+            no_proxy: true,
+            offline_mode: true,
+            ..Default::default()
+        };
+        let opts = NetworkOptions::from_config(&config);
+        assert!(opts.offline_mode);
+        assert_eq!(opts.proxy_source(), ProxySource::Direct);
+        assert_eq!(opts.proxy_url.as_deref(), Some("socks5://127.0.0.1:1080"));
+    }
+
+    #[test]
+    fn apply_network_options_builds_for_every_source() {
+        for opts in [opts(None, false), opts(None, true), opts(Some(""), false), opts(Some("http://proxy.local:3128"), false)] {
+            let builder = apply_network_options(Client::builder(), &opts).unwrap();
+            assert!(builder.build().is_ok(), "{:?}", opts);
+        }
+        assert!(apply_network_options(Client::builder(), &opts(Some("not a proxy"), false)).is_err());
+    }
+
+    #[test]
+    fn offline_mode_is_published_by_apply_config() {
+        let config = GeneralConfig { offline_mode: true, ..Default::default() };
+        apply_config(&config);
+        assert!(is_offline());
+        apply_config(&GeneralConfig::default());
+        assert!(!is_offline());
+    }
+}
@@ -5,17 +5,38 @@ use tracing::debug;
 #[derive(Debug, Error)]
 pub enum TransportError {
     #[error("Network IO failed: {0}")]
-    Network(#[from] reqwest::Error),
+    Network(reqwest::Error),
+
+    #[error("DNS resolution failed — check your internet connection ({0})")]
+    Dns(String),
+
+    #[error("Connection refused — the server or a proxy rejected the connection ({0})")]
+    ConnectionRefused(String),
+
+    #[error("Could not connect to server — check your connection or proxy settings ({0})")]
+    Connect(String),
+
+    #[error("Secure connection failed — a proxy or firewall may be intercepting TLS ({0})")]
+    Tls(String),
+
+    #[error("Connection timed out — the server may be slow or blocked")]
+    Timeout,
     
     #[error("File system failed: {0}")]
     FileSystem(#[from] std::io::Error),
     
+    #[error("Target already exists: {0}")]
+    AlreadyExists(String),
+
     #[error("Validation failed: {0}")]
     Validation(String),
     
     #[error("Server returned error status: {0}")]
     HttpStatus(u16),
 
+    #[error("Proxy settings are invalid — {0}")]
+    Proxy(String),
+
     #[error("Max retries exceeded")]
     MaxRetriesExceeded,
 
@@ -23,6 +44,47 @@ pub enum TransportError {
     Cancelled,
 }
 
+/// Flattens an error and its `source()` chain; reqwest hides the useful part
+/// (hyper / io / rustls) several levels down.
+fn error_chain_text(err: &dyn std::error::Error) -> String {
+    let mut text = err.to_string();
+    let mut source = err.source();
+    while let Some(s) = source {
+        text.push_str(": ");
+        text.push_str(&s.to_string());
+        source = s.source();
+    }
+    text
+}
+
+impl From<reqwest::Error> for TransportError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            return TransportError::Timeout;
+        }
+
+        let detail = error_chain_text(&err);
+        let lower = detail.to_lowercase();
+        let is_tls = lower.contains("certificate") || lower.contains("tls") || lower.contains("handshake");
+
+        if err.is_connect() {
+            if lower.contains("dns error") || lower.contains("failed to lookup address") || lower.contains("name or service not known") || lower.contains("no such host") {
+                TransportError::Dns(detail)
+            } else if lower.contains("connection refused") || lower.contains("actively refused") {
+                TransportError::ConnectionRefused(detail)
+            } else if is_tls {
+                TransportError::Tls(detail)
+            } else {
+                TransportError::Connect(detail)
+            }
+        } else if err.is_request() && is_tls {
+            TransportError::Tls(detail)
+        } else {
+            TransportError::Network(err)
+        }
+    }
+}
+
 pub struct RetryPolicy {
     max_retries: u32,
     current_attempt: u32,
@@ -38,6 +100,15 @@ impl RetryPolicy {
         }
     }
 
+    /// Number of backoffs handed out so far (1-based once retrying has started).
+    pub fn attempt(&self) -> u32 {
+        self.current_attempt
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
     /// Calculates the next backoff duration.
     /// Returns None if max retries have been exceeded.
     pub fn next_backoff(&mut self) -> Option<Duration> {
@@ -34,13 +34,12 @@ fn main() {
     
     core::deps::register_sfs_app();
 
-    let home = dirs::home_dir().expect("Could not find home directory");
-    let temp_dir = home.join(".multiyt-dlp").join("temp_downloads");
+    let temp_dir = core::paths::temp_dir();
     if !temp_dir.exists() {
         let _ = fs::create_dir_all(&temp_dir);
     }
 
-    let common_bin_dir = core::deps::get_common_bin_dir();
+    let common_bin_dir = core::paths::bin_dir();
     if let Ok(entries) = std::fs::read_dir(&common_bin_dir) {
         for entry in entries.flatten() {
             if let Some(name) = entry.path().file_name() {
@@ -51,16 +50,18 @@ fn main() {
         }
     }
 
-    if let Err(e) = rotate_logs() {
-        eprintln!("WARNING: Log rotation failed: {}", e);
-    }
-
     let config_manager = Arc::new(ConfigManager::new());
     
     let initial_config = config_manager.get_config();
-    let log_manager = LogManager::init(&initial_config.general.log_level);
+
+    if let Err(e) = rotate_logs(&initial_config.general) {
+        eprintln!("WARNING: Log rotation failed: {}", e);
+    }
+
+    let log_manager = LogManager::init(&initial_config.general.log_level, initial_config.general.is_daily_log_rotation());
+    core::transport::network::apply_config(&initial_config.general);
     
-    let history_manager = HistoryManager::new();
+    let history_manager = HistoryManager::new(initial_config.general.max_history_entries);
 
     let config_manager_setup = config_manager.clone();
     let config_manager_event = config_manager.clone();
@@ -174,13 +175,19 @@ fn main() {
             commands::system::install_dependency,
             commands::system::cancel_dependency_install,
             commands::system::sync_dependencies,
+            commands::system::uninstall_dependency,
             commands::system::open_external_link,
             commands::system::close_splash,
+            commands::system::ready_check,
             commands::system::get_latest_app_version, 
+            commands::system::check_app_update,
             commands::system::show_in_folder, 
             commands::system::open_log_folder,
+            commands::system::get_app_paths,
             commands::system::log_frontend_message, 
             commands::system::request_attention,
+            commands::system::get_supported_sites,
+            commands::system::get_ytdlp_capabilities,
             
             commands::downloader::start_download,
             commands::downloader::cancel_download,
@@ -189,15 +196,43 @@ fn main() {
             commands::downloader::get_pending_jobs,
             commands::downloader::resume_pending_jobs,
             commands::downloader::clear_pending_jobs,
+            commands::downloader::clear_finished_persistence,
+            commands::downloader::pause_queue,
+            commands::downloader::resume_queue,
+            commands::downloader::get_queue_summary,
+            commands::downloader::get_stats,
+            commands::downloader::get_group_progress,
+            commands::downloader::can_retry,
+            commands::downloader::relocate_download,
+            commands::downloader::retry_group_failures,
+            commands::downloader::preview_filename,
+            commands::downloader::preview_conflicts,
+            commands::downloader::estimate_batch_size,
+            commands::downloader::set_job_rate_limit,
+            commands::downloader::set_job_max_retries,
+            commands::downloader::force_retry_now,
+            commands::downloader::retry_without_merging,
             commands::downloader::sync_download_state,
+            commands::downloader::generate_error_report,
             
             commands::config::get_app_config,
             commands::config::save_general_config,
+            commands::config::validate_config,
+            commands::config::open_config_file,
+            commands::config::reload_config,
+            commands::config::set_log_level,
             commands::config::save_preference_config,
+            commands::config::get_site_preferences,
+            commands::config::save_site_preferences,
+            commands::config::set_github_token,
+            commands::config::has_github_token,
+            commands::config::set_cookies_text,
+            commands::config::clear_cookies,
             
             commands::history::get_download_history,
             commands::history::save_download_history,
             commands::history::clear_download_history,
+            commands::history::normalize_url,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -12,11 +12,60 @@ pub enum JobStatus {
     Cancelled,
     Error,
     FileConflict,
+    Skipped,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// What a job is currently doing. The UI derives the display text from this, so
+/// nothing here is meant to be shown verbatim.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobPhase {
+    Queued,
+    Initializing,
+    /// Lazy provisioning; the detail names the dependency.
+    CheckingDependency,
+    /// Restarted with sanitized filenames after a filesystem error.
+    RetryingSanitized,
+    /// Restarted with a looser format selection; the detail is the fallback level.
+    FormatFallback,
+    StartingDownload,
+    Downloading,
+    ParsingMetadata,
+    /// Fetching comments for the `.info.json` sidecar.
+    DownloadingComments,
+    WritingMetadata,
+    EmbeddingThumbnail,
+    Merging,
+    ExtractingAudio,
+    FixingContainer,
+    /// Stream-copying into the requested container (`remux_only`).
+    Remuxing,
+    /// Any other ffmpeg step.
+    PostProcessing,
+    Finalizing,
+    MovingToLibrary,
+    FileConflict,
+    Done,
+    Discarded,
+    /// Deliberately skipped by yt-dlp; the detail is the reason.
+    Skipped,
+}
+
+impl JobPhase {
+    /// Steps after the media is downloaded. Progress lines seen during them must not
+    /// pull the phase back to `Downloading`.
+    pub fn is_post_processing(self) -> bool {
+        matches!(self,
+            JobPhase::Merging | JobPhase::ExtractingAudio | JobPhase::WritingMetadata
+            | JobPhase::EmbeddingThumbnail | JobPhase::FixingContainer | JobPhase::Remuxing | JobPhase::PostProcessing
+            | JobPhase::MovingToLibrary)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DownloadFormatPreset {
+    #[default]
     Best,
     BestMp4,
     BestMkv,
@@ -27,6 +76,61 @@ pub enum DownloadFormatPreset {
     AudioM4a,
 }
 
+impl DownloadFormatPreset {
+    /// The string stored in `PreferenceConfig` and sent by the frontend.
+    pub fn to_preset_string(&self) -> &'static str {
+        match self {
+            Self::Best => "best",
+            Self::BestMp4 => "best_mp4",
+            Self::BestMkv => "best_mkv",
+            Self::BestWebm => "best_webm",
+            Self::AudioBest => "audio_best",
+            Self::AudioMp3 => "audio_mp3",
+            Self::AudioFlac => "audio_flac",
+            Self::AudioM4a => "audio_m4a",
+        }
+    }
+
+    /// Audio presets are remembered in `audio_preset`, everything else in `video_preset`.
+    pub fn is_audio(&self) -> bool {
+        matches!(self, Self::AudioBest | Self::AudioMp3 | Self::AudioFlac | Self::AudioM4a)
+    }
+
+    /// Output container of the video presets that pin one.
+    pub fn container(&self) -> Option<&'static str> {
+        match self {
+            Self::BestMp4 => Some("mp4"),
+            Self::BestMkv => Some("mkv"),
+            Self::BestWebm => Some("webm"),
+            _ => None,
+        }
+    }
+
+    /// Presets that merge streams or transcode audio. `Best` can fall back to a
+    /// progressive format and `AudioBest` keeps the source codec, so neither needs it.
+    pub fn requires_ffmpeg(&self) -> bool {
+        !matches!(self, Self::Best | Self::AudioBest)
+    }
+}
+
+impl TryFrom<&str> for DownloadFormatPreset {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim() {
+            "best" => Ok(Self::Best),
+            "best_mp4" => Ok(Self::BestMp4),
+            "best_mkv" => Ok(Self::BestMkv),
+            "best_webm" => Ok(Self::BestWebm),
+            "audio_best" => Ok(Self::AudioBest),
+            "audio_mp3" => Ok(Self::AudioMp3),
+            "audio_flac" => Ok(Self::AudioFlac),
+            "audio_m4a" => Ok(Self::AudioM4a),
+            other => Err(format!("Unknown format preset: '{}'", other)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Job {
     pub id: Uuid,
@@ -42,7 +146,9 @@ pub struct Job {
     pub speed: Option<String>,
     pub eta: Option<String>,
     pub filename: Option<String>,
-    pub phase: Option<String>,
+    pub phase: Option<JobPhase>,
+    /// Extra context for `phase`, e.g. a fragment count or skip reason.
+    pub phase_detail: Option<String>,
     pub error: Option<String>,
     pub exit_code: Option<i32>,
     pub stderr: Option<String>,
@@ -73,10 +179,46 @@ pub struct Job {
     #[serde(rename = "downloadSections")]
     pub download_sections: Option<String>,
 
+    #[serde(rename = "musicMetadata")]
+    pub music_metadata: Option<bool>,
+
+    #[serde(rename = "groupId")]
+    pub group_id: Option<Uuid>,
+
+    #[serde(rename = "groupTotal")]
+    pub group_total: Option<u32>,
+
+    #[serde(rename = "groupIndex")]
+    pub group_index: Option<u32>,
+
+    #[serde(rename = "groupTitle")]
+    pub group_title: Option<String>,
+
+    #[serde(rename = "rateLimit")]
+    pub rate_limit: Option<String>,
+
+    /// Result of the post-download hook; `None` exit code means it never ran to completion.
+    #[serde(rename = "hookExitCode")]
+    pub hook_exit_code: Option<i32>,
+
+    #[serde(rename = "hookOutput")]
+    pub hook_output: Option<String>,
+
+    /// Times this job has been re-queued after failing, against its `max_retries` budget.
+    #[serde(rename = "retryCount")]
+    pub retry_count: u32,
+
+    #[serde(rename = "maxRetries")]
+    pub max_retries: u32,
+
     pub is_modified: bool,
     pub used_command: Option<String>,
 }
 
+/// Retry budget a new job starts with; `set_job_max_retries` can raise it per job.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+pub const MAX_RETRIES_LIMIT: u32 = 20;
+
 impl Job {
     pub fn new(id: Uuid, url: String) -> Self {
         Self {
@@ -92,6 +234,7 @@ impl Job {
             eta: None,
             filename: None,
             phase: None,
+            phase_detail: None,
             error: None,
             exit_code: None,
             stderr: None,
@@ -105,6 +248,16 @@ impl Job {
             restrict_filenames: None,
             live_from_start: None,
             download_sections: None,
+            music_metadata: None,
+            group_id: None,
+            group_total: None,
+            group_index: None,
+            group_title: None,
+            rate_limit: None,
+            hook_exit_code: None,
+            hook_output: None,
+            retry_count: 0,
+            max_retries: DEFAULT_MAX_RETRIES,
             is_modified: false,
             used_command: None,
         }
@@ -125,7 +278,8 @@ pub struct Download {
     pub temp_path: Option<String>,
     pub error: Option<String>,
     pub filename: Option<String>,
-    pub phase: Option<String>,
+    pub phase: Option<JobPhase>,
+    pub phase_detail: Option<String>,
     pub exit_code: Option<i32>,
     pub stderr: Option<String>,
     pub logs: Option<String>,
@@ -155,11 +309,107 @@ pub struct Download {
     #[serde(rename = "downloadSections")]
     pub download_sections: Option<String>,
 
+    #[serde(rename = "musicMetadata")]
+    pub music_metadata: Option<bool>,
+
     #[serde(rename = "usedCommand")]
     pub used_command: Option<String>,
+
+    /// 0-based position in the execution queue; `None` once a job has left the queue.
+    #[serde(rename = "queuePosition")]
+    pub queue_position: Option<u32>,
+
+    #[serde(rename = "groupId")]
+    pub group_id: Option<Uuid>,
+
+    #[serde(rename = "groupTotal")]
+    pub group_total: Option<u32>,
+
+    #[serde(rename = "rateLimit")]
+    pub rate_limit: Option<String>,
+
+    #[serde(rename = "hookExitCode")]
+    pub hook_exit_code: Option<i32>,
+
+    #[serde(rename = "hookOutput")]
+    pub hook_output: Option<String>,
+
+    #[serde(rename = "retryCount")]
+    pub retry_count: u32,
+
+    #[serde(rename = "maxRetries")]
+    pub max_retries: u32,
+}
+
+/// Aggregate state of the jobs queued from one playlist.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupProgress {
+    pub group_id: Uuid,
+    pub title: Option<String>,
+    pub total: u32,
+    /// Completed, modified or skipped.
+    pub completed: u32,
+    pub failed: u32,
+    pub cancelled: u32,
+    /// 0-100 across the whole group; finished jobs count as 100.
+    pub percentage: f32,
+}
+
+/// A failed member of a playlist group, as seen by `retry_group_failures`.
+#[derive(Debug, Clone)]
+pub struct GroupFailure {
+    pub id: Uuid,
+    pub url: String,
+    /// Failed with an error the classifier treats as permanent, or its request is gone.
+    pub is_fatal: bool,
+}
+
+/// Outcome of re-queuing the failed items of a playlist group.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupRetryResult {
+    pub requeued: u32,
+    /// Permanently unavailable per the fatal-error patterns; not retried.
+    pub skipped_fatal: u32,
+    /// Re-probed and still unreachable for now.
+    pub still_unavailable: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    /// The setting works but something is missing or will be ignored.
+    Warning,
+    /// The setting is rejected or will make downloads fail.
+    Error,
+}
+
+/// One finding of `validate_config`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigIssue {
+    pub field: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+/// Outcome of restoring the persisted queue.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeResult {
+    pub resumed: Vec<QueuedJob>,
+    /// Persisted jobs skipped because the same job (or URL) is already active.
+    pub already_active: Vec<Uuid>,
+}
+
+/// Whether a job may be retried, and if not, why.
+#[derive(Debug, Clone, Serialize)]
+pub struct RetryEligibility {
+    pub can_retry: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct QueuedJob {
     pub id: Uuid,
     pub url: String,
@@ -172,15 +422,101 @@ pub struct QueuedJob {
     pub restrict_filenames: bool,
     pub live_from_start: bool,
     pub download_sections: Option<String>,
+    #[serde(default)]
+    pub music_metadata: bool,
+    /// Shared by every job queued from one playlist.
+    #[serde(default)]
+    pub group_id: Option<Uuid>,
+    #[serde(default)]
+    pub group_total: Option<u32>,
+    /// 0-based position within the playlist.
+    #[serde(default)]
+    pub group_index: Option<u32>,
+    #[serde(default)]
+    pub group_title: Option<String>,
+    /// yt-dlp `-S` string applied on top of the preset's selector.
+    #[serde(default)]
+    pub format_sort: Option<String>,
+    /// yt-dlp `--limit-rate` for this job only, e.g. "500K".
+    #[serde(default)]
+    pub rate_limit: Option<String>,
+    /// yt-dlp `--audio-quality` for the transcoding audio presets: a VBR level (0-9)
+    /// or a bitrate like "128K". `None` keeps the best quality.
+    #[serde(default)]
+    pub audio_quality: Option<String>,
+    /// Set on a live recording restored after a restart: its temp dir holds the
+    /// fragments captured so far, which the worker continues instead of wiping.
+    #[serde(default)]
+    pub resume_live: bool,
+    /// Save the live chat replay as a `.live_chat.json` sidecar.
+    #[serde(default)]
+    pub write_live_chat: bool,
+    /// Save comments into an `.info.json` sidecar. Can take a long time on popular videos.
+    #[serde(default)]
+    pub write_comments: bool,
+    /// Store the page URL in the file's `comment` and `purl` tags so the source can be
+    /// found again from the file alone.
+    #[serde(default)]
+    pub embed_source_url: bool,
+    /// Save the uploader's subtitles (`--write-subs`).
+    #[serde(default)]
+    pub download_subtitles: bool,
+    /// Also save auto-generated captions (`--write-auto-subs`).
+    #[serde(default)]
+    pub auto_subtitles: bool,
+    /// `--sub-langs` entries, e.g. `["en", "de.*"]`. `None` leaves yt-dlp's default (English).
+    #[serde(default)]
+    pub subtitle_langs: Option<Vec<String>>,
+    /// Mux the subtitles into the output container instead of keeping sidecar files.
+    #[serde(default)]
+    pub embed_subtitles: bool,
+    /// For the container presets, stream-copy into the container with `--remux-video`
+    /// instead of `--merge-output-format`. Fails rather than re-encodes when the codecs
+    /// don't fit the container.
+    #[serde(default)]
+    pub remux_only: bool,
+    /// Download video and audio as separate files instead of merging them. A recovery
+    /// mode for jobs whose merge failed; set through "retry without merging".
+    #[serde(default)]
+    pub split_streams: bool,
+    /// Set by the worker after yt-dlp reports the requested format as unavailable; the
+    /// next attempt asks for the best format of the same kind (video or audio) instead.
+    #[serde(default)]
+    pub relax_format: bool,
     
     pub status: Option<String>,
     pub error: Option<String>,
     pub stderr: Option<String>,
 }
 
+/// On-disk layout of `jobs.json`. Files written before the version header existed hold a
+/// bare job array; `parse` accepts both.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PersistedQueue {
+    /// App version that last wrote the file.
+    #[serde(default)]
+    pub app_version: Option<String>,
+    /// `yt-dlp --version` of the managed binary at the time of writing.
+    #[serde(default)]
+    pub ytdlp_version: Option<String>,
+    pub jobs: Vec<QueuedJob>,
+}
+
+impl PersistedQueue {
+    pub fn parse(content: &str) -> Option<Self> {
+        serde_json::from_str::<Self>(content).ok().or_else(|| {
+            serde_json::from_str::<Vec<QueuedJob>>(content).ok().map(|jobs| Self { jobs, ..Default::default() })
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PlaylistResult {
+    /// Playlist title; `None` for a single video.
+    pub title: Option<String>,
     pub entries: Vec<PlaylistEntry>,
+    /// Playlist items that had neither a usable `url` nor a reconstructable id.
+    pub dropped_count: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -190,12 +526,90 @@ pub struct PlaylistEntry {
     pub title: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct SizeEstimateItem {
+    pub url: String,
+    pub title: Option<String>,
+    /// `None` when the site reports neither `filesize` nor `filesize_approx`.
+    pub bytes: Option<u64>,
+    /// True only when every selected format reported an exact `filesize` and no
+    /// audio conversion follows.
+    pub exact: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchSizeEstimate {
+    pub total_bytes: u64,
+    pub unknown_count: u32,
+    pub items: Vec<SizeEstimateItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileConflictItem {
+    pub url: String,
+    pub filename: String,
+    /// Full path of the existing file the download would collide with.
+    pub path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnresolvedUrl {
+    pub url: String,
+    pub error: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ConflictPreview {
+    /// Number of output names resolved across all URLs (playlists count each entry).
+    pub checked_count: u32,
+    pub conflicts: Vec<FileConflictItem>,
+    /// URLs whose output name could not be resolved.
+    pub unresolved: Vec<UnresolvedUrl>,
+}
+
+/// Everything `start_download` takes besides the URL. Unset options fall back to the
+/// global settings or off.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartDownloadOptions {
+    pub download_path: Option<String>,
+    pub format_preset: String,
+    pub video_resolution: String,
+    pub embed_metadata: bool,
+    pub embed_thumbnail: bool,
+    pub filename_template: String,
+    pub restrict_filenames: Option<bool>,
+    pub force_download: Option<bool>,
+    pub live_from_start: Option<bool>,
+    /// Entries picked from a playlist; skips the probe.
+    pub url_whitelist: Option<Vec<String>>,
+    pub download_sections: Option<String>,
+    pub music_metadata: Option<bool>,
+    pub record_in_history: Option<bool>,
+    pub title_filter: Option<String>,
+    pub format_sort: Option<String>,
+    pub rate_limit: Option<String>,
+    pub audio_quality: Option<String>,
+    pub write_live_chat: Option<bool>,
+    pub write_comments: Option<bool>,
+    pub embed_source_url: Option<bool>,
+    pub download_subtitles: Option<bool>,
+    pub auto_subtitles: Option<bool>,
+    pub subtitle_langs: Option<Vec<String>>,
+    pub embed_subtitles: Option<bool>,
+    pub remux_only: Option<bool>,
+    pub apply_site_preferences: Option<bool>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct StartDownloadResponse {
     pub job_ids: Vec<Uuid>,
     pub skipped_count: u32,
     pub total_found: u32,
     pub skipped_urls: Vec<String>,
+    /// Entries whose title didn't match `title_filter`.
+    pub filtered_count: u32,
 }
 
 #[derive(Clone, serde::Serialize)]
@@ -207,7 +621,8 @@ pub struct DownloadProgressPayload {
     pub speed: String,
     pub eta: String,
     pub filename: Option<String>,
-    pub phase: Option<String>,
+    pub phase: Option<JobPhase>,
+    pub phase_detail: Option<String>,
     pub status: Option<JobStatus>,
 }
 
@@ -227,6 +642,37 @@ pub struct DownloadCompletePayload {
     pub used_command: Option<String>,
 }
 
+#[derive(Clone, serde::Serialize)]
+pub struct QueuePreparingPayload {
+    pub url: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct QueuePreparedPayload {
+    pub url: String,
+    pub total: u32,
+    pub success: bool,
+}
+
+/// Sent when `stop_on_first_error` cancelled the rest of the queue.
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchHaltedPayload {
+    pub failed_job_id: Uuid,
+    pub cancelled_job_ids: Vec<Uuid>,
+}
+
+/// Sent when free space on a download or temp volume drops below `min_free_space_mb`.
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LowDiskSpacePayload {
+    pub path: String,
+    pub free_bytes: u64,
+    pub min_free_bytes: u64,
+    /// Running downloads that were suspended because of it.
+    pub suspended_count: u32,
+}
+
 #[derive(Clone, serde::Serialize)]
 pub struct DownloadCancelledPayload {
     #[serde(rename = "jobId")]
@@ -243,8 +689,49 @@ pub struct DownloadErrorPayload {
     pub logs: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueSummary {
+    /// No new jobs are started while set. Survives restarts.
+    pub paused: bool,
+    /// Running jobs whose processes are currently stopped.
+    pub suspended_count: u32,
+    pub queued_count: u32,
+    pub active_count: u32,
+    /// Set while the free-space guard is holding the queue back.
+    pub low_disk_space: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UsageCounters {
+    pub completed: u64,
+    pub failed: u64,
+    pub retries: u64,
+    /// Size of the finished files, measured once they are in place.
+    pub bytes_downloaded: u64,
+}
+
+impl UsageCounters {
+    pub fn add(&mut self, other: &UsageCounters) {
+        self.completed += other.completed;
+        self.failed += other.failed;
+        self.retries += other.retries;
+        self.bytes_downloaded += other.bytes_downloaded;
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageStats {
+    /// Since the app started.
+    pub session: UsageCounters,
+    /// All sessions, including this one. Kept in `stats.json`.
+    pub lifetime: UsageCounters,
+}
+
 pub enum JobMessage {
-    AddJob { job: QueuedJob, resp: oneshot::Sender<Result<(), String>> },
+    AddJob { job: Box<QueuedJob>, resp: oneshot::Sender<Result<(), String>> },
     CancelJob { id: Uuid },
     ResolveConflict { id: Uuid, resolution: String, resp: oneshot::Sender<Result<(), String>> },
     UpdateProgress { 
@@ -253,16 +740,79 @@ pub enum JobMessage {
         speed: String, 
         eta: String, 
         filename: Option<String>, 
-        phase: String 
+        phase: JobPhase,
+        detail: Option<String>,
     },
-    ProcessStarted { id: Uuid, pid: u32 },
+    ProcessStarted { id: Uuid, pid: u32, used_command: String },
     JobCompleted { id: Uuid, output_path: String, is_modified: bool, used_command: String },
     JobError { id: Uuid, payload: DownloadErrorPayload },
+    JobSkipped { id: Uuid, reason: String },
     FileConflict { id: Uuid, temp_path: String, output_path: String, is_modified: bool, used_command: String },
     WorkerFinished,
+    HookFinished { id: Uuid, exit_code: Option<i32>, output: String },
     GetPendingCount(oneshot::Sender<u32>),
-    ResumePending(oneshot::Sender<Vec<QueuedJob>>),
+    GetActiveCount(oneshot::Sender<u32>),
+    GetJob { id: Uuid, resp: oneshot::Sender<Option<Job>> },
+    SetRateLimit { id: Uuid, limit: Option<String>, resp: oneshot::Sender<Result<(), String>> },
+    ResumePending(oneshot::Sender<ResumeResult>),
     ClearPending,
+    ClearFinishedPersistence(oneshot::Sender<u32>),
     SyncState(oneshot::Sender<Vec<Download>>),
+    PauseQueue { suspend_running: bool, resp: oneshot::Sender<QueueSummary> },
+    ResumeQueue(oneshot::Sender<QueueSummary>),
+    GetQueueSummary(oneshot::Sender<QueueSummary>),
+    GetStats(oneshot::Sender<UsageStats>),
+    GetGroupProgress(oneshot::Sender<Vec<GroupProgress>>),
+    CanRetry { id: Uuid, resp: oneshot::Sender<RetryEligibility> },
+    SetOutputPath { id: Uuid, output_path: String, resp: oneshot::Sender<Result<(), String>> },
+    GetGroupFailures { group_id: Uuid, resp: oneshot::Sender<Vec<GroupFailure>> },
+    /// `immediate` jumps the queue and ignores the retry budget.
+    RequeueJob { id: Uuid, immediate: bool, split_streams: bool, resp: oneshot::Sender<Result<(), String>> },
+    SetMaxRetries { id: Uuid, max_retries: u32, resp: oneshot::Sender<Result<(), String>> },
     Shutdown(oneshot::Sender<()>),
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_PRESETS: [DownloadFormatPreset; 8] = [
+        DownloadFormatPreset::Best,
+        DownloadFormatPreset::BestMp4,
+        DownloadFormatPreset::BestMkv,
+        DownloadFormatPreset::BestWebm,
+        DownloadFormatPreset::AudioBest,
+        DownloadFormatPreset::AudioMp3,
+        DownloadFormatPreset::AudioFlac,
+        DownloadFormatPreset::AudioM4a,
+    ];
+
+    #[test]
+    fn preset_strings_round_trip() {
+        for preset in ALL_PRESETS {
+            let s = preset.to_preset_string();
+            assert_eq!(DownloadFormatPreset::try_from(s), Ok(preset.clone()));
+            assert_eq!(DownloadFormatPreset::try_from(format!(" {} ", s).as_str()), Ok(preset.clone()));
+            // The frontend sends the serde form; both spellings must agree.
+            assert_eq!(serde_json::to_value(&preset).unwrap(), serde_json::Value::from(s));
+        }
+        assert!(DownloadFormatPreset::try_from("audio").is_err());
+        assert!(DownloadFormatPreset::try_from("BEST_MP4").is_err());
+    }
+
+    #[test]
+    fn audio_and_video_presets_are_told_apart() {
+        for preset in ALL_PRESETS {
+            assert_eq!(preset.is_audio(), preset.to_preset_string().starts_with("audio_"), "{:?}", preset);
+            assert_eq!(preset.container().is_some(), preset.to_preset_string().starts_with("best_"), "{:?}", preset);
+        }
+    }
+
+    #[test]
+    fn generic_ffmpeg_phase_counts_as_post_processing() {
+        assert!(JobPhase::PostProcessing.is_post_processing());
+        assert!(JobPhase::Merging.is_post_processing());
+        assert!(!JobPhase::Downloading.is_post_processing());
+        assert!(!JobPhase::Done.is_post_processing());
+    }
+}
@@ -87,7 +87,7 @@ function App() {
   };
 
   const handleClearCancelled = () => {
-    const cancelledJobs = Array.from(downloads.values()).filter(d => d.status === 'cancelled');
+    const cancelledJobs = Array.from(downloads.values()).filter(d => d.status === 'cancelled' || d.status === 'skipped');
     cancelledJobs.forEach(job => removeDownload(job.jobId));
   };
 
@@ -107,7 +107,8 @@ function App() {
             true,
             undefined,
             job.liveFromStart || false,
-            job.downloadSections
+            job.downloadSections,
+            job.musicMetadata || false
         );
     });
   };
@@ -137,7 +138,9 @@ function App() {
             false,
             true, 
             skipNotice.skippedUrls,
-            preferences.live_from_start
+            preferences.live_from_start,
+            undefined,
+            preferences.music_metadata
         );
         setSkipNotice(null);
     } catch (e) {
@@ -157,7 +160,7 @@ function App() {
   const queued = Array.from(downloads.values()).filter(d => d.status === 'pending').length;
   const completed = Array.from(downloads.values()).filter(d => d.status === 'completed' || d.status === 'modified').length;
   const failed = Array.from(downloads.values()).filter(d => d.status === 'error').length;
-  const cancelledCount = Array.from(downloads.values()).filter(d => d.status === 'cancelled').length;
+  const cancelledCount = Array.from(downloads.values()).filter(d => d.status === 'cancelled' || d.status === 'skipped').length;
   const hasActiveJobs = active > 0 || queued > 0;
 
   return (
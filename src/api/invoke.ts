@@ -1,6 +1,6 @@
 import { invoke } from "@tauri-apps/api/tauri";
 import { open } from "@tauri-apps/api/dialog";
-import { DownloadFormatPreset, AppDependencies, AppConfig, GeneralConfig, PreferenceConfig, PlaylistResult, QueuedJob, StartDownloadResponse, Download } from '@/types';
+import { DownloadFormatPreset, AppDependencies, DependencyReadiness, AppConfig, GeneralConfig, PreferenceConfig, PlaylistResult, StartDownloadResponse, Download, QueueSummary, GroupProgress, BatchSizeEstimate, AppUpdateInfo, RetryEligibility, GroupRetryResult, ResumeResult, ConfigIssue, ConflictPreview, AppPaths, UsageStats, YtDlpCapabilities, SitePreferences } from '@/types';
 
 // New Granular Types
 export interface LocalScanResult {
@@ -30,23 +30,43 @@ export async function cancelDependencyInstall(name: string): Promise<void> {
     return await invoke("cancel_dependency_install", { name });
 }
 
+export async function uninstallDependency(name: string): Promise<AppDependencies> {
+    return await invoke("uninstall_dependency", { name });
+}
+
 export async function syncDependencies(): Promise<AppDependencies> {
     // Legacy mapping for compatibility
     return await invoke("sync_dependencies");
 }
 
+export async function getSupportedSites(filter?: string): Promise<string[]> {
+    return await invoke("get_supported_sites", { filter });
+}
+
+export async function getYtdlpCapabilities(refresh: boolean = false): Promise<YtDlpCapabilities> {
+    return await invoke("get_ytdlp_capabilities", { refresh });
+}
+
 export async function openExternalLink(url: string): Promise<void> {
   return await invoke("open_external_link", { url });
 }
 
-export async function closeSplash(): Promise<void> {
-  return await invoke("close_splash");
+export async function closeSplash(force: boolean = false, timeoutSecs?: number): Promise<DependencyReadiness> {
+  return await invoke("close_splash", { force, timeoutSecs });
+}
+
+export async function readyCheck(timeoutSecs?: number): Promise<DependencyReadiness> {
+  return await invoke("ready_check", { timeoutSecs });
 }
 
 export async function getLatestAppVersion(): Promise<string> {
     return await invoke("get_latest_app_version");
 }
 
+export async function checkAppUpdate(): Promise<AppUpdateInfo> {
+    return await invoke("check_app_update");
+}
+
 export async function requestAttention(): Promise<void> {
     return await invoke("request_attention");
 }
@@ -59,6 +79,18 @@ export async function openLogFolder(): Promise<void> {
     return await invoke("open_log_folder");
 }
 
+export async function getAppPaths(): Promise<AppPaths> {
+    return await invoke("get_app_paths");
+}
+
+export async function openConfigFile(): Promise<void> {
+    return await invoke("open_config_file");
+}
+
+export async function reloadConfig(): Promise<AppConfig> {
+    return await invoke("reload_config");
+}
+
 // --- Config API ---
 
 export async function getAppConfig(): Promise<AppConfig> {
@@ -69,10 +101,42 @@ export async function saveGeneralConfig(config: GeneralConfig): Promise<void> {
     return await invoke("save_general_config", { config });
 }
 
+export async function validateConfig(): Promise<ConfigIssue[]> {
+    return await invoke("validate_config");
+}
+
+export async function setLogLevel(level: 'error' | 'warn' | 'info' | 'debug' | 'trace'): Promise<void> {
+    return await invoke("set_log_level", { level });
+}
+
 export async function savePreferenceConfig(config: PreferenceConfig): Promise<void> {
     return await invoke("save_preference_config", { config });
 }
 
+export async function getSitePreferences(): Promise<Record<string, SitePreferences>> {
+    return await invoke("get_site_preferences");
+}
+
+export async function saveSitePreferences(rules: Record<string, SitePreferences>): Promise<void> {
+    return await invoke("save_site_preferences", { rules });
+}
+
+export async function setGithubToken(token: string | null): Promise<void> {
+    return await invoke("set_github_token", { token });
+}
+
+export async function hasGithubToken(): Promise<boolean> {
+    return await invoke("has_github_token");
+}
+
+export async function setCookiesText(text: string): Promise<string> {
+    return await invoke("set_cookies_text", { text });
+}
+
+export async function clearCookies(): Promise<void> {
+    return await invoke("clear_cookies");
+}
+
 // --- Downloader API ---
 
 export async function expandPlaylist(url: string): Promise<PlaylistResult> {
@@ -91,28 +155,80 @@ export async function startDownload(
   forceDownload: boolean = false,
   urlWhitelist: string[] | undefined,
   liveFromStart: boolean = false,
-  downloadSections?: string
+  downloadSections?: string,
+  musicMetadata: boolean = false,
+  recordInHistory: boolean = true,
+  titleFilter?: string,
+  formatSort?: string,
+  rateLimit?: string,
+  writeLiveChat: boolean = false,
+  writeComments: boolean = false,
+  audioQuality?: string,
+  embedSourceUrl: boolean = false,
+  downloadSubtitles: boolean = false,
+  autoSubtitles: boolean = false,
+  subtitleLangs?: string[],
+  embedSubtitles: boolean = false,
+  remuxOnly: boolean = false,
+  applySitePreferences: boolean = true
 ): Promise<StartDownloadResponse> { 
-  return await invoke("start_download", { 
-    url, 
-    downloadPath, 
-    formatPreset,
-    videoResolution,
-    embedMetadata,
-    embedThumbnail,
-    filenameTemplate,
-    restrictFilenames,
-    forceDownload,
-    urlWhitelist,
-    liveFromStart,
-    downloadSections
+  return await invoke("start_download", {
+    url,
+    options: {
+      downloadPath,
+      formatPreset,
+      videoResolution,
+      embedMetadata,
+      embedThumbnail,
+      filenameTemplate,
+      restrictFilenames,
+      forceDownload,
+      urlWhitelist,
+      liveFromStart,
+      downloadSections,
+      musicMetadata,
+      recordInHistory,
+      titleFilter,
+      formatSort,
+      rateLimit,
+      writeLiveChat,
+      writeComments,
+      audioQuality,
+      embedSourceUrl,
+      downloadSubtitles,
+      autoSubtitles,
+      subtitleLangs,
+      embedSubtitles,
+      remuxOnly,
+      applySitePreferences
+    }
   });
 }
 
+export async function estimateBatchSize(urls: string[], formatPreset: DownloadFormatPreset, videoResolution: string): Promise<BatchSizeEstimate> {
+    return await invoke("estimate_batch_size", { urls, formatPreset, videoResolution });
+}
+
 export async function cancelDownload(jobId: string): Promise<void> {
   return await invoke("cancel_download", { jobId });
 }
 
+export async function setJobRateLimit(jobId: string, limit: string | null): Promise<void> {
+    return await invoke("set_job_rate_limit", { jobId, limit });
+}
+
+export async function setJobMaxRetries(jobId: string, maxRetries: number): Promise<void> {
+    return await invoke("set_job_max_retries", { jobId, maxRetries });
+}
+
+export async function forceRetryNow(jobId: string): Promise<void> {
+    return await invoke("force_retry_now", { jobId });
+}
+
+export async function retryWithoutMerging(jobId: string): Promise<void> {
+    return await invoke("retry_without_merging", { jobId });
+}
+
 export async function resolveFileConflict(jobId: string, resolution: 'overwrite' | 'discard'): Promise<void> {
     return await invoke("resolve_file_conflict", { jobId, resolution });
 }
@@ -121,13 +237,17 @@ export async function syncDownloadState(): Promise<Download[]> {
     return await invoke("sync_download_state");
 }
 
+export async function generateErrorReport(jobId: string): Promise<string> {
+    return await invoke("generate_error_report", { jobId });
+}
+
 // --- Persistence API ---
 
 export async function getPendingJobs(): Promise<number> {
     return await invoke("get_pending_jobs");
 }
 
-export async function resumePendingJobs(): Promise<QueuedJob[]> {
+export async function resumePendingJobs(): Promise<ResumeResult> {
     return await invoke("resume_pending_jobs");
 }
 
@@ -135,6 +255,65 @@ export async function clearPendingJobs(): Promise<void> {
     return await invoke("clear_pending_jobs");
 }
 
+export async function clearFinishedPersistence(): Promise<number> {
+    return await invoke("clear_finished_persistence");
+}
+
+export async function pauseQueue(suspendRunning: boolean = false): Promise<QueueSummary> {
+    return await invoke("pause_queue", { suspendRunning });
+}
+
+export async function resumeQueue(): Promise<QueueSummary> {
+    return await invoke("resume_queue");
+}
+
+export async function getQueueSummary(): Promise<QueueSummary> {
+    return await invoke("get_queue_summary");
+}
+
+export async function getStats(): Promise<UsageStats> {
+    return await invoke("get_stats");
+}
+
+export async function getGroupProgress(): Promise<GroupProgress[]> {
+    return await invoke("get_group_progress");
+}
+
+// formatPreset and videoResolution default to the remembered preferences.
+export async function previewFilename(
+    url: string,
+    template?: string | null,
+    restrictFilenames = false,
+    formatPreset?: DownloadFormatPreset | null,
+    videoResolution?: string | null
+): Promise<string[]> {
+    return await invoke("preview_filename", { url, template, formatPreset, videoResolution, restrictFilenames });
+}
+
+// formatPreset and videoResolution default to the remembered preferences.
+export async function previewConflicts(
+    urls: string[],
+    downloadPath?: string | null,
+    template?: string | null,
+    restrictFilenames = false,
+    formatPreset?: DownloadFormatPreset | null,
+    videoResolution?: string | null
+): Promise<ConflictPreview> {
+    return await invoke("preview_conflicts", { urls, downloadPath, template, formatPreset, videoResolution, restrictFilenames });
+}
+
+export async function retryGroupFailures(groupId: string): Promise<GroupRetryResult> {
+    return await invoke("retry_group_failures", { groupId });
+}
+
+export async function relocateDownload(jobId: string, newDir: string, overwrite = false): Promise<string> {
+    return await invoke("relocate_download", { jobId, newDir, overwrite });
+}
+
+export async function canRetry(jobId: string): Promise<RetryEligibility> {
+    return await invoke("can_retry", { jobId });
+}
+
 export async function selectDirectory(): Promise<string | null> {
     const selected = await open({
         directory: true,
@@ -161,6 +340,10 @@ export async function saveDownloadHistory(content: string): Promise<void> {
     return await invoke("save_download_history", { content });
 }
 
+export async function normalizeUrl(url: string): Promise<string> {
+    return await invoke("normalize_url", { url });
+}
+
 // --- Logging API ---
 
 export async function logFrontendMessage(level: 'Info' | 'Warn' | 'Error' | 'Debug', message: string, context?: string): Promise<void> {
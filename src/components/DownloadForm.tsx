@@ -1,7 +1,7 @@
 import React, { useState, useRef, useEffect, useCallback } from 'react';
 import { Button } from './ui/Button';
 import { Card, CardContent } from './ui/Card';
-import { Download, FolderOpen, Link2, MonitorPlay, Headphones, FileText, Image as ImageIcon, AlertTriangle, Loader2, ChevronDown, Radio, ClipboardPaste, Clock } from 'lucide-react';
+import { Download, FolderOpen, Link2, MonitorPlay, Headphones, FileText, Image as ImageIcon, AlertTriangle, Loader2, ChevronDown, Radio, ClipboardPaste, Clock, Music } from 'lucide-react';
 import { selectDirectory, expandPlaylist } from '@/api/invoke';
 import { DownloadFormatPreset, PreferenceConfig, StartDownloadResponse, PlaylistEntry } from '@/types';
 import { useAppContext } from '@/contexts/AppContext';
@@ -25,7 +25,8 @@ interface DownloadFormProps {
       forceDownload: boolean,
       urlWhitelist?: string[],
       liveFromStart?: boolean,
-      downloadSections?: string
+      downloadSections?: string,
+      musicMetadata?: boolean
     ) => Promise<StartDownloadResponse>; 
 }
 
@@ -116,6 +117,7 @@ export function DownloadForm({ onDownload }: DownloadFormProps) {
 
   // Playlist state
   const [playlistEntries, setPlaylistEntries] = useState<PlaylistEntry[]>([]);
+  const [playlistDroppedCount, setPlaylistDroppedCount] = useState(0);
   const [isPlaylistModalOpen, setIsPlaylistModalOpen] = useState(false);
   const [pendingForce, setPendingForce] = useState(false); 
 
@@ -153,7 +155,8 @@ export function DownloadForm({ onDownload }: DownloadFormProps) {
               force, 
               whitelist, 
               preferences.live_from_start,
-              downloadSections
+              downloadSections,
+              preferences.music_metadata
           );
 
           if (response.skipped_count > 0) {
@@ -199,6 +202,7 @@ export function DownloadForm({ onDownload }: DownloadFormProps) {
               const result = await expandPlaylist(targetUrl);
               if (result.entries.length > 1) {
                   setPlaylistEntries(result.entries);
+                  setPlaylistDroppedCount(result.dropped_count);
                   setIsPlaylistModalOpen(true);
                   setIsProcessing(false);
                   return;
@@ -318,6 +322,7 @@ export function DownloadForm({ onDownload }: DownloadFormProps) {
             entries={playlistEntries}
             onConfirm={handlePlaylistConfirm}
             title="Configure Playlist Items"
+            droppedCount={playlistDroppedCount}
         />
 
         <form onSubmit={(e) => handleSubmit(e, false)} className="flex flex-col gap-6">
@@ -471,6 +476,23 @@ export function DownloadForm({ onDownload }: DownloadFormProps) {
                             Thumbnail
                          </button>
 
+                         {currentMode === 'audio' && (
+                             <button
+                                type="button"
+                                onClick={() => updatePreferences({ music_metadata: !preferences.music_metadata })}
+                                className={twMerge(
+                                    "flex-1 flex items-center justify-center gap-2 px-2 py-2.5 rounded-md border transition-all text-xs font-medium",
+                                    preferences.music_metadata
+                                        ? "bg-zinc-800 border-theme-red/50 text-theme-red"
+                                        : "bg-surfaceHighlight border-border text-zinc-500 hover:text-zinc-300"
+                                )}
+                                title="Tag Artist / Title / Album"
+                             >
+                                <Music className="h-3.5 w-3.5" />
+                                Tags
+                             </button>
+                         )}
+
                          <button
                             type="button"
                             onClick={() => updatePreferences({ live_from_start: !preferences.live_from_start })}
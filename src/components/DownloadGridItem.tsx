@@ -5,6 +5,7 @@ import { showInFolder } from '@/api/invoke';
 import { parseError } from '@/utils/errorRegistry';
 import { useState, useEffect } from 'react';
 import { useDownloadManager, progressEmitter, ProgressData } from '@/hooks/useDownloadManager';
+import { isProcessingPhaseKey, isMetaPhaseKey } from '@/utils/phase';
 
 interface DownloadGridItemProps {
   download: Download;
@@ -24,12 +25,14 @@ export function DownloadGridItem({ download, onCancel }: DownloadGridItemProps)
   // Localized Subscribed State
   const [localProgress, setLocalProgress] = useState(progressEmitter.get(jobId)?.progress ?? download.progress);
   const [localPhase, setLocalPhase] = useState(progressEmitter.get(jobId)?.phase ?? download.phase);
+  const [localPhaseKey, setLocalPhaseKey] = useState(progressEmitter.get(jobId)?.phaseKey ?? download.phaseKey);
   const [localStatus, setLocalStatus] = useState(progressEmitter.get(jobId)?.status ?? download.status);
 
   useEffect(() => {
       const handleProgress = (data: ProgressData) => {
           if (data.progress !== undefined) setLocalProgress(data.progress);
           if (data.phase !== undefined) setLocalPhase(data.phase);
+          if (data.phaseKey !== undefined) setLocalPhaseKey(data.phaseKey);
           if (data.status !== undefined) setLocalStatus(data.status);
       };
       progressEmitter.subscribe(jobId, handleProgress);
@@ -46,22 +49,12 @@ export function DownloadGridItem({ download, onCancel }: DownloadGridItemProps)
   const isError = localStatus === 'error';
   const isCompleted = localStatus === 'completed';
   const isModified = localStatus === 'modified';
-  const isCancelled = localStatus === 'cancelled';
+  // Skipped items produced no file, so they render like cancelled ones
+  const isCancelled = localStatus === 'cancelled' || localStatus === 'skipped';
   const isConflict = localStatus === 'file_conflict';
 
-  const isProcessingPhase = isActive && (
-       localPhase?.includes('Merging') 
-    || localPhase?.includes('Extracting') 
-    || localPhase?.includes('Fixing')
-    || localPhase?.includes('Moving')
-    || localPhase?.includes('Finalizing')
-    || localPhase?.includes('Processing')
-  );
-
-  const isMetaPhase = isActive && (
-       localPhase?.includes('Metadata') 
-    || localPhase?.includes('Thumbnail')
-  );
+  const isProcessingPhase = isActive && isProcessingPhaseKey(localPhaseKey);
+  const isMetaPhase = isActive && isMetaPhaseKey(localPhaseKey);
 
   // Parse error for tooltip
   let friendlyError = error;
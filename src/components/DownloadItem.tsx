@@ -3,10 +3,11 @@ import { Progress } from './ui/Progress';
 import { Button } from './ui/Button';
 import { X, MonitorPlay, Clock, CheckCircle2, AlertTriangle, Headphones, Activity, FileOutput, Tags, FileText, Image as ImageIcon, Hourglass, FolderSearch, Copy, Trash2, ChevronDown, ChevronUp, FileWarning, RefreshCw, HelpCircle } from 'lucide-react';
 import { twMerge } from 'tailwind-merge';
-import { showInFolder, openLogFolder } from '@/api/invoke';
+import { showInFolder, openLogFolder, generateErrorReport } from '@/api/invoke';
 import { useState, useEffect } from 'react';
 import { SmartError } from './ui/SmartError';
 import { useDownloadManager, progressEmitter, ProgressData } from '@/hooks/useDownloadManager';
+import { isProcessingPhaseKey, isMetaPhaseKey } from '@/utils/phase';
 
 interface DownloadItemProps {
   download: Download;
@@ -17,7 +18,7 @@ export function DownloadItem({ download, onCancel }: DownloadItemProps) {
   const { resolveConflict } = useDownloadManager();
   const { 
     jobId, url, error, filename, preset, embedMetadata, 
-    embedThumbnail, outputPath, stderr, logs, usedCommand 
+    embedThumbnail, outputPath, stderr, logs, usedCommand, queuePosition 
   } = download;
 
   // Localized Subscribed State
@@ -25,6 +26,7 @@ export function DownloadItem({ download, onCancel }: DownloadItemProps) {
   const [localSpeed, setLocalSpeed] = useState(progressEmitter.get(jobId)?.speed ?? download.speed);
   const [localEta, setLocalEta] = useState(progressEmitter.get(jobId)?.eta ?? download.eta);
   const [localPhase, setLocalPhase] = useState(progressEmitter.get(jobId)?.phase ?? download.phase);
+  const [localPhaseKey, setLocalPhaseKey] = useState(progressEmitter.get(jobId)?.phaseKey ?? download.phaseKey);
   const [localStatus, setLocalStatus] = useState(progressEmitter.get(jobId)?.status ?? download.status);
 
   useEffect(() => {
@@ -33,6 +35,7 @@ export function DownloadItem({ download, onCancel }: DownloadItemProps) {
           if (data.speed !== undefined) setLocalSpeed(data.speed);
           if (data.eta !== undefined) setLocalEta(data.eta);
           if (data.phase !== undefined) setLocalPhase(data.phase);
+          if (data.phaseKey !== undefined) setLocalPhaseKey(data.phaseKey);
           if (data.status !== undefined) setLocalStatus(data.status);
       };
       progressEmitter.subscribe(jobId, handleProgress);
@@ -48,7 +51,8 @@ export function DownloadItem({ download, onCancel }: DownloadItemProps) {
   const isError = localStatus === 'error';
   const isCompleted = localStatus === 'completed';
   const isModified = localStatus === 'modified';
-  const isCancelled = localStatus === 'cancelled';
+  // Skipped items produced no file, so they render like cancelled ones
+  const isCancelled = localStatus === 'cancelled' || localStatus === 'skipped';
   const isConflict = localStatus === 'file_conflict';
 
   const formatStat = (text?: string) => {
@@ -56,19 +60,8 @@ export function DownloadItem({ download, onCancel }: DownloadItemProps) {
       return text;
   };
 
-  const isProcessingPhase = isActive && (
-       localPhase?.includes('Merging') 
-    || localPhase?.includes('Extracting') 
-    || localPhase?.includes('Fixing')
-    || localPhase?.includes('Moving')
-    || localPhase?.includes('Finalizing')
-    || localPhase?.includes('Processing')
-  );
-
-  const isMetaPhase = isActive && (
-       localPhase?.includes('Metadata') 
-    || localPhase?.includes('Thumbnail')
-  );
+  const isProcessingPhase = isActive && isProcessingPhaseKey(localPhaseKey);
+  const isMetaPhase = isActive && isMetaPhaseKey(localPhaseKey);
 
   const getStatusColor = () => {
       if (isError) return "text-red-500 bg-red-500/10 border-red-500/20";
@@ -107,6 +100,9 @@ export function DownloadItem({ download, onCancel }: DownloadItemProps) {
 
   const handleOpenFolder = () => outputPath && showInFolder(outputPath).catch(console.error);
   const handleCopyLogs = () => logs && navigator.clipboard.writeText(logs);
+  const handleCopyReport = () => generateErrorReport(jobId)
+      .then(report => navigator.clipboard.writeText(report))
+      .catch(console.error);
 
   return (
     <div className={twMerge(
@@ -162,7 +158,7 @@ export function DownloadItem({ download, onCancel }: DownloadItemProps) {
                             getStatusColor()
                         )}>
                             {isActive && <Activity className={twMerge("h-3 w-3", (isProcessingPhase || isMetaPhase) && "animate-spin")} />}
-                            {isConflict ? "File Exists" : isModified ? "Modified" : (localPhase || (isQueued ? "Waiting" : localStatus))}
+                            {isConflict ? "File Exists" : isModified ? "Modified" : (localPhase || (isQueued ? (queuePosition !== undefined ? `Waiting #${queuePosition + 1}` : "Waiting") : localStatus))}
                         </span>
 
                         {/* Modified Help Icon */}
@@ -274,7 +270,7 @@ export function DownloadItem({ download, onCancel }: DownloadItemProps) {
             {/* Error Details Section */}
             {isError && (
                 <div className="mt-2 animate-fade-in">
-                    <SmartError error={error} stderr={stderr} />
+                    <SmartError error={error} stderr={stderr} jobId={jobId} />
                     
                     <div className="flex items-center gap-2 mt-2">
                          <button 
@@ -291,6 +287,13 @@ export function DownloadItem({ download, onCancel }: DownloadItemProps) {
                         >
                             Open Log Folder
                         </button>
+                        <div className="h-3 w-px bg-zinc-800" />
+                        <button 
+                             onClick={handleCopyReport}
+                             className="text-[10px] text-zinc-400 hover:text-zinc-200 transition-colors"
+                        >
+                            Copy Error Report
+                        </button>
                     </div>
 
                     {showLogs && logs && (
@@ -2,7 +2,7 @@ import { useState, useMemo, useEffect, useRef } from 'react';
 import { Modal } from './ui/Modal';
 import { Button } from './ui/Button';
 import { PlaylistEntry } from '@/types';
-import { Search, CheckSquare, Square, Download, X, ListFilter } from 'lucide-react';
+import { Search, CheckSquare, Square, Download, X, ListFilter, AlertTriangle } from 'lucide-react';
 import { twMerge } from 'tailwind-merge';
 
 interface PlaylistSelectionModalProps {
@@ -11,9 +11,10 @@ interface PlaylistSelectionModalProps {
     entries: PlaylistEntry[];
     onConfirm: (selectedUrls: string[]) => void;
     title?: string;
+    droppedCount?: number;
 }
 
-export function PlaylistSelectionModal({ isOpen, onClose, entries, onConfirm, title }: PlaylistSelectionModalProps) {
+export function PlaylistSelectionModal({ isOpen, onClose, entries, onConfirm, title, droppedCount = 0 }: PlaylistSelectionModalProps) {
     const [search, setSearch] = useState('');
     const [selectedIds, setSelectedIds] = useState<Set<string>>(new Set(entries.map(e => e.url)));
     const searchRef = useRef<HTMLInputElement>(null);
@@ -87,6 +88,13 @@ export function PlaylistSelectionModal({ isOpen, onClose, entries, onConfirm, ti
                         />
                     </div>
                     
+                    {droppedCount > 0 && (
+                        <div className="flex items-center gap-2 text-xs text-amber-400 bg-amber-950/20 border border-amber-500/30 rounded-md px-3 py-2">
+                            <AlertTriangle className="h-3.5 w-3.5 shrink-0" />
+                            <span>{droppedCount} item{droppedCount > 1 ? 's' : ''} could not be resolved to a URL and will be skipped.</span>
+                        </div>
+                    )}
+
                     <div className="flex items-center justify-between bg-zinc-900/50 p-3 rounded-lg border border-zinc-800">
                         <div className="flex items-center gap-6">
                             <button 
@@ -5,19 +5,14 @@ import icon from '@/assets/icon.webp';
 import { Loader2, Zap, ZapOff, PlayCircle } from 'lucide-react';
 import { Progress } from './ui/Progress';
 import { Button } from './ui/Button';
-
-interface InstallProgress {
-    name: string;
-    percentage: number;
-    status: string;
-}
+import { InstallProgress } from '@/types';
 
 type SplashStatus = 'init' | 'check-updates' | 'aria-prompt' | 'installing' | 'ready' | 'error';
 
 export function SplashWindow() {
   const [status, setStatus] = useState<SplashStatus>('init');
   const [message, setMessage] = useState('Checking system...');
-  const [installState, setInstallState] = useState<InstallProgress>({ name: '', percentage: 0, status: '' });
+  const [installState, setInstallState] = useState<InstallProgress>({ name: '', percentage: 0, phase: 'downloading', status: '' });
   const [errorDetails, setErrorDetails] = useState('');
   
   // State for the "Skip" button timeout logic
@@ -39,6 +34,13 @@ export function SplashWindow() {
 
         // 1. Instant Local Scan
         const scan = await checkLocalDeps();
+        const config = await getAppConfig();
+
+        // Lazy provisioning: missing tools are installed by the first download that needs them
+        if (config.general.dependency_provisioning === 'lazy') {
+            finishStartup();
+            return;
+        }
         
         // Add missing local binaries to pending
         scan.missing.forEach(dep => {
@@ -75,7 +77,6 @@ export function SplashWindow() {
 
         // 3. Lazy Aria2 Check
         // Only trigger if we have heavy lifting to do
-        const config = await getAppConfig();
         if (pendingInstalls.current.length > 0) {
             if (!scan.aria2_available && !config.general.aria2_prompt_dismissed) {
                 setStatus('aria-prompt');
@@ -109,7 +110,7 @@ export function SplashWindow() {
           for (const dep of pendingInstalls.current) {
               setMessage(`Installing ${dep}...`);
               // Reset progress bar for visual clarity
-              setInstallState({ name: dep, percentage: 0, status: 'Starting...' });
+              setInstallState({ name: dep, percentage: 0, phase: 'downloading', status: 'Starting...' });
               setShowDelayedText(false);
               await installDependency(dep);
           }
@@ -148,12 +149,17 @@ export function SplashWindow() {
       });
   };
 
-  const finishStartup = async () => {
+  const finishStartup = async (force: boolean = false) => {
       setStatus('ready');
-      setMessage('Launching...');
+      setMessage(force ? 'Launching...' : 'Waiting for dependencies...');
       setTimeout(async () => { 
           try {
-              await closeSplash(); 
+              const readiness = await closeSplash(force); 
+              if (!readiness.ready && !force) {
+                  setErrorDetails(`Required components are not available yet: ${readiness.missing.join(', ')}`);
+                  setMessage('Dependencies not ready.');
+                  setStatus('error');
+              }
           } catch (err) {
               console.error("Failed to transition window", err);
               setErrorDetails(`${err}`);
@@ -299,7 +305,7 @@ export function SplashWindow() {
                 </div>
                 <div className="flex gap-2">
                     <Button size="sm" className="flex-1" variant="secondary" onClick={() => { bootSequence(); }}>Retry</Button>
-                    <Button size="sm" className="flex-1" variant="ghost" onClick={finishStartup}>Skip</Button>
+                    <Button size="sm" className="flex-1" variant="ghost" onClick={() => finishStartup(true)}>Launch Anyway</Button>
                 </div>
             </div>
         )}
@@ -2,7 +2,7 @@ import { useEffect, useState } from 'react';
 import { getName } from '@tauri-apps/api/app';
 import { listen } from '@tauri-apps/api/event';
 import { checkDependencies, installDependency, cancelDependencyInstall, openExternalLink } from '@/api/invoke';
-import { DependencyInfo } from '@/types';
+import { DependencyInfo, InstallProgress } from '@/types';
 import { Copy, Check, Terminal, AlertCircle, Cpu, Download, Loader2, ArrowUpCircle, RefreshCw, Zap, Box, Lock, AlertTriangle, Trash2 } from 'lucide-react';
 import icon from '@/assets/icon.webp';
 import { Button } from '../ui/Button';
@@ -12,12 +12,6 @@ import { useAppContext } from '@/contexts/AppContext';
 import { useDownloadManager } from '@/hooks/useDownloadManager';
 import { twMerge } from 'tailwind-merge';
 
-interface InstallProgress {
-    name: string;
-    percentage: number;
-    status: string;
-}
-
 interface DependencyRowProps {
     info: DependencyInfo;
     onInstall?: () => void;
@@ -47,7 +41,7 @@ const DependencyRow = ({ info, onInstall, onCancel, installingState, label, desc
     );
 
     // Indeterminate State Logic: If "Native" download detected (0%), show spinner
-    const isNativeIndeterminate = isUpdatingThis && installingState.percentage === 0 && installingState.status.includes('Native');
+    const isNativeIndeterminate = isUpdatingThis && installingState.phase === 'downloading' && installingState.percentage === 0 && installingState.status.includes('Native');
 
     useEffect(() => {
         let timer: ReturnType<typeof setTimeout>;
@@ -226,7 +220,7 @@ export function AboutSettings() {
         fetchData();
 
         const unlisten = listen<InstallProgress>('install-progress', (event) => {
-            if (!event.payload.name) {
+            if (!event.payload.name || event.payload.phase === 'done') {
                 setActiveInstall(null);
             } else {
                 setActiveInstall(event.payload);
@@ -242,7 +236,7 @@ export function AboutSettings() {
         if (activeInstall || isQueueBusy) return;
 
         // Optimistic UI Update
-        setActiveInstall({ name, percentage: 0, status: 'Initializing...' });
+        setActiveInstall({ name, percentage: 0, phase: 'downloading', status: 'Initializing...' });
 
         try {
             await installDependency(name);
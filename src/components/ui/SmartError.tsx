@@ -1,4 +1,5 @@
 import { parseError } from '@/utils/errorRegistry';
+import { retryWithoutMerging } from '@/api/invoke';
 import { useAppContext } from '@/contexts/AppContext';
 import { Settings, ExternalLink, RefreshCw } from 'lucide-react';
 import { Button } from './Button';
@@ -6,9 +7,11 @@ import { Button } from './Button';
 interface SmartErrorProps {
     error?: string;
     stderr?: string;
+    // Needed for actions on the failed job, e.g. retrying without merging
+    jobId?: string;
 }
 
-export function SmartError({ error, stderr }: SmartErrorProps) {
+export function SmartError({ error, stderr, jobId }: SmartErrorProps) {
     const { openSettings } = useAppContext();
     const { title, description, actionLabel, actionType, actionTarget, rawMatches } = parseError(stderr, error);
 
@@ -18,6 +21,8 @@ export function SmartError({ error, stderr }: SmartErrorProps) {
             openSettings(tab, section);
         } else if (actionType === 'OPEN_URL' && actionTarget) {
             window.open(actionTarget, '_blank');
+        } else if (actionType === 'RETRY_WITHOUT_MERGE' && jobId) {
+            retryWithoutMerging(jobId).catch(e => console.error("Retry without merging failed", e));
         }
     };
 
@@ -39,7 +44,7 @@ export function SmartError({ error, stderr }: SmartErrorProps) {
                     </div>
                 </div>
 
-                {actionLabel && (
+                {actionLabel && (actionType !== 'RETRY_WITHOUT_MERGE' || jobId) && (
                     <div className="mt-3 pt-2 border-t border-white/5 flex">
                         <Button 
                             size="sm" 
@@ -49,7 +54,7 @@ export function SmartError({ error, stderr }: SmartErrorProps) {
                         >
                             {actionType === 'OPEN_SETTINGS' && <Settings className="h-3 w-3 mr-1.5" />}
                             {actionType === 'OPEN_URL' && <ExternalLink className="h-3 w-3 mr-1.5" />}
-                            {actionType === 'RETRY_WITH_AUTH' && <RefreshCw className="h-3 w-3 mr-1.5" />}
+                            {(actionType === 'RETRY_WITH_AUTH' || actionType === 'RETRY_WITHOUT_MERGE') && <RefreshCw className="h-3 w-3 mr-1.5" />}
                             {actionLabel}
                         </Button>
                     </div>
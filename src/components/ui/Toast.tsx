@@ -1,17 +1,20 @@
 import { useEffect, useState, useRef } from 'react';
 import { useAppContext } from '@/contexts/AppContext';
-import { X, Download, PartyPopper, PlayCircle, Trash2, AlertTriangle, FileText } from 'lucide-react';
+import { X, Download, PartyPopper, PlayCircle, Trash2, AlertTriangle, FileText, RefreshCw } from 'lucide-react';
+import { listen } from '@tauri-apps/api/event';
 import { openExternalLink, getPendingJobs, resumePendingJobs, clearPendingJobs, openLogFolder } from '@/api/invoke';
 import { Button } from './Button';
 import { useDownloadManager } from '@/hooks/useDownloadManager';
+import { YtDlpUpdatePayload } from '@/types';
 
 export function Toast() {
     const { isUpdateAvailable, latestVersion, currentVersion } = useAppContext();
     const { importResumedJobs, downloads } = useDownloadManager();
     
     const [visible, setVisible] = useState(false);
-    const [mode, setMode] = useState<'update' | 'resume' | 'error' | null>(null);
+    const [mode, setMode] = useState<'update' | 'resume' | 'error' | 'ytdlp' | null>(null);
     const [pendingCount, setPendingCount] = useState(0);
+    const [ytdlpVersion, setYtdlpVersion] = useState<string | null>(null);
 
     const errorCount = Array.from(downloads.values()).filter(d => d.status === 'error').length;
     const prevErrorCountRef = useRef(0);
@@ -36,6 +39,21 @@ export function Toast() {
         return () => { if (timer) clearTimeout(timer); };
     }, [errorCount]);
 
+    // Background yt-dlp auto-update finished
+    useEffect(() => {
+        let timer: ReturnType<typeof setTimeout>;
+        const unlisten = listen<YtDlpUpdatePayload>('update-complete', (event) => {
+            setYtdlpVersion(event.payload.newVersion);
+            setMode('ytdlp');
+            setVisible(true);
+            timer = setTimeout(() => setVisible(false), 6000);
+        });
+        return () => {
+            if (timer) clearTimeout(timer);
+            unlisten.then(f => f());
+        };
+    }, []);
+
     useEffect(() => {
         let timers: ReturnType<typeof setTimeout>[] = [];
         
@@ -76,9 +94,9 @@ export function Toast() {
 
     const handleResume = async () => {
         // 1. Trigger backend resume (returns full job details)
-        const resumedJobs = await resumePendingJobs();
+        const { resumed } = await resumePendingJobs();
         // 2. Hydrate frontend state immediately
-        importResumedJobs(resumedJobs);
+        importResumedJobs(resumed);
         setVisible(false);
     };
 
@@ -104,11 +122,13 @@ export function Toast() {
                         {mode === 'update' && <PartyPopper className="h-5 w-5" />}
                         {mode === 'resume' && <PlayCircle className="h-5 w-5" />}
                         {mode === 'error' && <AlertTriangle className="h-5 w-5" />}
+                        {mode === 'ytdlp' && <RefreshCw className="h-5 w-5" />}
                         
                         <span>
                             {mode === 'update' && 'Update Available'}
                             {mode === 'resume' && 'Incomplete Downloads'}
                             {mode === 'error' && `${errorCount} Failed Download${errorCount > 1 ? 's' : ''}`}
+                            {mode === 'ytdlp' && 'yt-dlp Updated'}
                         </span>
                     </div>
                     <button 
@@ -153,6 +173,12 @@ export function Toast() {
                     </>
                 )}
                 
+                {mode === 'ytdlp' && (
+                    <div className="text-sm text-zinc-300">
+                        yt-dlp updated{ytdlpVersion ? <> to <span className="font-mono text-theme-cyan">{ytdlpVersion}</span></> : ''}.
+                    </div>
+                )}
+
                 {mode === 'resume' && (
                     <>
                         <div className="text-sm text-zinc-300">
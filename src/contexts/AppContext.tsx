@@ -1,7 +1,8 @@
 import React, { useState, useEffect, useCallback, useRef } from 'react';
-import { TemplateBlock, PreferenceConfig, GeneralConfig } from '@/types';
+import { TemplateBlock, PreferenceConfig, GeneralConfig, AppConfig } from '@/types';
 import { getAppConfig, saveGeneralConfig, savePreferenceConfig, checkDependencies, getLatestAppVersion } from '@/api/invoke';
 import { getVersion } from '@tauri-apps/api/app';
+import { listen } from '@tauri-apps/api/event';
 
 export interface SkipNotice {
     skipped: number;
@@ -79,7 +80,8 @@ const DEFAULT_PREFS: PreferenceConfig = {
     embed_metadata: false,
     embed_thumbnail: false,
     live_from_start: false,
-    enable_playlist_selection: true
+    enable_playlist_selection: true,
+    music_metadata: false
 };
 
 export const AppContext = React.createContext<AppContextType | undefined>(undefined);
@@ -119,6 +121,8 @@ export const AppProvider = ({ children }: { children: React.ReactNode }) => {
   const [aria2PromptDismissed, _setAria2PromptDismissed] = useState(false);
 
   const saveTimeoutRef = useRef<ReturnType<typeof setTimeout> | null>(null);
+  // Last config loaded from the backend; keeps fields without UI state intact on save.
+  const loadedGeneralRef = useRef<Partial<GeneralConfig>>({});
 
   const openSettings = useCallback((tab?: string, sectionId?: string) => {
     if (tab) setSettingsActiveTab(tab);
@@ -164,9 +168,8 @@ export const AppProvider = ({ children }: { children: React.ReactNode }) => {
   };
 
   useEffect(() => {
-    const load = async () => {
-      try {
-        const config = await getAppConfig();
+    const applyConfig = (config: AppConfig) => {
+        loadedGeneralRef.current = config.general;
         
         if (config.general.download_path) _setDownloadPath(config.general.download_path);
         if (config.general.cookies_path) _setCookiesPath(config.general.cookies_path);
@@ -190,6 +193,11 @@ export const AppProvider = ({ children }: { children: React.ReactNode }) => {
         }
 
         _setPreferences({ ...DEFAULT_PREFS, ...config.preferences });
+    };
+
+    const load = async () => {
+      try {
+        applyConfig(await getAppConfig());
         
         const deps = await checkDependencies();
         if (!deps.js_runtime.available) {
@@ -206,6 +214,10 @@ export const AppProvider = ({ children }: { children: React.ReactNode }) => {
       }
     };
     load();
+
+    // Manual edits to config.json applied through reload_config.
+    const unlisten = listen<AppConfig>('config-reloaded', (event) => applyConfig(event.payload));
+    return () => { unlisten.then(f => f()); };
   }, []);
 
   useEffect(() => {
@@ -234,6 +246,7 @@ export const AppProvider = ({ children }: { children: React.ReactNode }) => {
 
       saveTimeoutRef.current = setTimeout(() => {
           const config: GeneralConfig = {
+            ...(loadedGeneralRef.current as GeneralConfig),
             download_path: defaultDownloadPath,
             filename_template: getTemplateString(filenameTemplateBlocks),
             template_blocks_json: JSON.stringify(filenameTemplateBlocks),
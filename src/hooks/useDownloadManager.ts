@@ -1,8 +1,9 @@
 import { useState, useEffect, useCallback, useRef } from 'react';
 import { listen } from '@tauri-apps/api/event';
-import { Download, DownloadCompletePayload, DownloadErrorPayload, BatchProgressPayload, DownloadFormatPreset, QueuedJob, DownloadCancelledPayload, StartDownloadResponse, DownloadStatus } from '@/types';
+import { Download, DownloadCompletePayload, DownloadErrorPayload, BatchProgressPayload, DownloadFormatPreset, QueuedJob, DownloadCancelledPayload, StartDownloadResponse, DownloadStatus, QueuePreparingPayload, QueuePreparedPayload, JobPhase, BatchHaltedPayload } from '@/types';
 import { startDownload as apiStartDownload, cancelDownload as apiCancelDownload, resolveFileConflict as apiResolveConflict, syncDownloadState } from '@/api/invoke';
 import { useAppContext } from '@/contexts/AppContext';
+import { formatPhase } from '@/utils/phase';
 
 // --- DECOUPLED PROGRESS PUB/SUB ---
 export type ProgressData = {
@@ -10,6 +11,7 @@ export type ProgressData = {
     speed?: string;
     eta?: string;
     phase?: string;
+    phaseKey?: JobPhase;
     status?: DownloadStatus;
 };
 
@@ -47,6 +49,8 @@ export const progressEmitter = new ProgressEmitter();
 export function useDownloadManager() {
   const { maxConcurrentDownloads } = useAppContext();
   const [downloads, setDownloads] = useState<Map<string, Download>>(new Map());
+  // URLs currently being probed/expanded by start_download
+  const [preparingUrls, setPreparingUrls] = useState<Set<string>>(new Set());
   const hasSynced = useRef(false);
   const downloadsRef = useRef(downloads);
 
@@ -116,7 +120,9 @@ export function useDownloadManager() {
                         if (localJob && localJob.sequence_id > remoteJob.sequence_id) {
                             return;
                         }
-                        newMap.set(remoteJob.jobId, remoteJob);
+                        // sync_download_state carries the phase key; the label is derived here.
+                        const phaseKey = remoteJob.phase as JobPhase | undefined;
+                        newMap.set(remoteJob.jobId, { ...remoteJob, phaseKey, phase: formatPhase(phaseKey, remoteJob.phase_detail) });
                     });
                     return newMap;
                 });
@@ -130,13 +136,15 @@ export function useDownloadManager() {
 
         event.payload.updates.forEach(u => {
             const currentGlobal = downloadsRef.current.get(u.jobId);
+            const phase = formatPhase(u.phase, u.phase_detail);
             
             // 1. Emit locally (Zero React Overhead)
             progressEmitter.emit(u.jobId, {
                 progress: u.percentage,
                 speed: u.speed,
                 eta: u.eta,
-                phase: u.phase || undefined,
+                phase,
+                phaseKey: u.phase || undefined,
                 status: u.status || undefined,
             });
 
@@ -152,7 +160,8 @@ export function useDownloadManager() {
                         data: {
                             status: u.status || currentGlobal.status,
                             filename: u.filename || currentGlobal.filename,
-                            phase: u.phase || currentGlobal.phase,
+                            phase: phase || currentGlobal.phase,
+                            phaseKey: u.phase || currentGlobal.phaseKey,
                             sequence_id: u.sequence_id
                         }
                     });
@@ -197,11 +206,34 @@ export function useDownloadManager() {
         });
     });
 
+    // Follows the individual cancellations; relabel them so it's clear the user didn't cancel.
+    const unlistenHalted = listen<BatchHaltedPayload>('batch-halted', (event) => {
+        for (const jobId of event.payload.cancelledJobIds) {
+            progressEmitter.emit(jobId, { phase: 'Stopped after another download failed' });
+            updateDownload(jobId, { phase: 'Stopped after another download failed' });
+        }
+    });
+
+    const unlistenPreparing = listen<QueuePreparingPayload>('queue-preparing', (event) => {
+        setPreparingUrls(prev => new Set(prev).add(event.payload.url));
+    });
+
+    const unlistenPrepared = listen<QueuePreparedPayload>('queue-prepared', (event) => {
+        setPreparingUrls(prev => {
+            const next = new Set(prev);
+            next.delete(event.payload.url);
+            return next;
+        });
+    });
+
     return () => {
+      unlistenPreparing.then((f) => f());
+      unlistenPrepared.then((f) => f());
       unlistenProgress.then((f) => f());
       unlistenComplete.then((f) => f());
       unlistenError.then((f) => f());
       unlistenCancelled.then((f) => f());
+      unlistenHalted.then((f) => f());
     };
   }, [updateDownloadsBatch, updateDownload]);
 
@@ -217,7 +249,8 @@ export function useDownloadManager() {
     forceDownload: boolean = false,
     urlWhitelist?: string[],
     liveFromStart: boolean = false,
-    downloadSections?: string
+    downloadSections?: string,
+    musicMetadata: boolean = false
   ): Promise<StartDownloadResponse> => {
     try {
       const response = await apiStartDownload(
@@ -232,7 +265,8 @@ export function useDownloadManager() {
           forceDownload,
           urlWhitelist,
           liveFromStart,
-          downloadSections
+          downloadSections,
+          musicMetadata
       ); 
       
       setDownloads((prev) => {
@@ -254,6 +288,7 @@ export function useDownloadManager() {
                     restrictFilenames,
                     liveFromStart,
                     downloadSections,
+                    musicMetadata,
                 });
             } else {
                 newMap.set(jobId, {
@@ -272,6 +307,7 @@ export function useDownloadManager() {
                     restrictFilenames,
                     liveFromStart,
                     downloadSections,
+                    musicMetadata,
                 });
             }
         });
@@ -315,7 +351,8 @@ export function useDownloadManager() {
                   embedThumbnail: job.embed_thumbnail,
                   restrictFilenames: job.restrict_filenames,
                   liveFromStart: job.live_from_start,
-                  downloadSections: job.download_sections
+                  downloadSections: job.download_sections,
+                  musicMetadata: job.music_metadata
               });
           });
           return newMap;
@@ -377,5 +414,5 @@ export function useDownloadManager() {
       }
   }, [updateDownload]);
 
-  return { downloads, startDownload, cancelDownload, removeDownload, importResumedJobs, cancelAllDownloads, resolveConflict };
+  return { downloads, preparingUrls, startDownload, cancelDownload, removeDownload, importResumedJobs, cancelAllDownloads, resolveConflict };
 }
\ No newline at end of file
@@ -11,6 +11,33 @@ export interface GeneralConfig {
   aria2_prompt_dismissed: boolean;
   use_concurrent_fragments: boolean;
   concurrent_fragments: number;
+  use_aria2_for_media: boolean;
+  proxy_url: string | null;
+  no_proxy: boolean;
+  no_check_certificate: boolean;
+  date_folder_format: string | null;
+  max_filesize: string | null;
+  min_duration: number | null;
+  max_duration: number | null;
+  offline_mode: boolean;
+  dependency_provisioning: 'eager' | 'lazy';
+  transport_engine: 'auto' | 'aria2' | 'native';
+  fatal_error_patterns: string[];
+  write_playlist_m3u: boolean;
+  write_cover_art: boolean;
+  overwrite_cover_art: boolean;
+  format_sort: string | null;
+  post_download_hook: string | null;
+  allowed_domains: string[] | null;
+  max_history_entries: number;
+  age_limit: number | null;
+  speed_smoothing: number;
+  stop_on_first_error: boolean;
+  log_rotation: 'launch' | 'daily';
+  log_archive_pattern: string;
+  log_archive_keep: number;
+  rate_limit: string | null;
+  min_free_space_mb: number;
 }
 
 export interface PreferenceConfig {
@@ -23,6 +50,7 @@ export interface PreferenceConfig {
   embed_thumbnail: boolean;
   live_from_start: boolean;
   enable_playlist_selection: boolean;
+  music_metadata: boolean;
 }
 
 export interface WindowConfig {
@@ -33,10 +61,26 @@ export interface WindowConfig {
   is_maximized: boolean;
 }
 
+export interface SitePreferences {
+  format_preset?: string | null;
+  video_resolution?: string | null;
+  embed_metadata?: boolean | null;
+  embed_thumbnail?: boolean | null;
+  live_from_start?: boolean | null;
+  music_metadata?: boolean | null;
+}
+
 export interface AppConfig {
   general: GeneralConfig;
   preferences: PreferenceConfig;
   window: WindowConfig;
+  site_preferences: Record<string, SitePreferences>;
+}
+
+export interface ConfigIssue {
+  field: string;
+  severity: 'warning' | 'error';
+  message: string;
 }
 
 export interface DependencyInfo {
@@ -49,6 +93,16 @@ export interface DependencyInfo {
     is_latest: boolean;
 }
 
+export interface YtDlpCapabilities {
+    ytdlp_version: string | null;
+    ytdlp_path: string | null;
+    js_runtime: DependencyInfo;
+    ffmpeg_available: boolean;
+    ffprobe_available: boolean;
+    ffmpeg_version: string | null;
+    postprocessors: string[];
+}
+
 export interface AppDependencies {
   yt_dlp: DependencyInfo;
   ffmpeg: DependencyInfo;
@@ -56,6 +110,20 @@ export interface AppDependencies {
   aria2: DependencyInfo;
 }
 
+export type InstallPhase = 'downloading' | 'extracting' | 'verifying' | 'done' | 'failed';
+
+export interface InstallProgress {
+  name: string;
+  percentage: number;
+  phase: InstallPhase;
+  status: string;
+}
+
+export interface DependencyReadiness {
+  ready: boolean;
+  missing: string[];
+}
+
 export type AppError = {
   IoError?: string;
   ProcessFailed?: { exit_code: number; stderr: string };
@@ -73,13 +141,105 @@ export type DownloadFormatPreset =
   | 'audio_flac'
   | 'audio_m4a';
 
+export interface SizeEstimateItem {
+    url: string;
+    title: string | null;
+    bytes: number | null;
+    exact: boolean;
+    error: string | null;
+}
+
+export interface BatchSizeEstimate {
+    total_bytes: number;
+    unknown_count: number;
+    items: SizeEstimateItem[];
+}
+
+export interface FileConflictItem {
+    url: string;
+    filename: string;
+    path: string;
+}
+
+export interface UnresolvedUrl {
+    url: string;
+    error: string;
+}
+
+export interface ConflictPreview {
+    checked_count: number;
+    conflicts: FileConflictItem[];
+    unresolved: UnresolvedUrl[];
+}
+
 export interface StartDownloadResponse {
     job_ids: string[];
     skipped_count: number;
     total_found: number;
     skipped_urls: string[];
+    filtered_count: number;
+}
+
+export interface AppPaths {
+    config_dir: string;
+    log_dir: string;
+    history_file: string;
+    temp_dir: string;
+    bin_dir: string;
+    persistence_file: string;
+}
+
+export interface AppUpdateInfo {
+    current: string;
+    latest: string;
+    update_available: boolean;
+    release_url: string;
 }
 
+export interface QueueSummary {
+    paused: boolean;
+    suspendedCount: number;
+    queuedCount: number;
+    activeCount: number;
+    lowDiskSpace: boolean;
+}
+
+export interface UsageCounters {
+    completed: number;
+    failed: number;
+    retries: number;
+    bytesDownloaded: number;
+}
+
+export interface UsageStats {
+    session: UsageCounters;
+    lifetime: UsageCounters;
+}
+
+export type JobPhase =
+  | 'queued'
+  | 'initializing'
+  | 'checking_dependency'
+  | 'retrying_sanitized'
+  | 'format_fallback'
+  | 'starting_download'
+  | 'downloading'
+  | 'parsing_metadata'
+  | 'downloading_comments'
+  | 'writing_metadata'
+  | 'embedding_thumbnail'
+  | 'merging'
+  | 'extracting_audio'
+  | 'fixing_container'
+  | 'remuxing'
+  | 'post_processing'
+  | 'finalizing'
+  | 'moving_to_library'
+  | 'file_conflict'
+  | 'done'
+  | 'discarded'
+  | 'skipped';
+
 export interface DownloadProgressPayload {
   jobId: string;
   percentage: number;
@@ -87,7 +247,8 @@ export interface DownloadProgressPayload {
   speed: string;
   eta: string;
   filename?: string; 
-  phase?: string;    
+  phase?: JobPhase;
+  phase_detail?: string;
   status?: DownloadStatus;
 }
 
@@ -102,10 +263,32 @@ export interface DownloadCompletePayload {
   usedCommand?: string;
 }
 
+export interface QueuePreparingPayload {
+    url: string;
+}
+
+export interface QueuePreparedPayload {
+    url: string;
+    total: number;
+    success: boolean;
+}
+
 export interface DownloadCancelledPayload {
     jobId: string;
 }
 
+export interface BatchHaltedPayload {
+  failedJobId: string;
+  cancelledJobIds: string[];
+}
+
+export interface LowDiskSpacePayload {
+  path: string;
+  freeBytes: number;
+  minFreeBytes: number;
+  suspendedCount: number;
+}
+
 export interface DownloadErrorPayload {
   jobId: string;
   error: string;
@@ -114,7 +297,7 @@ export interface DownloadErrorPayload {
   logs: string;
 }
 
-export type DownloadStatus = 'pending' | 'downloading' | 'completed' | 'modified' | 'error' | 'cancelled' | 'file_conflict';
+export type DownloadStatus = 'pending' | 'downloading' | 'completed' | 'modified' | 'error' | 'cancelled' | 'file_conflict' | 'skipped';
 
 export interface Download {
   jobId: string;
@@ -129,6 +312,8 @@ export interface Download {
   error?: string;
   filename?: string;
   phase?: string;
+  phaseKey?: JobPhase;
+  phase_detail?: string;
   
   // Error Details
   exit_code?: number;
@@ -144,7 +329,42 @@ export interface Download {
   restrictFilenames?: boolean;
   liveFromStart?: boolean;
   downloadSections?: string;
+  musicMetadata?: boolean;
   usedCommand?: string;
+  queuePosition?: number;
+  groupId?: string | null;
+  groupTotal?: number | null;
+  rateLimit?: string | null;
+  hookExitCode?: number | null;
+  hookOutput?: string | null;
+  retryCount?: number;
+  maxRetries?: number;
+}
+
+export interface GroupProgress {
+  groupId: string;
+  title: string | null;
+  total: number;
+  completed: number;
+  failed: number;
+  cancelled: number;
+  percentage: number;
+}
+
+export interface GroupRetryResult {
+  requeued: number;
+  skippedFatal: number;
+  stillUnavailable: number;
+}
+
+export interface ResumeResult {
+  resumed: QueuedJob[];
+  alreadyActive: string[];
+}
+
+export interface RetryEligibility {
+  can_retry: boolean;
+  reason: string | null;
 }
 
 export interface QueuedJob {
@@ -159,6 +379,21 @@ export interface QueuedJob {
   restrict_filenames: boolean;
   live_from_start: boolean;
   download_sections?: string;
+  music_metadata?: boolean;
+  group_id?: string | null;
+  group_total?: number | null;
+  group_index?: number | null;
+  group_title?: string | null;
+  format_sort?: string | null;
+  rate_limit?: string | null;
+  write_live_chat?: boolean;
+  write_comments?: boolean;
+  embed_source_url?: boolean;
+  download_subtitles?: boolean;
+  auto_subtitles?: boolean;
+  subtitle_langs?: string[] | null;
+  embed_subtitles?: boolean;
+  remux_only?: boolean;
   status?: string;
   error?: string;
   stderr?: string;
@@ -180,10 +415,12 @@ export interface PlaylistEntry {
 }
 
 export interface PlaylistResult {
+    title: string | null;
     entries: PlaylistEntry[];
+    dropped_count: number;
 }
 
-export type ErrorActionType = 'OPEN_SETTINGS' | 'OPEN_URL' | 'RETRY_WITH_AUTH';
+export type ErrorActionType = 'OPEN_SETTINGS' | 'OPEN_URL' | 'RETRY_WITH_AUTH' | 'RETRY_WITHOUT_MERGE';
 
 export interface ErrorPattern {
   id: string;
@@ -193,4 +430,9 @@ export interface ErrorPattern {
   actionLabel?: string;
   actionType?: ErrorActionType;
   actionTarget?: string;
-}
\ No newline at end of file
+}
+
+export interface YtDlpUpdatePayload {
+  oldVersion: string | null;
+  newVersion: string | null;
+}
@@ -67,6 +67,29 @@ export const ERROR_PATTERNS: ErrorPattern[] = [
         actionType: "OPEN_SETTINGS",
         actionTarget: "about:section-deps"
     },
+    {
+        id: "merge_failed",
+        pattern: /(Merging failed|Unable to merge|\[Merger\].*ERROR)/i,
+        title: "Merging Failed",
+        description: "The video and audio streams downloaded, but FFmpeg could not combine them. You can download them as separate files and merge them yourself.",
+        actionLabel: "Retry Without Merging",
+        actionType: "RETRY_WITHOUT_MERGE"
+    },
+    {
+        id: "remux_failed",
+        pattern: /Remux failed/i,
+        title: "Remux Failed",
+        description: "The streams use codecs that this container can't hold without re-encoding. Pick MKV, or turn off remux-only to allow conversion."
+    },
+    {
+        id: "postprocessing_failed",
+        pattern: /(Post-processing failed|ERROR: Postprocessing|Conversion failed)/i,
+        title: "Post-processing Failed",
+        description: "The media downloaded, but FFmpeg could not merge or convert it. Updating FFmpeg usually fixes this.",
+        actionLabel: "Check Dependencies",
+        actionType: "OPEN_SETTINGS",
+        actionTarget: "about:section-deps"
+    },
     {
         id: "network_error",
         pattern: /(Network problem|Connection reset|timed out|EOF occurred in violation of protocol)/i,
@@ -0,0 +1,53 @@
+import { JobPhase } from "@/types";
+
+const PHASE_LABELS: Record<JobPhase, string> = {
+    queued: "Queued",
+    initializing: "Initializing Process...",
+    checking_dependency: "Checking Dependencies...",
+    retrying_sanitized: "Sanitizing Filenames (Retry)",
+    format_fallback: "Fallback (Loose Format)",
+    starting_download: "Starting Download",
+    downloading: "Downloading",
+    parsing_metadata: "Parsing Metadata",
+    downloading_comments: "Downloading comments...",
+    writing_metadata: "Writing Metadata",
+    embedding_thumbnail: "Embedding Thumbnail",
+    merging: "Merging Formats",
+    extracting_audio: "Extracting Audio",
+    fixing_container: "Fixing Container",
+    remuxing: "Remuxing (No Re-encode)",
+    post_processing: "Processing (FFmpeg)",
+    finalizing: "Finalizing",
+    moving_to_library: "Moving to Library",
+    file_conflict: "File Exists - Action Required",
+    done: "Done",
+    discarded: "Discarded",
+    skipped: "Skipped",
+};
+
+/**
+ * Display text for a backend job phase. The backend only sends the phase key
+ * and optional detail so wording (and translation) lives here.
+ */
+export function formatPhase(phase?: JobPhase | null, detail?: string | null): string | undefined {
+    if (!phase) return undefined;
+    switch (phase) {
+        case 'checking_dependency':
+            return detail ? `Checking ${detail}...` : PHASE_LABELS[phase];
+        case 'format_fallback':
+            if (detail === 'format') return "Format Unavailable (Retrying Best)";
+            return detail === '2' ? "Fallback Level 2 (Any Format)" : "Fallback Level 1 (Loose Format)";
+        case 'downloading':
+            return detail ? `Downloading (fragment ${detail})` : PHASE_LABELS[phase];
+        case 'skipped':
+            return detail ? `Skipped (${detail})` : PHASE_LABELS[phase];
+        default:
+            return PHASE_LABELS[phase] ?? phase;
+    }
+}
+
+const PROCESSING_PHASES: JobPhase[] = ['merging', 'extracting_audio', 'fixing_container', 'remuxing', 'post_processing', 'finalizing', 'moving_to_library'];
+const META_PHASES: JobPhase[] = ['parsing_metadata', 'downloading_comments', 'writing_metadata', 'embedding_thumbnail'];
+
+export const isProcessingPhaseKey = (phase?: JobPhase) => !!phase && PROCESSING_PHASES.includes(phase);
+export const isMetaPhaseKey = (phase?: JobPhase) => !!phase && META_PHASES.includes(phase);